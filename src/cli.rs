@@ -0,0 +1,351 @@
+use crate::models::file_items::FileSystemItem;
+use crate::utils::bundle::{self, BundleOptions};
+use crate::utils::collections::CollectionStore;
+use crate::utils::config::AppConfig;
+use crate::utils::history::{self, asset_key};
+use crate::utils::metadata::MetadataStore;
+use crate::utils::lint::{self, LintRules};
+use crate::utils::sanitize;
+use crate::utils::server;
+use crate::utils::site;
+use crate::utils::svg_validator::Severity;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const USAGE: &str = "Usage: asset_gui <command> [options]
+
+Without a command the window opens.
+
+Commands:
+  bundle <output.zip> [paths...]   Package SVGs into a ZIP with manifest.json and LICENSE.txt
+      --collection <name>          Add the assets of a collection
+      --no-svg                     Leave out the original SVGs
+      --minified                   Add minified SVGs
+      --png <sizes>                Add PNGs, e.g. --png 32,64,128
+      --license <file>             Use this license text instead of the asset metadata
+  site <output-dir> [paths...]     Generate a static website to browse, search and copy the icons
+      --collection <name>          Add the assets of a collection
+      --title <text>               Page title, defaults to the vault name
+      --png <sizes>                Offer PNG downloads, e.g. --png 64,256
+  lint [paths...]                  Check SVGs against the vault's lint rules, exits with 1 on errors
+      --strict                     Fail on warnings too
+  sanitize [paths...]              Strip scripts, event handlers and external references from SVGs
+      --check                      Only list them, exits with 1 when any are found
+  serve                            Serve the vault over HTTP on localhost until stopped
+      --port <number>              Port to listen on, defaults to the one set in the app
+  help                             Show this message
+
+Without paths, site, lint and sanitize cover the whole vault.
+
+Common options:
+  --vault <dir>                    Vault to work on, defaults to the one set in the app";
+
+// Command line arguments split into positional values and `--name value` options
+struct Args {
+    positional: Vec<String>,
+    options: Vec<(String, Option<String>)>,
+}
+
+impl Args {
+    // `flags` take no value, `options` take one, either as `--name value` or `--name=value`
+    fn parse(args: &[String], flags: &[&str], options: &[&str]) -> Result<Self, String> {
+        let mut parsed = Args { positional: Vec::new(), options: Vec::new() };
+        let mut iter = args.iter();
+
+        while let Some(arg) = iter.next() {
+            let Some(option) = arg.strip_prefix("--") else {
+                parsed.positional.push(arg.clone());
+                continue;
+            };
+
+            let (name, inline) = match option.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (option, None),
+            };
+
+            if flags.contains(&name) && inline.is_none() {
+                parsed.options.push((name.to_string(), None));
+            } else if options.contains(&name) || name == "vault" {
+                let value = match inline {
+                    Some(value) => value,
+                    None => iter.next().cloned().ok_or_else(|| format!("--{} needs a value", name))?,
+                };
+                parsed.options.push((name.to_string(), Some(value)));
+            } else {
+                return Err(format!("Unknown option --{}", name));
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    fn flag(&self, name: &str) -> bool {
+        self.options.iter().any(|(option, _)| option == name)
+    }
+
+    fn value(&self, name: &str) -> Option<&str> {
+        self.values(name).last().copied()
+    }
+
+    fn values(&self, name: &str) -> Vec<&str> {
+        self.options
+            .iter()
+            .filter(|(option, _)| option == name)
+            .filter_map(|(_, value)| value.as_deref())
+            .collect()
+    }
+
+    // --vault, or the vault configured in the app
+    fn vault(&self) -> Result<PathBuf, String> {
+        let vault = match self.value("vault") {
+            Some(vault) => PathBuf::from(vault),
+            None => PathBuf::from(AppConfig::load().vault_path),
+        };
+        if !vault.is_dir() {
+            return Err(format!("Vault not found: {}", vault.display()));
+        }
+        std::path::absolute(&vault).map_err(|e| e.to_string())
+    }
+}
+
+// Paths are taken relative to the working directory, or to the vault when they don't exist there
+fn resolve(path: &str, vault: &Path) -> Result<PathBuf, String> {
+    let path = Path::new(path);
+    let resolved = if path.exists() { path.to_path_buf() } else { vault.join(path) };
+    if !resolved.exists() {
+        return Err(format!("Not found: {}", path.display()));
+    }
+    std::path::absolute(&resolved).map_err(|e| e.to_string())
+}
+
+// Append the assets of --collection, if given
+fn add_collection(args: &Args, vault: &Path, metadata: &MetadataStore, assets: &mut Vec<PathBuf>) -> Result<(), String> {
+    let Some(name) = args.value("collection") else {
+        return Ok(());
+    };
+    let collections = CollectionStore::load(vault);
+    let index = collections
+        .collections
+        .iter()
+        .position(|collection| collection.name.eq_ignore_ascii_case(name))
+        .ok_or_else(|| format!("No collection named \"{}\"", name))?;
+    for item in collections.items(index, metadata) {
+        if let FileSystemItem::SvgFile { path, .. } = item {
+            if !assets.contains(&path) {
+                assets.push(path);
+            }
+        }
+    }
+    Ok(())
+}
+
+// Run a command and return the process exit code
+pub fn run(args: &[String]) -> i32 {
+    let Some((command, rest)) = args.split_first() else {
+        println!("{}", USAGE);
+        return 0;
+    };
+
+    let result = match command.as_str() {
+        "bundle" => bundle(rest),
+        "site" => site(rest),
+        "serve" => serve(rest),
+        "lint" => lint(rest),
+        "sanitize" => sanitize(rest),
+        "help" | "--help" | "-h" => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        other => Err(format!("Unknown command: {}\n\n{}", other, USAGE)),
+    };
+
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("{}", e);
+            1
+        }
+    }
+}
+
+fn bundle(args: &[String]) -> Result<(), String> {
+    let args = Args::parse(args, &["no-svg", "minified"], &["collection", "png", "license"])?;
+    let vault = args.vault()?;
+    let metadata = MetadataStore::load(&vault);
+
+    let Some((output, paths)) = args.positional.split_first() else {
+        return Err(format!("Missing output file\n\n{}", USAGE));
+    };
+
+    let paths = paths.iter().map(|path| resolve(path, &vault)).collect::<Result<Vec<_>, _>>()?;
+    let mut assets = bundle::collect_assets(&paths);
+    add_collection(&args, &vault, &metadata, &mut assets)?;
+
+    if assets.is_empty() {
+        return Err("No SVGs to bundle, pass files, folders or --collection".to_string());
+    }
+
+    let options = BundleOptions {
+        svg: !args.flag("no-svg"),
+        minified_svg: args.flag("minified"),
+        png_sizes: bundle::parse_sizes(&args.values("png").join(",")),
+        license: match args.value("license") {
+            Some(file) => fs::read_to_string(file).map_err(|e| format!("{}: {}", file, e))?,
+            None => String::new(),
+        },
+    };
+
+    let format_options = AppConfig::load().format_options;
+    let report = bundle::export_bundle(&vault, &assets, Path::new(output), &options, &metadata, &format_options)?;
+    for error in &report.errors {
+        eprintln!("{}", error);
+    }
+    println!("{}: {}", output, report.summary());
+
+    if report.errors.is_empty() { Ok(()) } else { Err(format!("{} files failed", report.errors.len())) }
+}
+
+fn site(args: &[String]) -> Result<(), String> {
+    let args = Args::parse(args, &[], &["collection", "title", "png"])?;
+    let vault = args.vault()?;
+    let metadata = MetadataStore::load(&vault);
+
+    let Some((output, paths)) = args.positional.split_first() else {
+        return Err(format!("Missing output folder\n\n{}", USAGE));
+    };
+
+    let mut paths = paths.iter().map(|path| resolve(path, &vault)).collect::<Result<Vec<_>, _>>()?;
+    if paths.is_empty() && args.value("collection").is_none() {
+        paths.push(vault.clone());
+    }
+    let mut assets = bundle::collect_assets(&paths);
+    add_collection(&args, &vault, &metadata, &mut assets)?;
+
+    if assets.is_empty() {
+        return Err("No SVGs to publish".to_string());
+    }
+
+    let title = match args.value("title") {
+        Some(title) => title.to_string(),
+        None => vault.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_else(|| "Icons".to_string()),
+    };
+    let png_sizes = bundle::parse_sizes(&args.values("png").join(","));
+
+    let report = site::generate(&vault, &assets, Path::new(output), &title, &png_sizes, &metadata)?;
+    for error in &report.errors {
+        eprintln!("{}", error);
+    }
+    println!("{}: {}", Path::new(output).join("index.html").display(), report.summary());
+
+    if report.errors.is_empty() { Ok(()) } else { Err(format!("{} files failed", report.errors.len())) }
+}
+
+fn serve(args: &[String]) -> Result<(), String> {
+    let args = Args::parse(args, &[], &["port"])?;
+    let vault = args.vault()?;
+    let port = match args.value("port") {
+        Some(port) => port.parse().map_err(|_| format!("Not a port: {}", port))?,
+        None => AppConfig::load().server_options.port,
+    };
+
+    let handle = server::start(&vault, port)?;
+    println!("Serving {} at {}", vault.display(), handle.address);
+    println!("  {}/icons/<path>.svg?color=ff0000&size=24", handle.address);
+    println!("  {}/icons/<path>.png?size=64", handle.address);
+    println!("  {}/api/icons?q=<search>", handle.address);
+    println!("Press Ctrl+C to stop");
+    handle.wait();
+    Ok(())
+}
+
+fn lint(args: &[String]) -> Result<(), String> {
+    let args = Args::parse(args, &["strict"], &[])?;
+    let vault = args.vault()?;
+
+    let mut paths = args.positional.iter().map(|path| resolve(path, &vault)).collect::<Result<Vec<_>, _>>()?;
+    if paths.is_empty() {
+        paths.push(vault.clone());
+    }
+    let assets = bundle::collect_assets(&paths);
+    let results = lint::lint_assets(&assets, &LintRules::load(&vault));
+
+    // file:line:column: severity [rule] message, the format editors and CI annotate
+    let (mut errors, mut warnings) = (0, 0);
+    for (path, problems) in &results {
+        let name = if path.starts_with(&vault) { asset_key(&vault, path) } else { path.display().to_string() };
+        for problem in problems {
+            let severity = match problem.severity {
+                Severity::Error => {
+                    errors += 1;
+                    "error"
+                }
+                Severity::Warning => {
+                    warnings += 1;
+                    "warning"
+                }
+            };
+            println!("{}:{}:{}: {} [{}] {}", name, problem.line, problem.column, severity, problem.rule.id(), problem.message);
+        }
+    }
+    println!("{} files checked, {} errors, {} warnings", assets.len(), errors, warnings);
+
+    if errors > 0 || (args.flag("strict") && warnings > 0) {
+        Err(format!("Lint failed in {} files", results.len()))
+    } else {
+        Ok(())
+    }
+}
+
+fn sanitize(args: &[String]) -> Result<(), String> {
+    let args = Args::parse(args, &["check"], &[])?;
+    let vault = args.vault()?;
+
+    let mut paths = args.positional.iter().map(|path| resolve(path, &vault)).collect::<Result<Vec<_>, _>>()?;
+    if paths.is_empty() {
+        paths.push(vault.clone());
+    }
+    let assets = bundle::collect_assets(&paths);
+    let name = |path: &Path| if path.starts_with(&vault) { asset_key(&vault, path) } else { path.display().to_string() };
+
+    if args.flag("check") {
+        let (mut affected, mut failed) = (0, 0);
+        for path in &assets {
+            let findings = fs::read_to_string(path).map_err(|e| e.to_string()).and_then(|source| sanitize::find_active_content(&source));
+            match findings {
+                Ok(findings) => {
+                    for finding in &findings {
+                        println!("{}: {}", name(path), finding);
+                    }
+                    affected += usize::from(!findings.is_empty());
+                }
+                Err(e) => {
+                    eprintln!("{}: {}", name(path), e);
+                    failed += 1;
+                }
+            }
+        }
+        println!("{} files checked, {} with active content, {} failed", assets.len(), affected, failed);
+        return match (affected, failed) {
+            (0, 0) => Ok(()),
+            (0, _) => Err(format!("{} files failed", failed)),
+            _ => Err(format!("Active content in {} files", affected)),
+        };
+    }
+
+    let report = sanitize::sanitize_files(&assets);
+    for (path, before, findings) in &report.cleaned {
+        for finding in findings {
+            println!("{}: removed {}", name(path), finding);
+        }
+        if path.starts_with(&vault) {
+            let after = fs::read_to_string(path).map_err(|e| e.to_string())?;
+            history::record_change(&vault, path, Some(before), &after, "Sanitize").map_err(|e| e.to_string())?;
+        }
+    }
+    for error in &report.errors {
+        eprintln!("{}", error);
+    }
+    println!("{} files checked, {}", assets.len(), report.summary());
+
+    if report.errors.is_empty() { Ok(()) } else { Err(format!("{} files failed", report.errors.len())) }
+}
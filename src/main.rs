@@ -2,11 +2,18 @@ use crate::utils::font_loader::prepare_fonts;
 use eframe::egui;
 use egui_extras::install_image_loaders;
 
+mod cli;
 mod models;
 mod ui;
 mod utils;
 
 fn main() -> eframe::Result<()> {
+    // Arguments run a command line tool instead of opening the window
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        std::process::exit(cli::run(&args));
+    }
+
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default().with_inner_size([800.0, 600.0]),
         ..Default::default()
@@ -0,0 +1,19 @@
+use std::thread::JoinHandle;
+
+// Work running off the UI thread, like a vault-wide scan. Popups poll it every frame.
+pub struct BackgroundJob<T>(JoinHandle<T>);
+
+impl<T: Send + 'static> BackgroundJob<T> {
+    pub fn spawn(work: impl FnOnce() -> T + Send + 'static) -> Self {
+        Self(std::thread::spawn(work))
+    }
+
+    // The result once the job in `slot` is done, which empties the slot
+    pub fn poll(slot: &mut Option<Self>) -> Option<Result<T, String>> {
+        if !slot.as_ref()?.0.is_finished() {
+            return None;
+        }
+        let job = slot.take()?;
+        Some(job.0.join().map_err(|_| "The background task stopped unexpectedly".to_string()))
+    }
+}
@@ -0,0 +1,10 @@
+use std::path::PathBuf;
+
+// Assets waiting in the bundle export popup
+pub struct BundleExport {
+    pub assets: Vec<PathBuf>,
+    // Suggested name for the ZIP, without extension
+    pub name: String,
+    // PNG sizes as typed, e.g. "32, 64"
+    pub sizes_input: String,
+}
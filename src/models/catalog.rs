@@ -0,0 +1,8 @@
+use std::path::PathBuf;
+
+// Assets waiting in the catalog popup
+pub struct Catalog {
+    pub assets: Vec<PathBuf>,
+    // Suggested file name and page title
+    pub name: String,
+}
@@ -0,0 +1,32 @@
+use crate::utils::collections::Collection;
+
+// Collection being created or edited in the popup
+pub struct CollectionDraft {
+    // None for a new collection
+    pub index: Option<usize>,
+    pub collection: Collection,
+    // Smart query tags as typed, comma separated
+    pub tags_input: String,
+}
+
+impl CollectionDraft {
+    pub fn new(collection: Collection) -> Self {
+        Self {
+            index: None,
+            collection,
+            tags_input: String::new(),
+        }
+    }
+
+    pub fn edit(index: usize, collection: Collection) -> Self {
+        let tags_input = match &collection.kind {
+            crate::utils::collections::CollectionKind::Smart { query } => query.tags.join(", "),
+            _ => String::new(),
+        };
+        Self {
+            index: Some(index),
+            collection,
+            tags_input,
+        }
+    }
+}
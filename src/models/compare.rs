@@ -0,0 +1,64 @@
+use crate::models::gui::View;
+use crate::utils::svg_diff::DiffEntry;
+use std::fs;
+use std::path::Path;
+
+// One side of a comparison: a file, an unsaved buffer or a snapshot
+#[derive(Clone)]
+pub struct CompareSide {
+    pub label: String,
+    pub content: String,
+}
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum CompareMode {
+    SideBySide,
+    Difference,
+}
+
+// Textures and diffs computed from the two sides
+pub struct CompareResult {
+    pub before: Result<egui::TextureHandle, String>,
+    pub after: Result<egui::TextureHandle, String>,
+    pub difference: Option<egui::TextureHandle>,
+    pub changed_pixels: usize,
+    pub total_pixels: usize,
+    pub structure: Result<Vec<DiffEntry>, String>,
+}
+
+pub struct CompareState {
+    pub before: Option<CompareSide>,
+    pub after: Option<CompareSide>,
+    pub mode: CompareMode,
+    // None until computed, cleared whenever a side changes
+    pub result: Option<CompareResult>,
+    // View to go back to when the comparison is closed
+    pub return_view: View,
+}
+
+impl Default for CompareState {
+    fn default() -> Self {
+        Self {
+            before: None,
+            after: None,
+            mode: CompareMode::SideBySide,
+            result: None,
+            return_view: View::Gallery,
+        }
+    }
+}
+
+impl CompareSide {
+    pub fn from_file(path: &Path) -> std::io::Result<Self> {
+        Ok(Self {
+            label: file_label(path),
+            content: fs::read_to_string(path)?,
+        })
+    }
+}
+
+pub fn file_label(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.display().to_string())
+}
@@ -0,0 +1,45 @@
+use crate::models::background::BackgroundJob;
+use crate::utils::duplicates::{DuplicateGroup, DEFAULT_SIMILARITY};
+use std::path::{Path, PathBuf};
+
+// Deleting files from the Duplicates tool, waiting for confirmation
+#[derive(Clone, PartialEq)]
+pub enum DuplicateAction {
+    // Keep one asset of a group and delete the rest, optionally folding their
+    // tags, collections and favorite status into the one that is kept
+    Keep { group: usize, keep: PathBuf, merge: bool },
+    Delete(PathBuf),
+}
+
+pub struct DuplicatesState {
+    // Largest perceptual hash difference, in bits, that still counts as similar
+    pub max_distance: u32,
+    // None until the vault has been scanned
+    pub groups: Option<Vec<DuplicateGroup>>,
+    pub confirm: Option<DuplicateAction>,
+    // Scan running on a worker thread
+    pub scan: Option<BackgroundJob<Result<Vec<DuplicateGroup>, String>>>,
+}
+
+impl Default for DuplicatesState {
+    fn default() -> Self {
+        Self {
+            max_distance: DEFAULT_SIMILARITY,
+            groups: None,
+            confirm: None,
+            scan: None,
+        }
+    }
+}
+
+impl DuplicatesState {
+    // Drop deleted files from the results, and groups that no longer have a duplicate
+    pub fn forget(&mut self, path: &Path) {
+        if let Some(groups) = &mut self.groups {
+            for group in groups.iter_mut() {
+                group.assets.retain(|asset| asset != path);
+            }
+            groups.retain(|group| group.assets.len() > 1);
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use crate::utils::embedded_metadata::EmbeddedMetadata;
+use crate::utils::svg_validator::{self, Diagnostic};
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+// How often the editor looks at the file on disk for changes by other programs
+const DISK_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+// An SVG opened in the editor, with its unsaved buffer
+pub struct EditorDocument {
+    pub path: PathBuf,
+    pub content: String,
+    // Content as last read from or written to disk
+    pub saved_content: String,
+    // Modification time of the file when it was last read or written
+    pub disk_modified: Option<SystemTime>,
+    // Pinned tabs stay open; an unpinned clean tab is reused by the next gallery pick
+    pub pinned: bool,
+    // Hash of the buffer and what the validator found in it
+    diagnostics: Option<(u64, Vec<Diagnostic>)>,
+    // When the file was last looked at, and whether it had changed
+    disk_checked: Option<(Instant, bool)>,
+}
+
+// Embedded title, description and RDF fields being edited in the sidebar
+pub struct EmbeddedDraft {
+    pub path: PathBuf,
+    // As read from the asset when the draft was created
+    pub original: EmbeddedMetadata,
+    pub edited: EmbeddedMetadata,
+    // Keywords as typed, comma separated
+    pub keywords: String,
+}
+
+impl EmbeddedDraft {
+    pub fn new(path: &Path, embedded: EmbeddedMetadata) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            keywords: embedded.keywords.join(", "),
+            original: embedded.clone(),
+            edited: embedded,
+        }
+    }
+
+    pub fn is_modified(&self) -> bool {
+        self.edited != self.original || self.keywords != self.original.keywords.join(", ")
+    }
+}
+
+// What to do once the user has answered the unsaved changes prompt
+#[derive(Clone, PartialEq)]
+pub enum UnsavedPrompt {
+    // Another asset was picked while the active document had changes
+    Navigate(PathBuf),
+    // A tab with changes is being closed
+    Close(PathBuf),
+    // The window is being closed with unsaved documents
+    Exit,
+}
+
+impl EditorDocument {
+    pub fn open(path: &Path) -> std::io::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            saved_content: content.clone(),
+            content,
+            disk_modified: modified_time(path),
+            pinned: false,
+            diagnostics: None,
+            disk_checked: None,
+        })
+    }
+
+    pub fn name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.content != self.saved_content
+    }
+
+    pub fn changed_on_disk(&self) -> bool {
+        modified_time(&self.path) != self.disk_modified
+    }
+
+    // changed_on_disk() for every frame, looking at the file at most once per interval
+    pub fn poll_disk_change(&mut self) -> bool {
+        match self.disk_checked {
+            Some((at, changed)) if at.elapsed() < DISK_CHECK_INTERVAL => changed,
+            _ => {
+                let changed = self.changed_on_disk();
+                self.disk_checked = Some((Instant::now(), changed));
+                changed
+            }
+        }
+    }
+
+    // Validator output for the buffer, only validated again once the buffer changed
+    pub fn diagnostics(&mut self) -> Vec<Diagnostic> {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        self.content.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        match &self.diagnostics {
+            Some((validated, diagnostics)) if *validated == hash => diagnostics.clone(),
+            _ => {
+                let diagnostics = svg_validator::validate(&self.content);
+                self.diagnostics = Some((hash, diagnostics.clone()));
+                diagnostics
+            }
+        }
+    }
+
+    pub fn save(&mut self) -> std::io::Result<()> {
+        fs::write(&self.path, &self.content)?;
+        self.saved_content = self.content.clone();
+        self.disk_modified = modified_time(&self.path);
+        self.disk_checked = None;
+        Ok(())
+    }
+
+    // Replace the buffer with what is on disk now, dropping unsaved changes
+    pub fn reload(&mut self) -> std::io::Result<()> {
+        let content = fs::read_to_string(&self.path)?;
+        self.saved_content = content.clone();
+        self.content = content;
+        self.disk_modified = modified_time(&self.path);
+        self.disk_checked = None;
+        Ok(())
+    }
+
+    pub fn revert(&mut self) {
+        self.content = self.saved_content.clone();
+    }
+
+    // Accept the file on disk as changed without reloading it
+    pub fn acknowledge_disk_change(&mut self) {
+        self.disk_modified = modified_time(&self.path);
+        self.disk_checked = None;
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone)]
 pub enum FileSystemItem {
@@ -6,3 +6,22 @@ pub enum FileSystemItem {
     SvgFile { name: String, path: PathBuf },
     FontFile { name: String, path: PathBuf },
 }
+
+impl FileSystemItem {
+    // Gallery item for a single asset file, None for other files or missing ones
+    pub fn from_file(path: &Path) -> Option<Self> {
+        if !path.is_file() {
+            return None;
+        }
+
+        let name = path.file_name()?.to_string_lossy().to_string();
+        let extension = path.extension()?.to_string_lossy().to_lowercase();
+        let path = path.to_path_buf();
+
+        match extension.as_str() {
+            "svg" => Some(FileSystemItem::SvgFile { name, path }),
+            "ttf" | "otf" | "woff" | "woff2" => Some(FileSystemItem::FontFile { name, path }),
+            _ => None,
+        }
+    }
+}
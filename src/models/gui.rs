@@ -1,15 +1,56 @@
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
-use std::time::Instant;
+use std::time::{Instant, SystemTime};
 use arboard::Clipboard;
+use svgtypes::Color;
 use crate::models::FileSystemItem;
+use crate::models::collection::CollectionDraft;
+use crate::models::background::BackgroundJob;
+use crate::models::duplicates::DuplicatesState;
+use crate::models::bundle::BundleExport;
+use crate::models::mobile_export::MobileExport;
+use crate::models::print_export::PrintExport;
+use crate::models::catalog::Catalog;
+use crate::models::lint::LintRulesDraft;
+use crate::models::import::ImportState;
+use crate::models::editor::{EditorDocument, EmbeddedDraft, UnsavedPrompt};
+use crate::models::compare::CompareState;
+use crate::models::live_preview::LivePreview;
+use crate::utils::bundle::BundleOptions;
+use crate::utils::mobile_export::MobileExportOptions;
+use crate::utils::print_export::PrintOptions;
+use crate::utils::catalog::CatalogOptions;
+use crate::utils::server::{ServerHandle, ServerOptions};
+use crate::utils::lint::{LintRules, Problem};
+use crate::utils::collections::CollectionStore;
+use crate::utils::contrast::{ContrastLevel, IconContrastReport};
+use crate::utils::git::{FileStatus, LogEntry};
+use crate::utils::history::{DeletedAsset, Snapshot};
+use crate::utils::import::ImportOptions;
+use crate::utils::metadata::MetadataStore;
+use crate::utils::recent::RecentEntry;
+use crate::utils::snippets::SnippetKind;
+use crate::utils::svg_format::{FormatAction, FormatOptions};
+use crate::utils::theme_variants::ColorMapping;
 
-#[derive(PartialEq)]
+#[derive(PartialEq, Clone, Copy)]
 pub enum View {
     Gallery,
     Settings,
     Fonts,
     Help,
     Editor,
+    Compare,
+    Collection,
+    Favorites,
+    Recent,
+}
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum SidebarTab {
+    Details,
+    History,
+    Commits,
 }
 
 pub struct MyApp {
@@ -21,7 +62,12 @@ pub struct MyApp {
     pub(crate) current_font_input: String,
     pub(crate) current_items: Vec<FileSystemItem>,
     pub(crate) selected_svg: Option<PathBuf>,
-    pub(crate) svg_code: String,
+    // Gallery tiles picked with Ctrl+click, for actions on several assets at once
+    pub(crate) selected_assets: HashSet<PathBuf>,
+    pub(crate) documents: Vec<EditorDocument>,
+    pub(crate) active_document: Option<usize>,
+    pub(crate) unsaved_prompt: Option<UnsavedPrompt>,
+    pub(crate) exit_confirmed: bool,
     pub(crate) error_message: Option<String>,
     pub(crate) error_message_time: Option<Instant>,
     pub(crate) rename_file_path: Option<PathBuf>,
@@ -40,4 +86,92 @@ pub struct MyApp {
 
     pub(crate) show_sidebar_right: bool,
     pub(crate) sort_ascending: bool,
+
+    pub(crate) theme_variants_open: bool,
+    pub(crate) theme_color_map: Vec<ColorMapping>,
+
+    pub(crate) contrast_backgrounds: Vec<String>,
+    pub(crate) contrast_report_open: bool,
+    pub(crate) contrast_report: Option<Vec<IconContrastReport>>,
+    pub(crate) contrast_scan: Option<BackgroundJob<Result<Vec<IconContrastReport>, String>>>,
+    // Colors of the asset shown in the Contrast section, reloaded when None
+    pub(crate) contrast_colors: Option<(PathBuf, Vec<Color>)>,
+    pub(crate) contrast_minimum: ContrastLevel,
+
+    pub(crate) confirm_invalid_save: bool,
+    pub(crate) live_preview: LivePreview,
+
+    pub(crate) format_options: FormatOptions,
+    pub(crate) format_folder_open: bool,
+    pub(crate) format_folder_action: FormatAction,
+    pub(crate) format_folder_recursive: bool,
+
+    pub(crate) component_library_open: bool,
+    pub(crate) component_library_kind: SnippetKind,
+    pub(crate) component_library_output: String,
+    pub(crate) component_library_recursive: bool,
+
+    pub(crate) compare: CompareState,
+
+    pub(crate) sidebar_tab: SidebarTab,
+    // Snapshots of the asset shown in the History tab and the deleted assets, reloaded when None
+    pub(crate) history_cache: Option<(PathBuf, Vec<Snapshot>, Vec<DeletedAsset>)>,
+
+    // None when the vault is not inside a git repository
+    pub(crate) git_status: Option<HashMap<PathBuf, FileStatus>>,
+    pub(crate) git_commit_open: bool,
+    pub(crate) git_commit_message: String,
+    pub(crate) git_commit_selection: HashSet<PathBuf>,
+    pub(crate) git_log_cache: Option<(PathBuf, Result<Vec<LogEntry>, String>)>,
+
+    pub(crate) metadata: MetadataStore,
+    pub(crate) metadata_tag_input: String,
+    // Asset whose metadata is edited in the popup
+    pub(crate) metadata_edit_path: Option<PathBuf>,
+    pub(crate) mirror_metadata: bool,
+    pub(crate) embedded_draft: Option<EmbeddedDraft>,
+
+    pub(crate) collections: CollectionStore,
+    // Collection shown in View::Collection
+    pub(crate) active_collection: Option<usize>,
+    pub(crate) collection_edit: Option<CollectionDraft>,
+
+    pub(crate) favorites: Vec<PathBuf>,
+    // Newest first
+    pub(crate) recent: Vec<RecentEntry>,
+
+    pub(crate) duplicates_open: bool,
+    pub(crate) duplicates: DuplicatesState,
+
+    pub(crate) import_open: bool,
+    pub(crate) import: ImportState,
+    pub(crate) import_options: ImportOptions,
+
+    pub(crate) bundle_export: Option<BundleExport>,
+    pub(crate) bundle_options: BundleOptions,
+
+    pub(crate) mobile_export: Option<MobileExport>,
+    pub(crate) mobile_export_options: MobileExportOptions,
+
+    pub(crate) print_export: Option<PrintExport>,
+    pub(crate) print_options: PrintOptions,
+
+    pub(crate) catalog: Option<Catalog>,
+    pub(crate) catalog_options: CatalogOptions,
+
+    // Local HTTP server, running while Some
+    pub(crate) server: Option<ServerHandle>,
+    pub(crate) server_options: ServerOptions,
+
+    // Rules come from the vault, results are None until the linter has run
+    pub(crate) lint_rules: LintRules,
+    pub(crate) lint_results: Option<HashMap<PathBuf, Vec<Problem>>>,
+    pub(crate) problems_open: bool,
+    pub(crate) lint_rules_edit: Option<LintRulesDraft>,
+
+    // Scripts and external references found in the listed SVGs, by path,
+    // with the modification time of the file when it was read
+    pub(crate) active_content: HashMap<PathBuf, (Option<SystemTime>, Vec<String>)>,
+    // Vault files to sanitize, waiting for confirmation
+    pub(crate) sanitize_scan: Option<Vec<(PathBuf, Vec<String>)>>,
 }
\ No newline at end of file
@@ -0,0 +1,45 @@
+use crate::utils::archive::ArchiveEntry;
+use crate::utils::import::ImportReport;
+use std::path::PathBuf;
+
+// A .zip or .tar.gz opened in the import wizard
+pub struct ArchiveImport {
+    pub path: PathBuf,
+    pub entries: Vec<ArchiveEntry>,
+    // One flag per entry, entries with a problem can't be selected
+    pub selected: Vec<bool>,
+    // Recreate the archive's folders below the destination
+    pub keep_structure: bool,
+}
+
+impl ArchiveImport {
+    pub fn new(path: PathBuf, entries: Vec<ArchiveEntry>) -> Self {
+        let selected = entries.iter().map(|entry| entry.problem.is_none()).collect();
+        Self {
+            path,
+            entries,
+            selected,
+            keep_structure: false,
+        }
+    }
+
+    pub fn selected_count(&self) -> usize {
+        self.selected.iter().filter(|selected| **selected).count()
+    }
+
+    pub fn select_all(&mut self, select: bool) {
+        for (selected, entry) in self.selected.iter_mut().zip(&self.entries) {
+            *selected = select && entry.problem.is_none();
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ImportState {
+    // Files waiting to be imported
+    pub sources: Vec<PathBuf>,
+    pub archive: Option<ArchiveImport>,
+    pub destination: String,
+    // Outcome of the last run
+    pub report: Option<ImportReport>,
+}
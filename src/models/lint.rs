@@ -0,0 +1,19 @@
+use crate::utils::lint::LintRules;
+
+// Lint rules being edited in the popup
+pub struct LintRulesDraft {
+    pub rules: LintRules,
+    // Lists as typed, comma separated
+    pub grid_sizes_input: String,
+    pub palette_input: String,
+}
+
+impl LintRulesDraft {
+    pub fn new(rules: LintRules) -> Self {
+        Self {
+            grid_sizes_input: rules.grid_sizes.iter().map(|size| size.to_string()).collect::<Vec<_>>().join(", "),
+            palette_input: rules.palette.join(", "),
+            rules,
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use resvg::usvg::Tree;
+
+// State of the rendered preview next to the code editor
+#[derive(Default)]
+pub struct LivePreview {
+    // Hash of the buffer the tree was parsed from
+    pub source_hash: u64,
+    pub tree: Option<Result<Tree, String>>,
+    // Texture and the scale it was rasterized at
+    pub texture: Option<(f32, egui::TextureHandle)>,
+    // None fits the image to the panel
+    pub zoom: Option<f32>,
+    pub pan: egui::Vec2,
+}
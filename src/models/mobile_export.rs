@@ -0,0 +1,9 @@
+use crate::utils::mobile_export::MobileExportReport;
+use std::path::PathBuf;
+
+// Assets waiting in the Android / iOS export popup
+pub struct MobileExport {
+    pub assets: Vec<PathBuf>,
+    // Shown after exporting, until the popup is closed
+    pub report: Option<MobileExportReport>,
+}
@@ -1,4 +1,16 @@
 pub mod file_items;
 pub mod gui;
+pub mod editor;
+pub mod live_preview;
+pub mod compare;
+pub mod collection;
+pub mod duplicates;
+pub mod import;
+pub mod bundle;
+pub mod mobile_export;
+pub mod print_export;
+pub mod catalog;
+pub mod lint;
+pub mod background;
 
 pub use file_items::FileSystemItem;
\ No newline at end of file
@@ -0,0 +1,8 @@
+use std::path::PathBuf;
+
+// Assets waiting in the PDF / EPS export popup
+pub struct PrintExport {
+    pub assets: Vec<PathBuf>,
+    // Suggested file name and contact sheet title
+    pub name: String,
+}
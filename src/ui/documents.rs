@@ -0,0 +1,255 @@
+use crate::models::editor::{EditorDocument, UnsavedPrompt};
+use crate::models::gui::MyApp;
+use crate::utils::recent::RecentAction;
+use crate::utils::svg_validator;
+use std::fs;
+use std::path::Path;
+
+// Open documents (editor tabs) and their unsaved state
+impl MyApp {
+    pub(crate) fn active_document(&self) -> Option<&EditorDocument> {
+        self.active_document.and_then(|idx| self.documents.get(idx))
+    }
+
+    pub(crate) fn active_document_mut(&mut self) -> Option<&mut EditorDocument> {
+        self.active_document.and_then(|idx| self.documents.get_mut(idx))
+    }
+
+    pub(crate) fn has_dirty_documents(&self) -> bool {
+        self.documents.iter().any(|doc| doc.is_dirty())
+    }
+
+    fn document_index(&self, path: &Path) -> Option<usize> {
+        self.documents.iter().position(|doc| doc.path == path)
+    }
+
+    pub(crate) fn activate_document(&mut self, idx: usize) {
+        if let Some(doc) = self.documents.get(idx) {
+            self.selected_svg = Some(doc.path.clone());
+            self.active_document = Some(idx);
+        }
+    }
+
+    // Select an asset. Unsaved changes in the active document are never dropped
+    // silently: the user is asked to save, discard or keep them in their tab.
+    pub(crate) fn load_svg(&mut self, path: &Path) {
+        if let Some(idx) = self.document_index(path) {
+            self.activate_document(idx);
+            return;
+        }
+
+        if self.active_document().map(|doc| doc.is_dirty()).unwrap_or(false) {
+            self.unsaved_prompt = Some(UnsavedPrompt::Navigate(path.to_path_buf()));
+            return;
+        }
+
+        self.open_document(path);
+    }
+
+    // Open a document without asking, reusing the active tab when it is clean and unpinned
+    pub(crate) fn open_document(&mut self, path: &Path) {
+        let doc = match EditorDocument::open(path) {
+            Ok(doc) => doc,
+            Err(e) => {
+                self.set_error_message(format!("Failed to read file: {}", e));
+                return;
+            }
+        };
+
+        let reusable = self
+            .active_document
+            .filter(|&idx| !self.documents[idx].pinned && !self.documents[idx].is_dirty());
+
+        let idx = match reusable {
+            Some(idx) => {
+                self.documents[idx] = doc;
+                idx
+            }
+            None => {
+                self.documents.push(doc);
+                self.documents.len() - 1
+            }
+        };
+
+        self.activate_document(idx);
+        self.record_recent(path, RecentAction::Opened);
+    }
+
+    // Keep the active document's tab open, e.g. once it is opened for editing
+    pub(crate) fn pin_active_document(&mut self) {
+        if let Some(doc) = self.active_document_mut() {
+            doc.pinned = true;
+        }
+    }
+
+    pub(crate) fn request_close_document(&mut self, idx: usize) {
+        let Some(doc) = self.documents.get(idx) else {
+            return;
+        };
+
+        if doc.is_dirty() {
+            self.unsaved_prompt = Some(UnsavedPrompt::Close(doc.path.clone()));
+        } else {
+            self.close_document(idx);
+        }
+    }
+
+    pub(crate) fn close_document(&mut self, idx: usize) {
+        if idx >= self.documents.len() {
+            return;
+        }
+        self.documents.remove(idx);
+
+        // Keep the active tab pointing at the same document, or its neighbour
+        self.active_document = match self.active_document {
+            _ if self.documents.is_empty() => None,
+            Some(active) if active > idx => Some(active - 1),
+            Some(active) if active == idx => Some(idx.min(self.documents.len() - 1)),
+            other => other,
+        };
+        self.selected_svg = self.active_document().map(|doc| doc.path.clone());
+    }
+
+    pub(crate) fn close_document_at_path(&mut self, path: &Path) {
+        if let Some(idx) = self.document_index(path) {
+            self.close_document(idx);
+        }
+    }
+
+    // Follow a file that was renamed or moved inside the app
+    pub(crate) fn rename_document(&mut self, old_path: &Path, new_path: &Path) {
+        for doc in &mut self.documents {
+            if doc.path == old_path {
+                doc.path = new_path.to_path_buf();
+                doc.acknowledge_disk_change();
+            }
+        }
+        if self.selected_svg.as_deref() == Some(old_path) {
+            self.selected_svg = Some(new_path.to_path_buf());
+        }
+    }
+
+    // Pick up files changed by a batch action; documents with unsaved edits
+    // keep them and show the changed-on-disk warning instead
+    pub(crate) fn reload_clean_documents(&mut self) {
+        for doc in &mut self.documents {
+            if !doc.is_dirty() && doc.changed_on_disk() {
+                let _ = doc.reload();
+            }
+        }
+    }
+
+    // Rewrite an asset. An open document is changed in its buffer and only saved
+    // when it had no other unsaved changes, so edits are never written behind the user's back.
+    pub(crate) fn update_svg_content(
+        &mut self,
+        path: &Path,
+        reason: &str,
+        update: impl FnOnce(&str) -> Result<String, String>,
+    ) -> bool {
+        if let Some(idx) = self.document_index(path) {
+            let doc = &mut self.documents[idx];
+            let was_dirty = doc.is_dirty();
+            let content = match update(&doc.content) {
+                Ok(content) => content,
+                Err(e) => {
+                    let message = format!("Failed to update {}: {}", doc.name(), e);
+                    self.set_error_message(message);
+                    return false;
+                }
+            };
+            if content == doc.content {
+                return true;
+            }
+            doc.content = content;
+
+            if was_dirty {
+                let message = format!("{} has unsaved changes, save it to keep the update", doc.name());
+                self.set_error_message(message);
+                return true;
+            }
+
+            let before = fs::read_to_string(path).ok();
+            if let Err(e) = doc.save() {
+                let message = format!("Failed to save {}: {}", doc.name(), e);
+                self.set_error_message(message);
+                return false;
+            }
+            let content = doc.content.clone();
+            self.record_history(path, before.as_deref(), &content, reason);
+            return true;
+        }
+
+        let result = fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|before| update(&before).map(|after| (before, after)));
+        match result {
+            Ok((before, after)) if before == after => true,
+            Ok((before, after)) => match fs::write(path, &after) {
+                Ok(_) => {
+                    self.record_history(path, Some(&before), &after, reason);
+                    true
+                }
+                Err(e) => {
+                    self.set_error_message(format!("Failed to write file: {}", e));
+                    false
+                }
+            },
+            Err(e) => {
+                self.set_error_message(format!("Failed to update file: {}", e));
+                false
+            }
+        }
+    }
+
+    // Write one document to disk, reporting failures in the status bar
+    pub(crate) fn save_document(&mut self, idx: usize) -> bool {
+        let Some(doc) = self.documents.get_mut(idx) else {
+            return false;
+        };
+        let before = std::fs::read_to_string(&doc.path).ok();
+
+        match doc.save() {
+            Ok(_) => {
+                let (path, content) = (doc.path.clone(), doc.content.clone());
+                self.record_history(&path, before.as_deref(), &content, "Saved");
+                true
+            }
+            Err(e) => {
+                let message = format!("Failed to save {}: {}", doc.name(), e);
+                self.set_error_message(message);
+                false
+            }
+        }
+    }
+
+    // Save every document with changes; invalid documents are left for the user to fix
+    pub(crate) fn save_all_documents(&mut self) {
+        let mut saved = 0;
+        let mut skipped = Vec::new();
+
+        for idx in 0..self.documents.len() {
+            let doc = &self.documents[idx];
+            if !doc.is_dirty() {
+                continue;
+            }
+            if svg_validator::has_errors(&svg_validator::validate(&doc.content)) {
+                skipped.push(doc.name());
+                continue;
+            }
+            if self.save_document(idx) {
+                saved += 1;
+            }
+        }
+
+        if skipped.is_empty() {
+            self.set_error_message(format!("✅ Saved {} files", saved));
+        } else {
+            self.set_error_message(format!(
+                "Saved {} files, not saved (invalid): {}",
+                saved,
+                skipped.join(", ")
+            ));
+        }
+    }
+}
@@ -6,13 +6,40 @@ use arboard::Clipboard;
 use eframe::egui;
 use eframe::glow::Context;
 use egui::{CentralPanel, Vec2};
-use std::fs;
-use std::path::PathBuf;
 use std::time::Instant;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
 use crate::utils::file_finder::{scan_directory, FileFilter};
-use crate::models::gui::View;
+use crate::models::gui::{SidebarTab, View};
+use crate::models::editor::UnsavedPrompt;
+use crate::models::compare::CompareState;
+use crate::models::duplicates::DuplicatesState;
+use crate::models::bundle::BundleExport;
+use crate::models::mobile_export::MobileExport;
+use crate::models::print_export::PrintExport;
+use crate::models::catalog::Catalog;
+use crate::models::import::{ArchiveImport, ImportState};
+use crate::models::live_preview::LivePreview;
+use crate::models::file_items::FileSystemItem;
 pub(crate) use crate::models::gui::MyApp;
 use crate::ui::views::gallery;
+use crate::utils::collections::CollectionStore;
+use crate::utils::contrast::ContrastLevel;
+use crate::utils::git;
+use crate::utils::history;
+use crate::utils::archive;
+use crate::utils::bundle;
+use crate::utils::server;
+use crate::utils::lint::{self, LintRules};
+use crate::utils::sanitize;
+use crate::utils::import;
+use crate::utils::embedded_metadata;
+use crate::utils::metadata::MetadataStore;
+use crate::utils::recent::{self, RecentAction};
+use crate::utils::svg_validator;
+use crate::utils::svg_format::FormatAction;
+use crate::utils::snippets::SnippetKind;
 
 impl MyApp {
     pub fn get_thumbnail_size(&self) -> Vec2 {
@@ -23,7 +50,19 @@ impl MyApp {
         let config = AppConfig {
             vault_path: self.vault_path.clone(),
             font_path: self.font_path.clone(),
-            thumbnail_size: self.thumbnail_size
+            thumbnail_size: self.thumbnail_size,
+            theme_color_map: self.theme_color_map.clone(),
+            contrast_backgrounds: self.contrast_backgrounds.clone(),
+            format_options: self.format_options.clone(),
+            favorites: self.favorites.clone(),
+            recent: self.recent.clone(),
+            mirror_metadata: self.mirror_metadata,
+            import_options: self.import_options.clone(),
+            bundle_options: self.bundle_options.clone(),
+            mobile_export_options: self.mobile_export_options.clone(),
+            print_options: self.print_options.clone(),
+            catalog_options: self.catalog_options.clone(),
+            server_options: self.server_options.clone(),
         };
         config.save();
     }
@@ -47,6 +86,8 @@ impl MyApp {
             }
             Err(e) => self.set_error_message(format!("Error scanning directory: {}", e)),
         }
+        self.scan_active_content();
+        self.refresh_git_status();
     }
 
     fn navigate_to(&mut self, path: String) {
@@ -62,48 +103,448 @@ impl MyApp {
             Ok(items) => self.current_items = items,
             Err(e) => self.set_error_message(format!("Error scanning directory: {}", e)),
         }
+        self.scan_active_content();
+        self.refresh_git_status();
     }
 
-    fn load_svg(&mut self, path: &PathBuf) {
-        match fs::read_to_string(path) {
-            Ok(content) => {
-                self.svg_code = content;
-                self.selected_svg = Some(path.clone());
-            }
-            Err(e) => {
-                self.set_error_message(format!("Failed to read file: {}", e));
-            }
+    pub(crate) fn refresh_git_status(&mut self) {
+        let vault = PathBuf::from(&self.vault_path);
+        self.git_status = git::open_repo(&vault).map(|repo| git::statuses(&repo, &vault).unwrap_or_default());
+        self.git_log_cache = None;
+    }
+
+    pub(crate) fn rescan_current_directory(&mut self) {
+        if matches!(self.current_view, View::Collection | View::Favorites | View::Recent) {
+            self.refresh_list_view();
+            return;
         }
+        let path = self.current_path.clone();
+        self.navigate_to(path);
+        self.grid_reset_counter = self.grid_reset_counter.wrapping_add(1);
     }
 
+    // Validate before writing; invalid documents need an explicit confirmation
     pub(crate) fn save_svg(&mut self) {
-        if let Some(path) = &self.selected_svg {
-            match fs::write(path, &self.svg_code) {
-                Ok(_) => {
-                    self.set_error_message("✅ Saved!".to_string());
-                }
-                Err(e) => {
-                    self.set_error_message(format!("Failed to save: {}", e));
+        let Some(doc) = self.active_document() else {
+            return;
+        };
+        if svg_validator::has_errors(&svg_validator::validate(&doc.content)) {
+            self.confirm_invalid_save = true;
+            return;
+        }
+        self.write_svg();
+    }
+
+    pub(crate) fn write_svg(&mut self) {
+        if let Some(idx) = self.active_document {
+            if self.save_document(idx) {
+                self.set_error_message("✅ Saved!".to_string());
+            }
+        }
+    }
+
+    // Keep a snapshot of an asset that was just overwritten
+    pub(crate) fn record_history(&mut self, path: &Path, before: Option<&str>, after: &str, reason: &str) {
+        let vault = PathBuf::from(&self.vault_path);
+        if let Err(e) = history::record_change(&vault, path, before, after, reason) {
+            self.set_error_message(format!("Failed to record history: {}", e));
+        }
+        self.history_cache = None;
+        self.contrast_colors = None;
+        self.refresh_git_status();
+        self.record_recent(path, RecentAction::Edited);
+        self.relint(path);
+        self.recheck_active_content(path);
+    }
+
+    // Everything that tracks assets by path follows a rename here
+    pub(crate) fn on_asset_renamed(&mut self, old_path: &Path, new_path: &Path) {
+        self.rename_document(old_path, new_path);
+        if self.selected_assets.remove(old_path) {
+            self.selected_assets.insert(new_path.to_path_buf());
+        }
+
+        let vault = PathBuf::from(&self.vault_path);
+        if let Err(e) = history::rename(&vault, old_path, new_path) {
+            self.set_error_message(format!("Failed to move history: {}", e));
+        }
+        self.metadata.rename(old_path, new_path);
+        self.save_metadata();
+        self.collections.rename(old_path, new_path);
+        self.save_collections();
+        recent::rename(&mut self.favorites, &mut self.recent, old_path, new_path);
+        self.save_config();
+
+        self.history_cache = None;
+        self.refresh_git_status();
+        if let Some(results) = self.lint_results.as_mut() {
+            results.remove(old_path);
+        }
+        self.relint(new_path);
+        if let Some(found) = self.active_content.remove(old_path) {
+            self.active_content.insert(new_path.to_path_buf(), found);
+        }
+    }
+
+    // Drop everything that tracks an asset that was deleted from disk
+    pub(crate) fn forget_asset(&mut self, path: &Path) {
+        self.current_items.retain(|item| match item {
+            FileSystemItem::SvgFile { path: p, .. } => p != path,
+            FileSystemItem::FontFile { path: p, .. } => p != path,
+            FileSystemItem::Directory { path: p, .. } => p != path,
+        });
+
+        // Close the document if it was open
+        self.close_document_at_path(path);
+        if self.selected_svg.as_deref() == Some(path) {
+            self.selected_svg = None;
+        }
+        self.selected_assets.remove(path);
+
+        self.metadata.remove(path);
+        self.save_metadata();
+        self.collections.remove(path);
+        self.save_collections();
+        recent::remove(&mut self.favorites, &mut self.recent, path);
+        self.save_config();
+        self.refresh_git_status();
+        if let Some(results) = self.lint_results.as_mut() {
+            results.remove(path);
+        }
+        self.active_content.remove(path);
+
+        // Force grid to reset
+        self.grid_reset_counter = self.grid_reset_counter.wrapping_add(1);
+    }
+
+    pub(crate) fn save_metadata(&mut self) {
+        if let Err(e) = self.metadata.save() {
+            self.set_error_message(format!("Failed to save metadata: {}", e));
+        }
+    }
+
+    // Save the metadata of one asset, copying it into the SVG when mirroring is on
+    pub(crate) fn persist_metadata(&mut self, path: &Path) {
+        self.save_metadata();
+
+        let is_svg = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("svg"));
+        if !self.mirror_metadata || !is_svg {
+            return;
+        }
+
+        let Some(metadata) = self.metadata.get(path).cloned() else {
+            return;
+        };
+        self.update_svg_content(path, "Mirrored metadata", |content| {
+            let mut embedded = embedded_metadata::read(content);
+            embedded.mirror(&metadata);
+            embedded_metadata::write(content, &embedded)
+        });
+    }
+
+    pub(crate) fn save_collections(&mut self) {
+        if let Err(e) = self.collections.save() {
+            self.set_error_message(format!("Failed to save collections: {}", e));
+        }
+    }
+
+    pub(crate) fn open_collection(&mut self, index: usize) {
+        self.active_collection = Some(index);
+        self.open_list_view(View::Collection);
+    }
+
+    // Views listing assets from anywhere in the vault instead of one folder
+    pub(crate) fn open_list_view(&mut self, view: View) {
+        self.current_view = view;
+        self.search_active = false;
+        self.search_query.clear();
+        self.refresh_list_view();
+    }
+
+    // Re-evaluate the list view, smart collections pick up new matches
+    pub(crate) fn refresh_list_view(&mut self) {
+        self.current_items = match self.current_view {
+            View::Collection => match self.active_collection {
+                Some(index) => self.collections.items(index, &self.metadata),
+                None => Vec::new(),
+            },
+            View::Favorites => self.favorites.iter().filter_map(|path| FileSystemItem::from_file(path)).collect(),
+            View::Recent => self.recent.iter().filter_map(|entry| FileSystemItem::from_file(&entry.path)).collect(),
+            _ => return,
+        };
+
+        // Recent stays in chronological order
+        if self.current_view != View::Recent {
+            gallery::helpers::sort_items(&mut self.current_items, self.sort_ascending);
+        }
+        self.scan_active_content();
+        self.grid_reset_counter = self.grid_reset_counter.wrapping_add(1);
+    }
+
+    pub(crate) fn is_favorite(&self, path: &Path) -> bool {
+        self.favorites.iter().any(|favorite| favorite == path)
+    }
+
+    pub(crate) fn toggle_favorite(&mut self, path: &Path) {
+        if self.is_favorite(path) {
+            self.favorites.retain(|favorite| favorite != path);
+        } else {
+            self.favorites.push(path.to_path_buf());
+        }
+        self.save_config();
+
+        if self.current_view == View::Favorites {
+            self.refresh_list_view();
+        }
+    }
+
+    pub(crate) fn record_recent(&mut self, path: &Path, action: RecentAction) {
+        recent::push(&mut self.recent, path, action);
+        self.save_config();
+    }
+
+    pub(crate) fn delete_collection(&mut self, index: usize) {
+        if index >= self.collections.collections.len() {
+            return;
+        }
+        self.collections.collections.remove(index);
+        self.save_collections();
+
+        match self.active_collection {
+            Some(active) if active == index => {
+                self.active_collection = None;
+                if self.current_view == View::Collection {
+                    self.current_view = View::Gallery;
+                    self.refresh_directory();
                 }
             }
+            Some(active) if active > index => self.active_collection = Some(active - 1),
+            _ => {}
+        }
+    }
+
+    // Queue files for the import wizard, e.g. after they were dropped on the window
+    pub(crate) fn open_import(&mut self, paths: &[PathBuf]) {
+        if !self.import_open {
+            let destination = match self.current_view {
+                View::Gallery | View::Fonts => self.current_path.clone(),
+                _ => self.vault_path.clone(),
+            };
+            self.import = ImportState { destination, ..ImportState::default() };
+        }
+
+        // Archives are previewed entry by entry, one at a time
+        let (archives, files): (Vec<PathBuf>, Vec<PathBuf>) =
+            paths.iter().cloned().partition(|path| archive::archive_format(path).is_some());
+        for path in archives {
+            match archive::read_archive(&path) {
+                Ok(entries) => self.import.archive = Some(ArchiveImport::new(path, entries)),
+                Err(e) => self.set_error_message(format!("Failed to open {}: {}", path.display(), e)),
+            }
+        }
+
+        for path in import::collect_sources(&files) {
+            if !self.import.sources.contains(&path) {
+                self.import.sources.push(path);
+            }
+        }
+        self.import.report = None;
+        self.import_open = true;
+    }
+
+    pub(crate) fn open_bundle_export(&mut self, assets: Vec<PathBuf>, name: &str) {
+        if assets.is_empty() {
+            self.set_error_message("No SVGs to export".to_string());
+            return;
+        }
+        let sizes_input = self.bundle_options.png_sizes.iter().map(|size| size.to_string()).collect::<Vec<_>>().join(", ");
+        self.bundle_export = Some(BundleExport { assets, name: name.to_string(), sizes_input });
+    }
+
+    // The selected assets, or everything shown when nothing is selected, with a name for them
+    pub(crate) fn current_view_assets(&self) -> (Vec<PathBuf>, String) {
+        if !self.selected_assets.is_empty() {
+            let mut assets: Vec<PathBuf> = self.selected_assets.iter().cloned().collect();
+            assets.sort();
+            return (assets, "selection".to_string());
+        }
+
+        match self.current_view {
+            // Folders come with their subfolders
+            View::Gallery => {
+                let folder = PathBuf::from(&self.current_path);
+                let name = folder.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                (bundle::collect_assets(&[folder]), name)
+            }
+            _ => {
+                let assets = self
+                    .current_items
+                    .iter()
+                    .filter_map(|item| match item {
+                        FileSystemItem::SvgFile { path, .. } => Some(path.clone()),
+                        _ => None,
+                    })
+                    .collect();
+                let name = match self.current_view {
+                    View::Collection => self
+                        .active_collection
+                        .and_then(|index| self.collections.collections.get(index))
+                        .map(|collection| collection.name.clone())
+                        .unwrap_or_default(),
+                    View::Favorites => "favorites".to_string(),
+                    View::Recent => "recent".to_string(),
+                    _ => String::new(),
+                };
+                (assets, name)
+            }
+        }
+    }
+
+    pub(crate) fn bundle_current_view(&mut self) {
+        let (assets, name) = self.current_view_assets();
+        self.open_bundle_export(assets, &name);
+    }
+
+    pub(crate) fn open_mobile_export(&mut self, assets: Vec<PathBuf>) {
+        if assets.is_empty() {
+            self.set_error_message("No SVGs to export".to_string());
+            return;
+        }
+        self.mobile_export = Some(MobileExport { assets, report: None });
+    }
+
+    pub(crate) fn open_print_export(&mut self, assets: Vec<PathBuf>, name: &str) {
+        if assets.is_empty() {
+            self.set_error_message("No SVGs to export".to_string());
+            return;
+        }
+        self.print_export = Some(PrintExport { assets, name: name.to_string() });
+    }
+
+    pub(crate) fn open_catalog(&mut self, assets: Vec<PathBuf>, name: &str) {
+        if assets.is_empty() {
+            self.set_error_message("No SVGs for a catalog".to_string());
+            return;
+        }
+        self.catalog = Some(Catalog { assets, name: name.to_string() });
+    }
+
+    pub(crate) fn start_server(&mut self) {
+        // Dropping the old handle frees the port first
+        self.server = None;
+        match server::start(Path::new(&self.vault_path), self.server_options.port) {
+            Ok(handle) => {
+                self.set_error_message(format!("✅ Serving the vault at {}", handle.address));
+                self.server = Some(handle);
+            }
+            Err(e) => self.set_error_message(format!("Failed to start server: {}", e)),
+        }
+    }
+
+    // Lint every SVG in the vault and show the Problems panel
+    pub(crate) fn run_lint(&mut self) {
+        let assets = bundle::collect_assets(&[PathBuf::from(&self.vault_path)]);
+        let results: HashMap<PathBuf, Vec<lint::Problem>> = lint::lint_assets(&assets, &self.lint_rules).into_iter().collect();
+        self.lint_results = Some(results);
+        self.problems_open = true;
+    }
+
+    // Keep the results of a file current after it changed on disk
+    pub(crate) fn relint(&mut self, path: &Path) {
+        let Some(results) = self.lint_results.as_mut() else {
+            return;
+        };
+        let is_svg = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("svg"));
+        let problems = if is_svg && path.is_file() { lint::lint_file(path, &self.lint_rules) } else { Vec::new() };
+        if problems.is_empty() {
+            results.remove(path);
+        } else {
+            results.insert(path.to_path_buf(), problems);
+        }
+    }
+
+    // Look for scripts and external references in the SVGs being listed, for the tile badges.
+    // Results are kept per file and only read again once the file changed.
+    pub(crate) fn scan_active_content(&mut self) {
+        for item in &self.current_items {
+            let FileSystemItem::SvgFile { path, .. } = item else {
+                continue;
+            };
+            let modified = fs::metadata(path).and_then(|metadata| metadata.modified()).ok();
+            let cached = self.active_content.get(path).is_some_and(|(at, _)| at.is_some() && *at == modified);
+            if !cached {
+                self.active_content.insert(path.clone(), (modified, active_content_of(path)));
+            }
+        }
+    }
+
+    pub(crate) fn recheck_active_content(&mut self, path: &Path) {
+        let modified = fs::metadata(path).and_then(|metadata| metadata.modified()).ok();
+        self.active_content.insert(path.to_path_buf(), (modified, active_content_of(path)));
+    }
+
+    // Find every vault SVG with active content and ask before rewriting them
+    pub(crate) fn scan_vault_for_active_content(&mut self) {
+        let assets = bundle::collect_assets(&[PathBuf::from(&self.vault_path)]);
+        let mut found: Vec<(PathBuf, Vec<String>)> = assets
+            .into_iter()
+            .filter_map(|path| {
+                let findings = active_content_of(&path);
+                (!findings.is_empty()).then_some((path, findings))
+            })
+            .collect();
+        found.sort();
+        if found.is_empty() {
+            self.set_error_message("✅ No scripts or external references in the vault".to_string());
+        } else {
+            self.sanitize_scan = Some(found);
         }
     }
 
     pub(crate) fn copy_svg_to_clipboard(&mut self) {
-        if let Err(e) = self.clipboard.set_text(self.svg_code.clone()) {
+        let Some(doc) = self.active_document() else {
+            return;
+        };
+        let (path, code) = (doc.path.clone(), doc.content.clone());
+        if let Err(e) = self.clipboard.set_text(code) {
             self.set_error_message(format!("Failed to copy: {}", e));
         } else {
+            self.record_recent(&path, RecentAction::Copied);
             self.set_error_message("✅ Copied to clipboard!".to_string());
         }
     }
 }
 
+// Findings for the badges. A file the sanitizer refuses to read (like one with nested
+// entities) counts as unsafe, one that can't be read from disk doesn't.
+fn active_content_of(path: &Path) -> Vec<String> {
+    match fs::read_to_string(path) {
+        Ok(source) => sanitize::find_active_content(&source).unwrap_or_else(|e| vec![e]),
+        Err(_) => Vec::new(),
+    }
+}
+
 impl Default for MyApp {
     fn default() -> Self {
         let config = AppConfig::load();
         let vault_path = config.vault_path.clone();
         let font_path = config.font_path.clone();
         let thumbnail_size = config.thumbnail_size;
+        let theme_color_map = config.theme_color_map.clone();
+        let contrast_backgrounds = config.contrast_backgrounds.clone();
+        let format_options = config.format_options.clone();
+        let favorites = config.favorites.clone();
+        let recent = config.recent.clone();
+        let mirror_metadata = config.mirror_metadata;
+        let import_options = config.import_options.clone();
+        let bundle_options = config.bundle_options.clone();
+        let mobile_export_options = config.mobile_export_options.clone();
+        let print_options = config.print_options.clone();
+        let catalog_options = config.catalog_options.clone();
+        let server_options = config.server_options.clone();
+        let metadata = MetadataStore::load(Path::new(&vault_path));
+        let collections = CollectionStore::load(Path::new(&vault_path));
+        let lint_rules = LintRules::load(Path::new(&vault_path));
 
         // Only scan directory if path is valid, otherwise use empty vec
         let current_items = if config.is_valid() {
@@ -114,7 +555,7 @@ impl Default for MyApp {
 
 
 
-        Self {
+        let mut app = Self {
             grid_reset_counter: 0,
             reset_panel_width: false,
             vault_path: vault_path.clone(),
@@ -124,7 +565,11 @@ impl Default for MyApp {
             current_items,
             current_font_input: font_path,
             selected_svg: None,
-            svg_code: String::new(),
+            selected_assets: HashSet::new(),
+            documents: Vec::new(),
+            active_document: None,
+            unsaved_prompt: None,
+            exit_confirmed: false,
             show_sidebar_right: true,
             error_message: None,
             error_message_time: None,
@@ -140,13 +585,83 @@ impl Default for MyApp {
             search_active: false,
             search_query: String::new(),
             sort_ascending: false,
+            theme_variants_open: false,
+            theme_color_map,
+            contrast_backgrounds,
+            contrast_report_open: false,
+            contrast_report: None,
+            contrast_scan: None,
+            contrast_colors: None,
+            contrast_minimum: ContrastLevel::AA,
+            confirm_invalid_save: false,
+            live_preview: LivePreview::default(),
+            format_options,
+            format_folder_open: false,
+            format_folder_action: FormatAction::PrettyPrint,
+            format_folder_recursive: true,
+            component_library_open: false,
+            component_library_kind: SnippetKind::React,
+            component_library_output: String::new(),
+            component_library_recursive: true,
+            compare: CompareState::default(),
+            sidebar_tab: SidebarTab::Details,
+            history_cache: None,
+            git_status: None,
+            git_commit_open: false,
+            git_commit_message: String::new(),
+            git_commit_selection: HashSet::new(),
+            git_log_cache: None,
+            metadata,
+            metadata_tag_input: String::new(),
+            metadata_edit_path: None,
+            mirror_metadata,
+            embedded_draft: None,
+            collections,
+            active_collection: None,
+            collection_edit: None,
+            favorites,
+            recent,
+            duplicates_open: false,
+            duplicates: DuplicatesState::default(),
+            import_open: false,
+            import: ImportState::default(),
+            import_options,
+            bundle_export: None,
+            bundle_options,
+            mobile_export: None,
+            mobile_export_options,
+            print_export: None,
+            print_options,
+            catalog: None,
+            catalog_options,
+            server: None,
+            server_options,
+            lint_rules,
+            lint_results: None,
+            problems_open: false,
+            lint_rules_edit: None,
+            active_content: HashMap::new(),
+            sanitize_scan: None,
+        };
+
+        app.refresh_git_status();
+        app.scan_active_content();
+        if app.server_options.autostart && config.is_valid() {
+            app.start_server();
         }
+        app
     }
 }
 
 impl eframe::App for MyApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
 
+        // Ask before closing the window with unsaved documents
+        if ctx.input(|i| i.viewport().close_requested()) && self.has_dirty_documents() && !self.exit_confirmed {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            self.unsaved_prompt = Some(UnsavedPrompt::Exit);
+        }
+
         // hot-keys for app ui
         ctx.input_mut(|i| {
             if i.consume_key(egui::Modifiers::CTRL, egui::Key::Comma) {
@@ -157,7 +672,7 @@ impl eframe::App for MyApp {
                 self.show_sidebar_right = !self.show_sidebar_right
             }
 
-            if self.current_view == View::Gallery || self.current_view == View::Fonts {
+            if matches!(self.current_view, View::Gallery | View::Fonts | View::Collection | View::Favorites | View::Recent) {
                 if i.consume_key(egui::Modifiers::CTRL, egui::Key::F) {
                     self.search_active = !self.search_active
                 }
@@ -169,6 +684,10 @@ impl eframe::App for MyApp {
                     self.current_view = View::Gallery;
                 }
 
+                if self.current_view == View::Compare {
+                    self.current_view = self.compare.return_view;
+                }
+
                 if self.gallery_options && self.search_active {
                     self.gallery_options = false;
                 }
@@ -179,6 +698,9 @@ impl eframe::App for MyApp {
                 else if !self.gallery_options && self.search_active {
                     self.search_active = false;
                 }
+                else if !self.selected_assets.is_empty() {
+                    self.selected_assets.clear();
+                }
             }
 
 
@@ -192,6 +714,24 @@ impl eframe::App for MyApp {
                 self.current_view = View::Fonts;
             }
         });
+        // Files dragged in from the OS go through the import wizard
+        let dropped: Vec<PathBuf> = ctx.input(|i| i.raw.dropped_files.iter().filter_map(|file| file.path.clone()).collect());
+        if !dropped.is_empty() {
+            self.open_import(&dropped);
+        }
+        if ctx.input(|i| !i.raw.hovered_files.is_empty()) {
+            let painter = ctx.layer_painter(egui::LayerId::new(egui::Order::Foreground, egui::Id::new("import_drop_target")));
+            let screen = ctx.content_rect();
+            painter.rect_filled(screen, 0.0, egui::Color32::from_black_alpha(160));
+            painter.text(
+                screen.center(),
+                egui::Align2::CENTER_CENTER,
+                "Drop to import",
+                egui::FontId::proportional(24.0),
+                egui::Color32::WHITE,
+            );
+        }
+
         if self.current_view != View::Editor {
             // render the left sidebar
             sidebar_left::render(self, ctx);
@@ -209,15 +749,87 @@ impl eframe::App for MyApp {
             crate::ui::popups::gallery_options::render(self, ctx);
         }
 
+        if self.theme_variants_open {
+            crate::ui::popups::theme_variants::render(self, ctx);
+        }
+
+        if self.contrast_report_open {
+            crate::ui::popups::contrast_report::render(self, ctx);
+        }
+
+        if self.duplicates_open {
+            crate::ui::popups::duplicates::render(self, ctx);
+        }
+
+        if self.import_open {
+            crate::ui::popups::import::render(self, ctx);
+        }
+
+        if self.bundle_export.is_some() {
+            crate::ui::popups::export_bundle::render(self, ctx);
+        }
+
+        if self.mobile_export.is_some() {
+            crate::ui::popups::mobile_export::render(self, ctx);
+        }
+
+        if self.print_export.is_some() {
+            crate::ui::popups::print_export::render(self, ctx);
+        }
+
+        if self.sanitize_scan.is_some() {
+            crate::ui::popups::sanitize::render(self, ctx);
+        }
+
+        if self.catalog.is_some() {
+            crate::ui::popups::catalog::render(self, ctx);
+        }
+
+        if self.lint_rules_edit.is_some() {
+            crate::ui::popups::lint_rules::render(self, ctx);
+        }
+
+        if self.format_folder_open {
+            crate::ui::popups::format_folder::render(self, ctx);
+        }
+
+        if self.component_library_open {
+            crate::ui::popups::component_library::render(self, ctx);
+        }
+
+        if self.metadata_edit_path.is_some() {
+            crate::ui::popups::metadata_editor::render(self, ctx);
+        }
+
+        if self.collection_edit.is_some() {
+            crate::ui::popups::collection_editor::render(self, ctx);
+        }
+
+        if self.git_commit_open {
+            crate::ui::popups::git_commit::render(self, ctx);
+        }
+
+        if self.confirm_invalid_save {
+            crate::ui::popups::invalid_save::render(self, ctx);
+        }
+
+        if self.unsaved_prompt.is_some() {
+            crate::ui::popups::unsaved_changes::render(self, ctx);
+        }
+
 
 
         // Code editor on the right when SVG is selected
-        if let View::Gallery = self.current_view {
+        if let View::Gallery | View::Collection | View::Favorites | View::Recent = self.current_view {
             if self.selected_svg.is_some() && self.show_sidebar_right {
                 crate::ui::sidebar_right::render(self, ctx);
             }
         }
 
+        if self.problems_open {
+            crate::ui::problems::render(self, ctx);
+        }
+
         CentralPanel::default()
             .frame(
                 egui::Frame::default()
@@ -249,6 +861,9 @@ impl eframe::App for MyApp {
 
                     if let Some(path) = load_svg {
                         self.load_svg(&path);
+                        if self.current_view == View::Editor {
+                            self.pin_active_document();
+                        }
                     }
                 }
                 View::Fonts => {
@@ -269,23 +884,33 @@ impl eframe::App for MyApp {
                         self.navigate_to(new_path);
                     }
                 }
+                View::Collection | View::Favorites | View::Recent => {
+                    gallery::header::render_list(self, ui);
+                    gallery::header::render_status_messages(self, ui);
+
+                    let (_, load_svg) = gallery::render_content(self, ui);
+
+                    if let Some(path) = load_svg {
+                        self.load_svg(&path);
+                        if self.current_view == View::Editor {
+                            self.pin_active_document();
+                        }
+                    }
+                }
                 View::Help => {
                     help::render(self, ui);
                 }
                 View::Editor => {
-                    // If there's an SVG selected, make sure the code is loaded
-                    if self.selected_svg.is_some() && self.svg_code.is_empty() {
-                        // This shouldn't happen, but just in case
-                        if let Some(path) = &self.selected_svg.clone() {
-                            self.load_svg(&path);
-                        }
-                    }
                     code_editor::render_editor(self, ui);
                 }
+                View::Compare => {
+                    compare::render(self, ui);
+                }
             });
     }
 
     fn on_exit(&mut self, _gl: Option<&Context>) {
         self.save_config();
+        self.save_metadata();
     }
 }
\ No newline at end of file
@@ -1,5 +1,7 @@
 pub mod sidebar_left;
 pub mod gui;
+pub mod documents;
 pub mod views;
 pub mod sidebar_right;
+pub mod problems;
 pub mod popups;
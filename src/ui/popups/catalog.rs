@@ -0,0 +1,142 @@
+use crate::models::gui::MyApp;
+use crate::utils::catalog;
+use crate::utils::import::to_kebab_case;
+use crate::utils::print_export::PageSize;
+use egui::RichText;
+use std::path::Path;
+
+pub fn render(app: &mut MyApp, ctx: &egui::Context) {
+    let Some(catalog) = app.catalog.as_ref() else {
+        return;
+    };
+    let gallery_size = app.get_thumbnail_size().x;
+    let options = &mut app.catalog_options;
+    let mut open = true;
+    let mut should_generate = false;
+
+    egui::Window::new("Generate catalog")
+        .resizable(false)
+        .collapsible(false)
+        .open(&mut open)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label(format!("Lay out {} assets with their names, dimensions and colors", catalog.assets.len()));
+            ui.add_space(10.0);
+
+            egui::Grid::new("catalog_grid")
+                .num_columns(2)
+                .spacing([10.0, 8.0])
+                .show(ui, |ui| {
+                    ui.label("Columns");
+                    ui.add(egui::Slider::new(&mut options.columns, 2..=12));
+                    ui.end_row();
+
+                    ui.label("Thumbnail size");
+                    ui.horizontal(|ui| {
+                        ui.add(egui::Slider::new(&mut options.thumbnail_size, 16.0..=200.0).suffix(" px"));
+                        if ui.button("Match gallery").clicked() {
+                            options.thumbnail_size = gallery_size;
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label("");
+                    ui.checkbox(&mut options.group_by_folder, "Group by folder");
+                    ui.end_row();
+
+                    ui.label("Output");
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut options.html, "HTML page");
+                        ui.checkbox(&mut options.pdf, "PDF");
+                    });
+                    ui.end_row();
+
+                    if options.pdf {
+                        ui.label("Page size");
+                        ui.horizontal(|ui| {
+                            for size in [PageSize::A4, PageSize::Letter] {
+                                ui.selectable_value(&mut options.page_size, size, size.label());
+                            }
+                        });
+                        ui.end_row();
+                    }
+                });
+
+            ui.add_space(5.0);
+            ui.label(
+                RichText::new("The HTML page embeds the icons and needs nothing else. The PDF sits next to it with the same name.")
+                    .size(12.0)
+                    .color(egui::Color32::from_rgb(150, 150, 150)),
+            );
+
+            ui.add_space(10.0);
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                let ready = options.html || options.pdf;
+                if ui.add_enabled(ready, egui::Button::new("Generate…")).clicked() {
+                    should_generate = true;
+                }
+            });
+        });
+
+    if should_generate {
+        generate(app);
+    } else if !open {
+        app.catalog = None;
+    }
+}
+
+fn generate(app: &mut MyApp) {
+    let Some(catalog) = &app.catalog else {
+        return;
+    };
+    let options = &app.catalog_options;
+    let name = if catalog.name.trim().is_empty() { "catalog".to_string() } else { catalog.name.clone() };
+    let (extension, filter) = if options.html { ("html", "HTML") } else { ("pdf", "PDF") };
+
+    let Some(output) = rfd::FileDialog::new()
+        .add_filter(filter, &[extension])
+        .set_file_name(format!("{}.{}", to_kebab_case(&name), extension))
+        .save_file()
+    else {
+        return;
+    };
+
+    let entries = catalog::collect_entries(Path::new(&app.vault_path), &catalog.assets);
+    let mut written = Vec::new();
+    let mut issues = Vec::new();
+    let result = (|| {
+        if options.html {
+            let html = output.with_extension("html");
+            catalog::write_html(&entries, &name, &html, options)?;
+            written.push(html.display().to_string());
+        }
+        if options.pdf {
+            let pdf = output.with_extension("pdf");
+            let pages = catalog::write_pdf(&entries, &name, &pdf, options)?;
+            written.push(format!("{} ({} pages)", pdf.display(), pages));
+            issues = catalog::pdf_issues(&entries);
+        }
+        Ok::<(), String>(())
+    })();
+
+    match result {
+        Ok(()) if issues.is_empty() => {
+            app.set_error_message(format!("✅ Catalog of {} assets: {}", entries.len(), written.join(", ")));
+            app.save_config();
+            app.catalog = None;
+        }
+        Ok(()) => {
+            let details: Vec<String> = issues.iter().map(|(name, issues)| format!("{}: {}", name, issues.join(", "))).collect();
+            app.set_error_message(format!(
+                "Catalog of {} assets: {} ({} with unsupported features in the PDF: {})",
+                entries.len(),
+                written.join(", "),
+                issues.len(),
+                details.join("; ")
+            ));
+            app.save_config();
+            app.catalog = None;
+        }
+        Err(e) => app.set_error_message(format!("Failed to generate catalog: {}", e)),
+    }
+}
@@ -0,0 +1,194 @@
+use crate::models::gui::MyApp;
+use crate::utils::collections::{CollectionKind, SmartQuery};
+use egui::RichText;
+use std::str::FromStr;
+use svgtypes::Color;
+
+pub fn render(app: &mut MyApp, ctx: &egui::Context) {
+    let Some(draft) = app.collection_edit.as_mut() else {
+        return;
+    };
+    let mut open = true;
+    let mut save_clicked = false;
+    let mut cancel_clicked = false;
+
+    let title = if draft.index.is_some() { "Edit collection" } else { "New collection" };
+
+    egui::Window::new(title)
+        .id(egui::Id::new("collection_editor"))
+        .resizable(false)
+        .collapsible(false)
+        .open(&mut open)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            egui::Grid::new("collection_editor_grid")
+                .num_columns(2)
+                .spacing([10.0, 8.0])
+                .show(ui, |ui| {
+                    ui.label("Name");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut draft.collection.name)
+                            .hint_text("Brand icons")
+                            .desired_width(240.0),
+                    );
+                    ui.end_row();
+
+                    // The kind is fixed once created, switching would drop the picked assets
+                    ui.label("Type");
+                    if draft.index.is_none() {
+                        ui.horizontal(|ui| {
+                            let smart = draft.collection.is_smart();
+                            if ui.radio(!smart, "📌 Manual").clicked() && smart {
+                                draft.collection.kind = CollectionKind::Manual { assets: Vec::new() };
+                            }
+                            if ui.radio(smart, "🔎 Smart").clicked() && !smart {
+                                draft.collection.kind = CollectionKind::Smart { query: SmartQuery::default() };
+                            }
+                        });
+                    } else {
+                        ui.label(if draft.collection.is_smart() { "🔎 Smart" } else { "📌 Manual" });
+                    }
+                    ui.end_row();
+                });
+
+            match &mut draft.collection.kind {
+                CollectionKind::Manual { assets } => {
+                    ui.add_space(5.0);
+                    ui.label(
+                        RichText::new(format!(
+                            "{} assets. Add more from the gallery context menu.",
+                            assets.len()
+                        ))
+                        .weak(),
+                    );
+                }
+                CollectionKind::Smart { query } => {
+                    ui.separator();
+                    ui.label(RichText::new("Assets matching all of:").strong());
+                    ui.add_space(5.0);
+                    render_query(ui, query, &mut draft.tags_input);
+                }
+            }
+
+            ui.add_space(10.0);
+
+            ui.horizontal(|ui| {
+                let can_save = !draft.collection.name.trim().is_empty();
+                if ui.add_enabled(can_save, egui::Button::new("Save")).clicked() {
+                    save_clicked = true;
+                }
+                if ui.button("Cancel").clicked() {
+                    cancel_clicked = true;
+                }
+            });
+        });
+
+    if save_clicked {
+        save(app);
+    } else if cancel_clicked || !open {
+        app.collection_edit = None;
+    }
+}
+
+fn render_query(ui: &mut egui::Ui, query: &mut SmartQuery, tags_input: &mut String) {
+    egui::Grid::new("smart_query_grid")
+        .num_columns(2)
+        .spacing([10.0, 8.0])
+        .show(ui, |ui| {
+            ui.label("Name contains");
+            ui.add(egui::TextEdit::singleline(&mut query.name).desired_width(240.0));
+            ui.end_row();
+
+            ui.label("Tags");
+            ui.add(
+                egui::TextEdit::singleline(tags_input)
+                    .hint_text("icon, brand")
+                    .desired_width(240.0),
+            );
+            ui.end_row();
+
+            ui.label("Uses color");
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::TextEdit::singleline(&mut query.color)
+                        .hint_text("#FF0000")
+                        .desired_width(120.0),
+                );
+                if !query.color.trim().is_empty() {
+                    match Color::from_str(query.color.trim()) {
+                        Ok(c) => {
+                            let (rect, _) = ui.allocate_exact_size(egui::vec2(16.0, 16.0), egui::Sense::hover());
+                            ui.painter().rect_filled(
+                                rect,
+                                3.0,
+                                egui::Color32::from_rgba_unmultiplied(c.red, c.green, c.blue, c.alpha),
+                            );
+                        }
+                        Err(_) => {
+                            ui.colored_label(egui::Color32::RED, "Invalid color");
+                        }
+                    }
+                }
+            });
+            ui.end_row();
+
+            render_optional(ui, "Min size", &mut query.min_size_kb, 1.0, " KB");
+            render_optional(ui, "Max size", &mut query.max_size_kb, 10.0, " KB");
+            render_optional(ui, "Modified within", &mut query.modified_within_days, 7, " days");
+        });
+}
+
+// Checkbox enabling a criterion, with its value next to it
+fn render_optional<T: egui::emath::Numeric>(
+    ui: &mut egui::Ui,
+    label: &str,
+    value: &mut Option<T>,
+    default: T,
+    suffix: &str,
+) {
+    let mut enabled = value.is_some();
+    if ui.checkbox(&mut enabled, label).changed() {
+        *value = enabled.then_some(default);
+    }
+    if let Some(value) = value {
+        ui.add(egui::DragValue::new(value).range(0.0..=f64::MAX).suffix(suffix));
+    }
+    ui.end_row();
+}
+
+fn save(app: &mut MyApp) {
+    let Some(mut draft) = app.collection_edit.take() else {
+        return;
+    };
+
+    if let CollectionKind::Smart { query } = &mut draft.collection.kind {
+        query.tags = draft
+            .tags_input
+            .split(',')
+            .map(|tag| tag.trim().trim_start_matches('#').to_string())
+            .filter(|tag| !tag.is_empty())
+            .collect();
+
+        if !query.color.trim().is_empty() && Color::from_str(query.color.trim()).is_err() {
+            app.set_error_message(format!("Invalid color: {}", query.color));
+            app.collection_edit = Some(draft);
+            return;
+        }
+    }
+
+    draft.collection.name = draft.collection.name.trim().to_string();
+    let collections = &mut app.collections.collections;
+    let index = match draft.index {
+        Some(index) if index < collections.len() => {
+            collections[index] = draft.collection;
+            index
+        }
+        _ => {
+            collections.push(draft.collection);
+            collections.len() - 1
+        }
+    };
+
+    app.save_collections();
+    app.open_collection(index);
+}
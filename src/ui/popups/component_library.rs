@@ -0,0 +1,88 @@
+use crate::models::gui::MyApp;
+use crate::utils::snippets::{self, SnippetKind};
+use egui::RichText;
+use std::path::PathBuf;
+
+pub fn render(app: &mut MyApp, ctx: &egui::Context) {
+    let mut open = true;
+    let mut should_run = false;
+
+    egui::Window::new("Component Library")
+        .resizable(false)
+        .collapsible(false)
+        .open(&mut open)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label("Generate a component for every SVG in the current folder:");
+            ui.add_space(10.0);
+
+            ui.horizontal(|ui| {
+                for kind in SnippetKind::COMPONENTS {
+                    ui.selectable_value(&mut app.component_library_kind, kind, kind.label());
+                }
+            });
+
+            ui.add_space(5.0);
+            ui.checkbox(&mut app.component_library_recursive, "Include subfolders");
+
+            ui.add_space(5.0);
+            ui.horizontal(|ui| {
+                ui.label("Output");
+                ui.add(
+                    egui::TextEdit::singleline(&mut app.component_library_output)
+                        .hint_text("Folder for the components")
+                        .desired_width(260.0),
+                );
+                if ui.button("Browse…").clicked() {
+                    if let Some(folder) = rfd::FileDialog::new().pick_folder() {
+                        app.component_library_output = folder.display().to_string();
+                    }
+                }
+            });
+
+            ui.add_space(10.0);
+
+            ui.label(
+                RichText::new(format!(
+                    "Folder: {}\nComponents are named after the files and exported from index.ts",
+                    app.current_path
+                ))
+                .size(12.0)
+                .color(egui::Color32::from_rgb(150, 150, 150)),
+            );
+
+            ui.add_space(5.0);
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                let ready = !app.component_library_output.trim().is_empty();
+                if ui.add_enabled(ready, egui::Button::new("Generate")).clicked() {
+                    should_run = true;
+                }
+            });
+        });
+
+    if should_run {
+        let source = PathBuf::from(&app.current_path);
+        let output = PathBuf::from(app.component_library_output.trim());
+        let kind = app.component_library_kind;
+        match snippets::generate_library(kind, &source, &output, app.component_library_recursive) {
+            Ok(report) => {
+                if report.errors.is_empty() {
+                    app.set_error_message(format!("✅ Generated {} components", report.components));
+                } else {
+                    app.set_error_message(format!(
+                        "Generated {} components ({})",
+                        report.components,
+                        report.errors.join("; ")
+                    ));
+                }
+                app.component_library_open = false;
+            }
+            Err(e) => app.set_error_message(format!("Failed to generate components: {}", e)),
+        }
+    }
+
+    if !open {
+        app.component_library_open = false;
+    }
+}
@@ -0,0 +1,119 @@
+use crate::models::background::BackgroundJob;
+use crate::models::gui::MyApp;
+use crate::ui::sidebar_right::contrast::{level_color, render_swatch};
+use crate::utils::contrast::{self, ContrastLevel};
+use egui::RichText;
+use std::path::PathBuf;
+
+pub fn render(app: &mut MyApp, ctx: &egui::Context) {
+    let mut open = true;
+    let mut should_scan = false;
+    let mut select_svg: Option<PathBuf> = None;
+
+    egui::Window::new("Contrast Report")
+        .resizable(true)
+        .collapsible(false)
+        .open(&mut open)
+        .default_size([420.0, 480.0])
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Flag colors below:");
+                ui.selectable_value(&mut app.contrast_minimum, ContrastLevel::AA, "AA (3:1)");
+                ui.selectable_value(&mut app.contrast_minimum, ContrastLevel::AAA, "AAA (4.5:1)");
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    let scanning = app.contrast_scan.is_some();
+                    if ui.add_enabled(!scanning, egui::Button::new("Scan vault")).clicked() {
+                        should_scan = true;
+                    }
+                    if scanning {
+                        ui.spinner();
+                    }
+                });
+            });
+
+            ui.add_space(5.0);
+            ui.separator();
+
+            let Some(reports) = &app.contrast_report else {
+                ui.label(RichText::new("Run a scan to list failing icons").weak());
+                return;
+            };
+
+            if reports.is_empty() {
+                ui.label("✅ Every icon passes against the configured backgrounds");
+                return;
+            }
+
+            ui.label(format!("{} icons below threshold", reports.len()));
+            ui.add_space(5.0);
+
+            egui::ScrollArea::vertical()
+                .auto_shrink([false, false])
+                .show(ui, |ui| {
+                    for report in reports {
+                        ui.horizontal(|ui| {
+                            let img_uri = format!("file://{}", report.path.display());
+                            ui.add(egui::Image::new(img_uri).fit_to_exact_size(egui::vec2(24.0, 24.0)));
+
+                            if ui.link(&report.name).clicked() {
+                                select_svg = Some(report.path.clone());
+                            }
+                        });
+
+                        ui.indent(report.path.display().to_string(), |ui| {
+                            for failure in &report.failures {
+                                ui.horizontal(|ui| {
+                                    render_swatch(ui, &failure.color);
+                                    ui.label("on");
+                                    render_swatch(ui, &failure.background);
+                                    ui.label(
+                                        RichText::new(format!(
+                                            "{} on {}: {:.2}:1 {}",
+                                            contrast::to_hex(&failure.color),
+                                            contrast::to_hex(&failure.background),
+                                            failure.ratio,
+                                            failure.level.label()
+                                        ))
+                                        .size(11.0)
+                                        .monospace()
+                                        .color(level_color(failure.level)),
+                                    );
+                                });
+                            }
+                        });
+
+                        ui.add_space(4.0);
+                    }
+                });
+        });
+
+    if should_scan {
+        let backgrounds = contrast::parse_backgrounds(&app.contrast_backgrounds);
+        let vault_path = app.vault_path.clone();
+        let minimum = app.contrast_minimum;
+        app.contrast_scan = Some(BackgroundJob::spawn(move || {
+            contrast::scan_vault(&vault_path, &backgrounds, minimum).map_err(|e| e.to_string())
+        }));
+    }
+
+    // Rasterizing every icon takes a while, the window stays responsive meanwhile
+    if app.contrast_scan.is_some() {
+        ctx.request_repaint_after(std::time::Duration::from_millis(100));
+    }
+    match BackgroundJob::poll(&mut app.contrast_scan).map(|result| result.and_then(|reports| reports)) {
+        Some(Ok(reports)) => app.contrast_report = Some(reports),
+        Some(Err(e)) => app.set_error_message(format!("Failed to scan vault: {}", e)),
+        None => {}
+    }
+
+    if let Some(path) = select_svg {
+        app.load_svg(&path);
+        app.show_sidebar_right = true;
+    }
+
+    if !open {
+        app.contrast_report_open = false;
+    }
+}
@@ -1,5 +1,7 @@
 use crate::models::gui::MyApp;
+use crate::utils::history;
 use std::fs;
+use std::path::PathBuf;
 
 pub fn render(app: &mut MyApp, ctx: &egui::Context) {
     let mut should_close = false;
@@ -64,30 +66,20 @@ pub fn render(app: &mut MyApp, ctx: &egui::Context) {
 
     if should_delete {
         if let Some(path) = path_to_delete {
-            match fs::remove_file(&path) {
+            // Snapshot first, so the history can bring the file back
+            let vault = PathBuf::from(&app.vault_path);
+            let deleted = history::record_deletion(&vault, &path)
+                .map_err(|e| e.to_string())
+                .and_then(|_| fs::remove_file(&path).map_err(|e| e.to_string()));
+            app.history_cache = None;
+            match deleted {
                 Ok(_) => {
-                    app.set_error_message("✅ File deleted successfully".to_string());
-
-                    // Remove the item from current_items
-                    app.current_items.retain(|item| {
-                        use crate::models::file_items::FileSystemItem;
-                        match item {
-                            FileSystemItem::SvgFile { path: p, .. } => p != &path,
-                            FileSystemItem::FontFile { path: p, .. } => p != &path,
-                            FileSystemItem::Directory { path: p, .. } => p != &path,
-                        }
-                    });
-
-                    // Clear selected_svg if it was deleted
-                    if let Some(selected) = &app.selected_svg {
-                        if selected == &path {
-                            app.selected_svg = None;
-                            app.svg_code.clear();
-                        }
+                    if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("svg")) {
+                        app.set_error_message("✅ File deleted, restore it from History › Deleted assets".to_string());
+                    } else {
+                        app.set_error_message("✅ File deleted successfully".to_string());
                     }
-
-                    // Force grid to reset
-                    app.grid_reset_counter = app.grid_reset_counter.wrapping_add(1);
+                    app.forget_asset(&path);
                 }
                 Err(e) => {
                     app.set_error_message(format!("Failed to delete: {}", e));
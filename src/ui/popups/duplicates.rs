@@ -0,0 +1,261 @@
+use crate::models::background::BackgroundJob;
+use crate::models::duplicates::DuplicateAction;
+use crate::models::gui::MyApp;
+use crate::utils::duplicates::{self, DuplicateGroup, MatchKind};
+use crate::utils::history;
+use egui::RichText;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const THUMBNAIL_SIZE: f32 = 64.0;
+
+pub fn render(app: &mut MyApp, ctx: &egui::Context) {
+    let mut open = true;
+    let mut should_scan = false;
+    let mut select_svg: Option<PathBuf> = None;
+    let mut request: Option<DuplicateAction> = None;
+    let mut confirmed = false;
+    let mut cancelled = false;
+
+    egui::Window::new("Duplicates")
+        .resizable(true)
+        .collapsible(false)
+        .open(&mut open)
+        .default_size([560.0, 520.0])
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Similarity tolerance:");
+                ui.add(egui::Slider::new(&mut app.duplicates.max_distance, 0..=16).suffix(" bits"))
+                    .on_hover_text("How many of the 64 perceptual hash bits may differ for icons to count as similar");
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    let scanning = app.duplicates.scan.is_some();
+                    if ui.add_enabled(!scanning, egui::Button::new("Scan vault")).clicked() {
+                        should_scan = true;
+                    }
+                    if scanning {
+                        ui.spinner();
+                    }
+                });
+            });
+
+            ui.add_space(5.0);
+            ui.separator();
+
+            // Deleting asks first, the rest of the window waits meanwhile
+            if let Some(action) = &app.duplicates.confirm {
+                let count = match action {
+                    DuplicateAction::Keep { group, keep, .. } => app
+                        .duplicates
+                        .groups
+                        .as_ref()
+                        .and_then(|groups| groups.get(*group))
+                        .map(|group| group.assets.iter().filter(|asset| *asset != keep).count())
+                        .unwrap_or(0),
+                    DuplicateAction::Delete(_) => 1,
+                };
+
+                ui.horizontal(|ui| {
+                    ui.label(
+                        RichText::new(format!("⚠ Permanently delete {} file{}?", count, if count == 1 { "" } else { "s" }))
+                            .color(egui::Color32::from_rgb(255, 200, 0)),
+                    );
+                    if ui.button(RichText::new("Delete").color(egui::Color32::from_rgb(255, 100, 100))).clicked() {
+                        confirmed = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        cancelled = true;
+                    }
+                });
+                ui.separator();
+            }
+
+            let Some(groups) = &app.duplicates.groups else {
+                ui.label(RichText::new("Run a scan to find copies and look-alikes").weak());
+                return;
+            };
+
+            if groups.is_empty() {
+                ui.label("✅ No duplicates found");
+                return;
+            }
+
+            ui.label(format!("{} groups", groups.len()));
+            ui.add_space(5.0);
+
+            let vault = PathBuf::from(&app.vault_path);
+            egui::ScrollArea::vertical()
+                .auto_shrink([false, false])
+                .show(ui, |ui| {
+                    for (index, group) in groups.iter().enumerate() {
+                        render_group(ui, &vault, index, group, &mut select_svg, &mut request);
+                        ui.add_space(8.0);
+                    }
+                });
+        });
+
+    if should_scan {
+        let vault_path = app.vault_path.clone();
+        let max_distance = app.duplicates.max_distance;
+        app.duplicates.scan = Some(BackgroundJob::spawn(move || {
+            duplicates::scan_vault(&vault_path, max_distance).map_err(|e| e.to_string())
+        }));
+    }
+
+    // Fingerprinting rasterizes every icon, the window stays responsive meanwhile
+    if app.duplicates.scan.is_some() {
+        ctx.request_repaint_after(std::time::Duration::from_millis(100));
+    }
+    match BackgroundJob::poll(&mut app.duplicates.scan).map(|result| result.and_then(|groups| groups)) {
+        Some(Ok(groups)) => {
+            app.duplicates.groups = Some(groups);
+            app.duplicates.confirm = None;
+        }
+        Some(Err(e)) => app.set_error_message(format!("Failed to scan vault: {}", e)),
+        None => {}
+    }
+
+    if let Some(action) = request {
+        app.duplicates.confirm = Some(action);
+    }
+
+    if cancelled {
+        app.duplicates.confirm = None;
+    }
+
+    if confirmed {
+        if let Some(action) = app.duplicates.confirm.take() {
+            apply(app, action);
+        }
+    }
+
+    if let Some(path) = select_svg {
+        app.load_svg(&path);
+        app.show_sidebar_right = true;
+    }
+
+    if !open {
+        app.duplicates_open = false;
+        app.duplicates.confirm = None;
+    }
+}
+
+fn render_group(
+    ui: &mut egui::Ui,
+    vault: &Path,
+    index: usize,
+    group: &DuplicateGroup,
+    select_svg: &mut Option<PathBuf>,
+    request: &mut Option<DuplicateAction>,
+) {
+    egui::Frame::new()
+        .fill(egui::Color32::from_rgb(35, 39, 42))
+        .inner_margin(egui::Margin::same(8))
+        .corner_radius(8.0)
+        .show(ui, |ui| {
+            ui.set_width(ui.available_width());
+
+            let mut heading = format!("{} · {} files", group.kind.label(), group.assets.len());
+            if group.kind == MatchKind::Similar {
+                heading.push_str(&format!(" · up to {} bits apart", group.distance));
+            }
+            ui.label(RichText::new(heading).strong());
+            ui.add_space(4.0);
+
+            ui.horizontal_wrapped(|ui| {
+                for asset in &group.assets {
+                    ui.vertical(|ui| {
+                        ui.set_width(THUMBNAIL_SIZE + 60.0);
+
+                        let img_uri = format!("file://{}", asset.display());
+                        ui.add(egui::Image::new(img_uri).fit_to_exact_size(egui::vec2(THUMBNAIL_SIZE, THUMBNAIL_SIZE)));
+
+                        let name = asset.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                        if ui.link(RichText::new(name).size(11.0)).clicked() {
+                            *select_svg = Some(asset.clone());
+                        }
+
+                        let folder = asset
+                            .parent()
+                            .and_then(|parent| parent.strip_prefix(vault).ok())
+                            .map(|relative| relative.display().to_string())
+                            .unwrap_or_default();
+                        let size = fs::metadata(asset).map(|m| m.len()).unwrap_or(0);
+                        ui.label(RichText::new(format!("/{} · {} B", folder, size)).size(9.0).weak());
+
+                        ui.horizontal(|ui| {
+                            if ui.small_button("Keep").on_hover_text("Keep this one, delete the others").clicked() {
+                                *request = Some(DuplicateAction::Keep { group: index, keep: asset.clone(), merge: false });
+                            }
+                            if ui
+                                .small_button("Merge")
+                                .on_hover_text("Keep this one with the tags, collections and favorites of the others, then delete them")
+                                .clicked()
+                            {
+                                *request = Some(DuplicateAction::Keep { group: index, keep: asset.clone(), merge: true });
+                            }
+                            if ui.small_button("🗑").on_hover_text("Delete this file").clicked() {
+                                *request = Some(DuplicateAction::Delete(asset.clone()));
+                            }
+                        });
+                    });
+                }
+            });
+        });
+}
+
+fn apply(app: &mut MyApp, action: DuplicateAction) {
+    let remove = match action {
+        DuplicateAction::Delete(path) => vec![path],
+        DuplicateAction::Keep { group, keep, merge } => {
+            let others: Vec<PathBuf> = app
+                .duplicates
+                .groups
+                .as_ref()
+                .and_then(|groups| groups.get(group))
+                .map(|group| group.assets.iter().filter(|asset| **asset != keep).cloned().collect())
+                .unwrap_or_default();
+
+            if merge {
+                for other in &others {
+                    app.metadata.merge(other, &keep);
+                    app.collections.merge(other, &keep);
+                    if app.is_favorite(other) && !app.is_favorite(&keep) {
+                        app.favorites.push(keep.clone());
+                    }
+                }
+            }
+            others
+        }
+    };
+
+    let vault = PathBuf::from(&app.vault_path);
+    let mut deleted = 0;
+    let mut errors = Vec::new();
+    for path in remove {
+        // Snapshot first, so the history can bring the file back
+        let removed = history::record_deletion(&vault, &path)
+            .map_err(|e| e.to_string())
+            .and_then(|_| fs::remove_file(&path).map_err(|e| e.to_string()));
+        match removed {
+            Ok(_) => {
+                app.forget_asset(&path);
+                app.duplicates.forget(&path);
+                deleted += 1;
+            }
+            Err(e) => errors.push(format!("{}: {}", path.display(), e)),
+        }
+    }
+    app.history_cache = None;
+
+    if errors.is_empty() {
+        app.set_error_message(format!(
+            "✅ Deleted {} file{}, restore them from History › Deleted assets",
+            deleted,
+            if deleted == 1 { "" } else { "s" }
+        ));
+    } else {
+        app.set_error_message(format!("Failed to delete {}", errors.join(", ")));
+    }
+}
@@ -0,0 +1,110 @@
+use crate::models::gui::MyApp;
+use crate::utils::bundle::{self, LICENSE_FILE, MANIFEST_FILE};
+use crate::utils::import::to_kebab_case;
+use egui::RichText;
+use std::path::PathBuf;
+
+pub fn render(app: &mut MyApp, ctx: &egui::Context) {
+    let Some(export) = app.bundle_export.as_mut() else {
+        return;
+    };
+    let options = &mut app.bundle_options;
+    let mut open = true;
+    let mut should_export = false;
+
+    egui::Window::new("Export bundle")
+        .resizable(false)
+        .collapsible(false)
+        .open(&mut open)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label(format!("Package {} assets into a ZIP", export.assets.len()));
+            ui.add_space(10.0);
+
+            egui::Grid::new("export_bundle_grid")
+                .num_columns(2)
+                .spacing([10.0, 8.0])
+                .show(ui, |ui| {
+                    ui.label("Formats");
+                    ui.vertical(|ui| {
+                        ui.checkbox(&mut options.svg, "SVG");
+                        ui.checkbox(&mut options.minified_svg, "Minified SVG");
+                    });
+                    ui.end_row();
+
+                    ui.label("PNG sizes");
+                    if ui
+                        .add(
+                            egui::TextEdit::singleline(&mut export.sizes_input)
+                                .hint_text("e.g. 32, 64, 128")
+                                .desired_width(200.0),
+                        )
+                        .changed()
+                    {
+                        options.png_sizes = bundle::parse_sizes(&export.sizes_input);
+                    }
+                    ui.end_row();
+
+                    ui.label("License");
+                    ui.add(
+                        egui::TextEdit::multiline(&mut options.license)
+                            .hint_text("Leave empty to list the licenses from the asset metadata")
+                            .desired_rows(4)
+                            .desired_width(280.0),
+                    );
+                    ui.end_row();
+                });
+
+            ui.add_space(5.0);
+            ui.label(
+                RichText::new(format!(
+                    "{} lists every asset with its metadata, dimensions and colors. The license goes to {}.",
+                    MANIFEST_FILE, LICENSE_FILE
+                ))
+                .size(12.0)
+                .color(egui::Color32::from_rgb(150, 150, 150)),
+            );
+
+            ui.add_space(10.0);
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.add_enabled(options.has_formats(), egui::Button::new("Export…")).clicked() {
+                    should_export = true;
+                }
+            });
+        });
+
+    if should_export {
+        export_to_file(app);
+    } else if !open {
+        app.bundle_export = None;
+    }
+}
+
+fn export_to_file(app: &mut MyApp) {
+    let Some(export) = &app.bundle_export else {
+        return;
+    };
+    let name = if export.name.trim().is_empty() { "assets".to_string() } else { to_kebab_case(&export.name) };
+
+    let Some(output) = rfd::FileDialog::new()
+        .add_filter("ZIP", &["zip"])
+        .set_file_name(format!("{}.zip", name))
+        .save_file()
+    else {
+        return;
+    };
+
+    let vault = PathBuf::from(&app.vault_path);
+    match bundle::export_bundle(&vault, &export.assets, &output, &app.bundle_options, &app.metadata, &app.format_options) {
+        Ok(report) => {
+            if report.errors.is_empty() {
+                app.set_error_message(format!("✅ Exported {}", report.summary()));
+            } else {
+                app.set_error_message(format!("Exported {} ({})", report.summary(), report.errors.join("; ")));
+            }
+            app.save_config();
+            app.bundle_export = None;
+        }
+        Err(e) => app.set_error_message(format!("Failed to export bundle: {}", e)),
+    }
+}
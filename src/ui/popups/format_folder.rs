@@ -0,0 +1,85 @@
+use crate::models::gui::MyApp;
+use crate::utils::svg_format::{self, FormatAction};
+use egui::RichText;
+use std::path::PathBuf;
+
+pub fn render(app: &mut MyApp, ctx: &egui::Context) {
+    let mut open = true;
+    let mut should_run = false;
+
+    egui::Window::new("Format Folder")
+        .resizable(false)
+        .collapsible(false)
+        .open(&mut open)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label("Rewrite every SVG in the current folder:");
+            ui.add_space(10.0);
+
+            ui.horizontal(|ui| {
+                for action in FormatAction::ALL {
+                    ui.selectable_value(&mut app.format_folder_action, action, action.label());
+                }
+            });
+
+            ui.add_space(5.0);
+            ui.checkbox(&mut app.format_folder_recursive, "Include subfolders");
+
+            ui.add_space(10.0);
+
+            ui.label(
+                RichText::new(format!(
+                    "Folder: {}\nIndentation, attribute order, precision and quotes are set in Settings",
+                    app.current_path
+                ))
+                .size(12.0)
+                .color(egui::Color32::from_rgb(150, 150, 150)),
+            );
+
+            if app.has_dirty_documents() {
+                ui.add_space(5.0);
+                ui.label(
+                    RichText::new("⚠ Open documents with unsaved changes are not reloaded")
+                        .size(12.0)
+                        .color(egui::Color32::from_rgb(255, 200, 0)),
+                );
+            }
+
+            ui.add_space(5.0);
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.button("Run").clicked() {
+                    should_run = true;
+                }
+            });
+        });
+
+    if should_run {
+        let vault = PathBuf::from(&app.vault_path);
+        let root = PathBuf::from(&app.current_path);
+        let action = app.format_folder_action;
+        match svg_format::format_folder(&vault, &root, app.format_folder_recursive, action, &app.format_options) {
+            Ok(report) => {
+                if report.errors.is_empty() {
+                    app.set_error_message(format!("✅ {}: {}", action.label(), report.summary()));
+                } else {
+                    app.set_error_message(format!(
+                        "{}: {} ({})",
+                        action.label(),
+                        report.summary(),
+                        report.errors.join("; ")
+                    ));
+                }
+                app.history_cache = None;
+                app.reload_clean_documents();
+                app.rescan_current_directory();
+                app.format_folder_open = false;
+            }
+            Err(e) => app.set_error_message(format!("Failed to format folder: {}", e)),
+        }
+    }
+
+    if !open {
+        app.format_folder_open = false;
+    }
+}
@@ -0,0 +1,126 @@
+use crate::models::gui::MyApp;
+use crate::utils::git::{self, FileStatus};
+use egui::RichText;
+use std::path::PathBuf;
+
+pub fn render(app: &mut MyApp, ctx: &egui::Context) {
+    let mut open = true;
+    let mut should_commit = false;
+
+    let vault = PathBuf::from(&app.vault_path);
+    let mut changes: Vec<(PathBuf, FileStatus)> = app
+        .git_status
+        .iter()
+        .flat_map(|status| status.iter().map(|(path, status)| (path.clone(), *status)))
+        .collect();
+    changes.sort_by(|a, b| a.0.cmp(&b.0));
+
+    egui::Window::new("Commit Changes")
+        .resizable(true)
+        .collapsible(false)
+        .open(&mut open)
+        .default_size([420.0, 420.0])
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            if changes.is_empty() {
+                ui.label("✅ Nothing to commit, the vault is clean");
+                return;
+            }
+
+            ui.horizontal(|ui| {
+                ui.label(format!("{} changed files", changes.len()));
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.small_button("None").clicked() {
+                        app.git_commit_selection.clear();
+                    }
+                    if ui.small_button("All").clicked() {
+                        app.git_commit_selection = changes.iter().map(|(path, _)| path.clone()).collect();
+                    }
+                });
+            });
+
+            ui.add_space(5.0);
+
+            egui::ScrollArea::vertical()
+                .max_height(220.0)
+                .auto_shrink([false, true])
+                .show(ui, |ui| {
+                    for (path, status) in &changes {
+                        let relative = path.strip_prefix(&vault).unwrap_or(path).display().to_string();
+                        let mut selected = app.git_commit_selection.contains(path);
+
+                        ui.horizontal(|ui| {
+                            if ui.checkbox(&mut selected, "").changed() {
+                                if selected {
+                                    app.git_commit_selection.insert(path.clone());
+                                } else {
+                                    app.git_commit_selection.remove(path);
+                                }
+                            }
+                            ui.label(
+                                RichText::new(status.badge())
+                                    .monospace()
+                                    .strong()
+                                    .color(status_color(*status)),
+                            )
+                            .on_hover_text(status.label());
+                            ui.label(relative);
+                        });
+                    }
+                });
+
+            ui.add_space(10.0);
+            ui.label("Message:");
+            ui.add(
+                egui::TextEdit::multiline(&mut app.git_commit_message)
+                    .desired_rows(3)
+                    .desired_width(f32::INFINITY),
+            );
+
+            ui.add_space(5.0);
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                let ready = !app.git_commit_message.trim().is_empty() && !app.git_commit_selection.is_empty();
+                if ui.add_enabled(ready, egui::Button::new("Commit")).clicked() {
+                    should_commit = true;
+                }
+            });
+        });
+
+    if should_commit {
+        let paths: Vec<PathBuf> = changes
+            .iter()
+            .map(|(path, _)| path.clone())
+            .filter(|path| app.git_commit_selection.contains(path))
+            .collect();
+
+        let result = match git::open_repo(&vault) {
+            Some(repo) => git::commit(&repo, &paths, app.git_commit_message.trim()).map_err(|e| e.to_string()),
+            None => Err("The vault is not a git repository".to_string()),
+        };
+
+        match result {
+            Ok(id) => {
+                app.set_error_message(format!("✅ Committed {} files ({})", paths.len(), &id[..7]));
+                app.git_commit_message.clear();
+                app.git_commit_open = false;
+                app.refresh_git_status();
+            }
+            Err(e) => app.set_error_message(format!("Failed to commit: {}", e)),
+        }
+    }
+
+    if !open {
+        app.git_commit_open = false;
+    }
+}
+
+pub(crate) fn status_color(status: FileStatus) -> egui::Color32 {
+    match status {
+        FileStatus::New => egui::Color32::from_rgb(100, 220, 100),
+        FileStatus::Modified => egui::Color32::from_rgb(230, 200, 80),
+        FileStatus::Untracked => egui::Color32::from_rgb(120, 170, 255),
+        FileStatus::Deleted => egui::Color32::from_rgb(255, 100, 100),
+    }
+}
@@ -0,0 +1,330 @@
+use crate::models::gui::MyApp;
+use crate::models::import::ArchiveImport;
+use crate::utils::import::{self, AssetKind, ImportOptions, ImportReport};
+use crate::utils::svg_format::FormatAction;
+use egui::RichText;
+use std::path::PathBuf;
+
+const THUMBNAIL_SIZE: f32 = 48.0;
+
+pub fn render(app: &mut MyApp, ctx: &egui::Context) {
+    let mut open = true;
+    let mut add: Vec<PathBuf> = Vec::new();
+    let mut should_import = false;
+
+    egui::Window::new("Import assets")
+        .resizable(true)
+        .collapsible(false)
+        .open(&mut open)
+        .default_size([520.0, 480.0])
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                if ui.button("Add files…").clicked() {
+                    if let Some(files) = rfd::FileDialog::new()
+                        .add_filter("SVGs, fonts and archives", &["svg", "ttf", "otf", "woff", "woff2", "zip", "tgz", "gz"])
+                        .pick_files()
+                    {
+                        add.extend(files);
+                    }
+                }
+                if ui.button("Add folder…").clicked() {
+                    if let Some(folder) = rfd::FileDialog::new().pick_folder() {
+                        add.push(folder);
+                    }
+                }
+                if !app.import.sources.is_empty() && ui.button("Clear").clicked() {
+                    app.import.sources.clear();
+                }
+            });
+
+            ui.add_space(5.0);
+            render_sources(app, ui);
+
+            let mut close_archive = false;
+            if let Some(archive) = &mut app.import.archive {
+                ui.separator();
+                close_archive = render_archive(ui, archive, &app.import_options);
+            }
+            if close_archive {
+                app.import.archive = None;
+            }
+
+            ui.separator();
+            render_options(ui, &mut app.import_options, &mut app.import.destination);
+
+            ui.add_space(10.0);
+            ui.horizontal(|ui| {
+                let archive_selection = app.import.archive.as_ref().map_or(0, |archive| archive.selected_count());
+                let can_import = (!app.import.sources.is_empty() || archive_selection > 0)
+                    && !app.import.destination.trim().is_empty();
+                if ui.add_enabled(can_import, egui::Button::new("Import")).clicked() {
+                    should_import = true;
+                }
+                ui.label(RichText::new("Name conflicts get a numbered suffix").size(12.0).weak());
+            });
+
+            if let Some(report) = &app.import.report {
+                ui.separator();
+                ui.label(RichText::new(format!("✅ {}", report.summary())).strong());
+                for (source, reason) in &report.rejected {
+                    let name = source.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                    ui.label(
+                        RichText::new(format!("✖ {}: {}", name, reason))
+                            .size(12.0)
+                            .color(egui::Color32::from_rgb(255, 100, 100)),
+                    )
+                    .on_hover_text(source.display().to_string());
+                }
+                for (source, removed) in &report.sanitized {
+                    let name = source.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                    ui.label(
+                        RichText::new(format!("⚠ {}: {} removed", name, removed.len()))
+                            .size(12.0)
+                            .color(egui::Color32::from_rgb(255, 200, 0)),
+                    )
+                    .on_hover_text(removed.join("\n"));
+                }
+            }
+        });
+
+    if !add.is_empty() {
+        app.open_import(&add);
+    }
+
+    if should_import {
+        run(app);
+    }
+
+    if !open {
+        app.import_open = false;
+    }
+}
+
+fn render_sources(app: &mut MyApp, ui: &mut egui::Ui) {
+    if app.import.sources.is_empty() {
+        if app.import.archive.is_some() {
+            return;
+        }
+        ui.label(RichText::new("Drop SVGs, fonts or folders onto the window, or add them here").weak());
+        return;
+    }
+
+    let mut remove: Option<usize> = None;
+    egui::ScrollArea::vertical()
+        .max_height(200.0)
+        .auto_shrink([false, true])
+        .show(ui, |ui| {
+            egui::Grid::new("import_sources_grid")
+                .num_columns(3)
+                .striped(true)
+                .show(ui, |ui| {
+                    for (idx, source) in app.import.sources.iter().enumerate() {
+                        let name = source.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                        ui.label(RichText::new(name).size(12.0)).on_hover_text(source.display().to_string());
+
+                        let target = if import::asset_kind(source).is_some() {
+                            format!("→ {}", import::target_name(source, &app.import_options))
+                        } else {
+                            "unsupported".to_string()
+                        };
+                        ui.label(RichText::new(target).size(12.0).weak());
+
+                        if ui.small_button("✕").on_hover_text("Leave out").clicked() {
+                            remove = Some(idx);
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+
+    if let Some(idx) = remove {
+        app.import.sources.remove(idx);
+    }
+}
+
+// Returns true when the archive should be closed
+fn render_archive(ui: &mut egui::Ui, archive: &mut ArchiveImport, options: &ImportOptions) -> bool {
+    let mut close = false;
+    let name = archive.path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let problems = archive.entries.iter().filter(|entry| entry.problem.is_some()).count();
+
+    ui.horizontal(|ui| {
+        let mut heading = format!("📦 {} · {} assets", name, archive.entries.len());
+        if problems > 0 {
+            heading.push_str(&format!(" · {} can't be imported", problems));
+        }
+        ui.label(RichText::new(heading).strong());
+
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            if ui.small_button("✕").on_hover_text("Close archive").clicked() {
+                close = true;
+            }
+        });
+    });
+
+    if archive.entries.is_empty() {
+        ui.label(RichText::new("No SVGs or fonts in this archive").weak());
+        return close;
+    }
+
+    ui.horizontal(|ui| {
+        if ui.small_button("Select all").clicked() {
+            archive.select_all(true);
+        }
+        if ui.small_button("Select none").clicked() {
+            archive.select_all(false);
+        }
+        ui.checkbox(&mut archive.keep_structure, "Keep folder structure");
+        ui.label(RichText::new(format!("{} selected", archive.selected_count())).size(12.0).weak());
+    });
+
+    ui.add_space(5.0);
+    egui::ScrollArea::vertical()
+        .id_salt("import_archive_entries")
+        .max_height(260.0)
+        .auto_shrink([false, true])
+        .show(ui, |ui| {
+            ui.horizontal_wrapped(|ui| {
+                for (entry, selected) in archive.entries.iter().zip(archive.selected.iter_mut()) {
+                    ui.vertical(|ui| {
+                        ui.set_width(THUMBNAIL_SIZE + 48.0);
+
+                        match (&entry.problem, entry.kind) {
+                            (None, AssetKind::Svg) => {
+                                let uri = format!("bytes://{}/{}", archive.path.display(), entry.path.display());
+                                ui.add(
+                                    egui::Image::from_bytes(uri, egui::load::Bytes::Shared(entry.data.clone()))
+                                        .fit_to_exact_size(egui::vec2(THUMBNAIL_SIZE, THUMBNAIL_SIZE)),
+                                );
+                            }
+                            (None, AssetKind::Font) => {
+                                ui.label(RichText::new("🔤").size(THUMBNAIL_SIZE * 0.6));
+                            }
+                            (Some(_), _) => {
+                                ui.label(
+                                    RichText::new("⚠")
+                                        .size(THUMBNAIL_SIZE * 0.6)
+                                        .color(egui::Color32::from_rgb(255, 100, 100)),
+                                );
+                            }
+                        }
+
+                        let file_name = entry.path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                        let checkbox = ui.add_enabled(
+                            entry.problem.is_none(),
+                            egui::Checkbox::new(selected, RichText::new(file_name).size(11.0)),
+                        );
+
+                        match &entry.problem {
+                            Some(problem) => {
+                                checkbox.on_disabled_hover_text(format!("{}\n{}", entry.path.display(), problem));
+                                ui.label(
+                                    RichText::new(problem)
+                                        .size(9.0)
+                                        .color(egui::Color32::from_rgb(255, 100, 100)),
+                                );
+                            }
+                            None => {
+                                checkbox.on_hover_text(format!(
+                                    "{}\n→ {}",
+                                    entry.path.display(),
+                                    import::target_name(&entry.path, options)
+                                ));
+                            }
+                        }
+                    });
+                }
+            });
+        });
+
+    close
+}
+
+fn render_options(ui: &mut egui::Ui, options: &mut ImportOptions, destination: &mut String) {
+    egui::Grid::new("import_options_grid")
+        .num_columns(2)
+        .spacing([10.0, 8.0])
+        .show(ui, |ui| {
+            ui.label("Destination");
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(destination).desired_width(300.0));
+                if ui.button("Browse…").clicked() {
+                    if let Some(folder) = rfd::FileDialog::new().set_directory(&*destination).pick_folder() {
+                        *destination = folder.to_string_lossy().to_string();
+                    }
+                }
+            });
+            ui.end_row();
+
+            ui.label("Names");
+            ui.checkbox(&mut options.kebab_case, "kebab-case");
+            ui.end_row();
+
+            ui.label("Prefix");
+            ui.add(egui::TextEdit::singleline(&mut options.prefix).hint_text("ic-").desired_width(120.0));
+            ui.end_row();
+
+            ui.label("Optimize");
+            ui.horizontal(|ui| {
+                ui.selectable_value(&mut options.optimize, None, "Off");
+                for action in FormatAction::ALL {
+                    ui.selectable_value(&mut options.optimize, Some(action), action.label());
+                }
+            });
+            ui.end_row();
+
+            ui.label("Metadata");
+            ui.checkbox(&mut options.strip_metadata, "Strip comments, <metadata> and editor data");
+            ui.end_row();
+
+            ui.label("Security");
+            ui.checkbox(&mut options.sanitize, "Strip scripts, event handlers and external references");
+            ui.end_row();
+        });
+}
+
+fn run(app: &mut MyApp) {
+    let destination = PathBuf::from(app.import.destination.trim());
+    let (options, format_options) = (&app.import_options, &app.format_options);
+
+    let mut result = import::import_files(&app.import.sources, &destination, options, format_options);
+    if let (Ok(report), Some(archive)) = (&mut result, &app.import.archive) {
+        let entries: Vec<_> = archive
+            .entries
+            .iter()
+            .zip(&archive.selected)
+            .filter(|(_, selected)| **selected)
+            .map(|(entry, _)| entry)
+            .collect();
+        if !entries.is_empty() {
+            match import::import_entries(&archive.path, &entries, &destination, archive.keep_structure, options, format_options) {
+                Ok(entries_report) => report.merge(entries_report),
+                Err(e) => result = Err(e),
+            }
+        }
+    }
+
+    match result {
+        Ok(report) => {
+            app.set_error_message(format!("Import: {}", report.summary()));
+            keep_rejected(app, &report);
+            app.import.report = Some(report);
+            app.save_config();
+            app.rescan_current_directory();
+        }
+        Err(e) => app.set_error_message(format!("Failed to import: {}", e)),
+    }
+}
+
+// Rejected files stay listed and selected so they can be fixed and retried
+fn keep_rejected(app: &mut MyApp, report: &ImportReport) {
+    let rejected = |source: &PathBuf| report.rejected.iter().any(|(path, _)| path == source);
+
+    app.import.sources.retain(|source| rejected(source));
+    if let Some(archive) = &mut app.import.archive {
+        for (entry, selected) in archive.entries.iter().zip(archive.selected.iter_mut()) {
+            *selected = *selected && rejected(&archive.path.join(&entry.path));
+        }
+    }
+}
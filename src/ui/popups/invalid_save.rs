@@ -0,0 +1,77 @@
+use crate::models::gui::MyApp;
+use crate::utils::svg_validator::{self, Severity};
+
+const MAX_LISTED_ERRORS: usize = 5;
+
+pub fn render(app: &mut MyApp, ctx: &egui::Context) {
+    let mut should_close = false;
+    let mut should_save = false;
+
+    let content = app.active_document().map(|doc| doc.content.as_str()).unwrap_or_default();
+    let errors: Vec<_> = svg_validator::validate(content)
+        .into_iter()
+        .filter(|d| d.severity == Severity::Error)
+        .collect();
+
+    egui::Window::new("Invalid SVG")
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.vertical_centered(|ui| {
+                ui.add_space(10.0);
+
+                ui.label(
+                    egui::RichText::new("⚠ This document has errors")
+                        .size(20.0)
+                        .color(egui::Color32::from_rgb(255, 200, 0))
+                );
+
+                ui.add_space(10.0);
+
+                ui.label("Saving it may leave a file that no longer renders:");
+            });
+
+            ui.add_space(5.0);
+
+            for error in errors.iter().take(MAX_LISTED_ERRORS) {
+                ui.label(
+                    egui::RichText::new(error.to_string())
+                        .monospace()
+                        .size(12.0)
+                        .color(egui::Color32::from_rgb(255, 100, 100))
+                );
+            }
+            if errors.len() > MAX_LISTED_ERRORS {
+                ui.label(format!("…and {} more", errors.len() - MAX_LISTED_ERRORS));
+            }
+
+            ui.add_space(15.0);
+
+            ui.horizontal(|ui| {
+                if ui.button(egui::RichText::new("Cancel").size(14.0)).clicked()
+                    || ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                    should_close = true;
+                }
+
+                ui.add_space(10.0);
+
+                if ui.button(
+                    egui::RichText::new("Save anyway")
+                        .size(14.0)
+                        .color(egui::Color32::from_rgb(255, 100, 100))
+                ).clicked() {
+                    should_save = true;
+                }
+            });
+        });
+
+    if should_save {
+        app.write_svg();
+        should_close = true;
+    }
+
+    if should_close {
+        app.confirm_invalid_save = false;
+    }
+}
@@ -0,0 +1,90 @@
+use crate::models::gui::MyApp;
+use crate::utils::bundle::parse_sizes;
+use egui::RichText;
+use std::path::PathBuf;
+
+pub fn render(app: &mut MyApp, ctx: &egui::Context) {
+    let Some(draft) = app.lint_rules_edit.as_mut() else {
+        return;
+    };
+    let mut open = true;
+    let mut should_save = false;
+
+    egui::Window::new("Lint rules")
+        .resizable(false)
+        .collapsible(false)
+        .open(&mut open)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            let rules = &mut draft.rules;
+
+            ui.checkbox(&mut rules.require_view_box, "Require a viewBox");
+            ui.checkbox(&mut rules.no_fixed_size, "No fixed width / height on the root");
+            ui.checkbox(&mut rules.square, "Square aspect ratio");
+            ui.checkbox(&mut rules.no_raster_images, "No embedded or linked raster images");
+            ui.checkbox(&mut rules.no_scripts, "No scripts, event handlers or javascript: links");
+            ui.checkbox(&mut rules.no_external_references, "No external references");
+            ui.add_space(10.0);
+
+            egui::Grid::new("lint_rules_grid")
+                .num_columns(2)
+                .spacing([10.0, 8.0])
+                .show(ui, |ui| {
+                    ui.label("Grid sizes");
+                    ui.add(egui::TextEdit::singleline(&mut draft.grid_sizes_input).hint_text("Any size, e.g. 16, 20, 24"));
+                    ui.end_row();
+
+                    ui.label("Max paths");
+                    ui.add(egui::DragValue::new(&mut rules.max_paths).range(0..=1000))
+                        .on_hover_text("0 allows any number");
+                    ui.end_row();
+
+                    ui.label("Palette");
+                    ui.add(egui::TextEdit::singleline(&mut draft.palette_input).hint_text("Any color, e.g. #000, #1e88e5"));
+                    ui.end_row();
+
+                    ui.label("File names");
+                    ui.add(egui::TextEdit::singleline(&mut rules.name_pattern).hint_text("Any name, or a regex"));
+                    ui.end_row();
+                });
+
+            ui.add_space(5.0);
+            ui.label(
+                RichText::new("Saved in the vault's .asset_manager folder, so the CLI lints with the same rules.")
+                    .size(12.0)
+                    .color(egui::Color32::from_rgb(150, 150, 150)),
+            );
+
+            ui.add_space(10.0);
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.button("Save and lint").clicked() {
+                    should_save = true;
+                }
+            });
+        });
+
+    if should_save {
+        let Some(draft) = app.lint_rules_edit.take() else {
+            return;
+        };
+        let mut rules = draft.rules;
+        rules.grid_sizes = parse_sizes(&draft.grid_sizes_input);
+        rules.palette = draft
+            .palette_input
+            .split(',')
+            .map(|color| color.trim().to_string())
+            .filter(|color| !color.is_empty())
+            .collect();
+        rules.name_pattern = rules.name_pattern.trim().to_string();
+
+        match rules.save(&PathBuf::from(&app.vault_path)) {
+            Ok(_) => {
+                app.lint_rules = rules;
+                app.run_lint();
+            }
+            Err(e) => app.set_error_message(format!("Failed to save lint rules: {}", e)),
+        }
+    } else if !open {
+        app.lint_rules_edit = None;
+    }
+}
@@ -0,0 +1,33 @@
+use crate::models::gui::MyApp;
+use crate::ui::sidebar_right::metadata::render_fields;
+
+pub fn render(app: &mut MyApp, ctx: &egui::Context) {
+    let Some(path) = app.metadata_edit_path.clone() else {
+        return;
+    };
+    let mut open = true;
+
+    let title = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    egui::Window::new(format!("Metadata: {}", title))
+        .id(egui::Id::new("metadata_editor"))
+        .resizable(false)
+        .collapsible(false)
+        .open(&mut open)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            let suggestions = app.metadata.all_tags();
+            let metadata = app.metadata.entry(&path);
+            if render_fields(ui, metadata, &mut app.metadata_tag_input, &suggestions) {
+                app.persist_metadata(&path);
+            }
+        });
+
+    if !open {
+        app.save_metadata();
+        app.metadata_edit_path = None;
+    }
+}
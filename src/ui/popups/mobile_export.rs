@@ -0,0 +1,122 @@
+use crate::models::gui::MyApp;
+use crate::utils::mobile_export::{self, AppleFormat, MobileTarget};
+use egui::RichText;
+
+pub fn render(app: &mut MyApp, ctx: &egui::Context) {
+    let Some(export) = app.mobile_export.as_mut() else {
+        return;
+    };
+    let options = &mut app.mobile_export_options;
+    let mut open = true;
+    let mut should_export = false;
+
+    egui::Window::new("Export for Android / iOS")
+        .resizable(false)
+        .collapsible(false)
+        .open(&mut open)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label(format!("Convert {} assets for mobile apps", export.assets.len()));
+            ui.add_space(10.0);
+
+            egui::Grid::new("mobile_export_grid")
+                .num_columns(2)
+                .spacing([10.0, 8.0])
+                .show(ui, |ui| {
+                    ui.label("Target");
+                    ui.horizontal(|ui| {
+                        for target in [MobileTarget::Android, MobileTarget::Ios] {
+                            ui.selectable_value(&mut options.target, target, target.label());
+                        }
+                    });
+                    ui.end_row();
+
+                    if options.target == MobileTarget::Ios {
+                        ui.label("Format");
+                        ui.horizontal(|ui| {
+                            for format in [AppleFormat::Pdf, AppleFormat::Svg] {
+                                ui.selectable_value(&mut options.apple_format, format, format.label());
+                            }
+                        });
+                        ui.end_row();
+
+                        ui.label("");
+                        ui.checkbox(&mut options.template_rendering, "Render as template image");
+                        ui.end_row();
+                    }
+
+                    ui.label("Output");
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            egui::TextEdit::singleline(&mut options.output_dir)
+                                .hint_text(match options.target {
+                                    MobileTarget::Android => "res/drawable folder",
+                                    MobileTarget::Ios => "An .xcassets catalog or a folder",
+                                })
+                                .desired_width(240.0),
+                        );
+                        if ui.button("Browse…").clicked() {
+                            if let Some(folder) = rfd::FileDialog::new().pick_folder() {
+                                options.output_dir = folder.display().to_string();
+                            }
+                        }
+                    });
+                    ui.end_row();
+                });
+
+            ui.add_space(5.0);
+            ui.label(
+                RichText::new(match options.target {
+                    MobileTarget::Android => "One ic_<name>.xml per asset. Transforms are baked into the paths.",
+                    MobileTarget::Ios => "One <name>.imageset per asset, Icons.xcassets is created unless you pick a catalog.",
+                })
+                .size(12.0)
+                .color(egui::Color32::from_rgb(150, 150, 150)),
+            );
+
+            if let Some(report) = &export.report {
+                ui.add_space(10.0);
+                ui.separator();
+                ui.label(RichText::new(report.summary()).strong());
+                if report.issues.is_empty() && report.errors.is_empty() {
+                    ui.label(RichText::new("✅ Everything carried over").color(egui::Color32::from_rgb(100, 200, 100)));
+                }
+
+                egui::ScrollArea::vertical().max_height(240.0).show(ui, |ui| {
+                    for error in &report.errors {
+                        ui.label(RichText::new(error).color(egui::Color32::from_rgb(255, 100, 100)));
+                    }
+                    for (name, issues) in &report.issues {
+                        ui.label(RichText::new(name).strong());
+                        for issue in issues {
+                            ui.label(RichText::new(format!("  ⚠ {}", issue)).color(egui::Color32::from_rgb(255, 200, 0)));
+                        }
+                    }
+                });
+            }
+
+            ui.add_space(10.0);
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                let ready = !options.output_dir.trim().is_empty();
+                if ui.add_enabled(ready, egui::Button::new("Export")).clicked() {
+                    should_export = true;
+                }
+            });
+        });
+
+    if should_export {
+        let assets = export.assets.clone();
+        match mobile_export::export(&assets, &app.mobile_export_options) {
+            Ok(report) => {
+                app.set_error_message(format!("✅ {} to {}", report.summary(), report.output.display()));
+                if let Some(export) = app.mobile_export.as_mut() {
+                    export.report = Some(report);
+                }
+                app.save_config();
+            }
+            Err(e) => app.set_error_message(format!("Failed to export: {}", e)),
+        }
+    } else if !open {
+        app.mobile_export = None;
+    }
+}
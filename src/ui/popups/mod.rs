@@ -1,3 +1,20 @@
 pub mod rename_file;
 pub mod delete_file;
-pub mod gallery_options;
\ No newline at end of file
+pub mod gallery_options;
+pub mod theme_variants;
+pub mod contrast_report;
+pub mod invalid_save;
+pub mod unsaved_changes;
+pub mod format_folder;
+pub mod git_commit;
+pub mod metadata_editor;
+pub mod collection_editor;
+pub mod duplicates;
+pub mod import;
+pub mod export_bundle;
+pub mod component_library;
+pub mod mobile_export;
+pub mod print_export;
+pub mod catalog;
+pub mod lint_rules;
+pub mod sanitize;
@@ -0,0 +1,135 @@
+use crate::models::gui::MyApp;
+use crate::utils::import::to_kebab_case;
+use crate::utils::print_export::{self, PageSize, PdfLayout, PrintFormat};
+use egui::RichText;
+
+pub fn render(app: &mut MyApp, ctx: &egui::Context) {
+    let Some(export) = app.print_export.as_ref() else {
+        return;
+    };
+    let options = &mut app.print_options;
+    let mut open = true;
+    let mut should_export = false;
+
+    egui::Window::new("Export PDF / EPS")
+        .resizable(false)
+        .collapsible(false)
+        .open(&mut open)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label(format!("Export {} assets as vectors", export.assets.len()));
+            ui.add_space(10.0);
+
+            egui::Grid::new("print_export_grid")
+                .num_columns(2)
+                .spacing([10.0, 8.0])
+                .show(ui, |ui| {
+                    ui.label("Format");
+                    ui.horizontal(|ui| {
+                        for format in [PrintFormat::Pdf, PrintFormat::Eps] {
+                            ui.selectable_value(&mut options.format, format, format.label());
+                        }
+                    });
+                    ui.end_row();
+
+                    if options.format == PrintFormat::Pdf {
+                        ui.label("Layout");
+                        ui.horizontal(|ui| {
+                            for layout in PdfLayout::ALL {
+                                ui.selectable_value(&mut options.layout, layout, layout.label());
+                            }
+                        });
+                        ui.end_row();
+                    }
+
+                    if options.single_file() {
+                        ui.label("Page size");
+                        ui.horizontal(|ui| {
+                            for size in PageSize::ALL {
+                                // Contact sheets need a real page
+                                if size == PageSize::Fit && options.layout == PdfLayout::ContactSheet {
+                                    continue;
+                                }
+                                ui.selectable_value(&mut options.page_size, size, size.label());
+                            }
+                        });
+                        ui.end_row();
+
+                        if options.layout == PdfLayout::ContactSheet {
+                            ui.label("Columns");
+                            ui.add(egui::Slider::new(&mut options.columns, 2..=12));
+                            ui.end_row();
+                        }
+
+                        if options.page_size != PageSize::Fit || options.layout == PdfLayout::ContactSheet {
+                            ui.label("");
+                            ui.checkbox(&mut options.labels, "Show file names");
+                            ui.end_row();
+                        }
+                    }
+                });
+
+            ui.add_space(5.0);
+            ui.label(
+                RichText::new(if options.single_file() {
+                    "Everything goes into one PDF. Paths stay vectors, filters, masks and text are left out."
+                } else {
+                    "One file per asset in the folder you pick. Paths stay vectors, filters, masks and text are left out."
+                })
+                .size(12.0)
+                .color(egui::Color32::from_rgb(150, 150, 150)),
+            );
+
+            ui.add_space(10.0);
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.button("Export…").clicked() {
+                    should_export = true;
+                }
+            });
+        });
+
+    if should_export {
+        export_files(app);
+    } else if !open {
+        app.print_export = None;
+    }
+}
+
+fn export_files(app: &mut MyApp) {
+    let Some(export) = &app.print_export else {
+        return;
+    };
+    let options = &app.print_options;
+    let name = if export.name.trim().is_empty() { "assets".to_string() } else { export.name.clone() };
+
+    let result = if options.single_file() {
+        let Some(output) = rfd::FileDialog::new()
+            .add_filter("PDF", &["pdf"])
+            .set_file_name(format!("{}.pdf", to_kebab_case(&name)))
+            .save_file()
+        else {
+            return;
+        };
+        print_export::export_document(&export.assets, &output, options, &name)
+    } else {
+        let Some(folder) = rfd::FileDialog::new().pick_folder() else {
+            return;
+        };
+        print_export::export_separate(&export.assets, &folder, options)
+    };
+
+    match result {
+        Ok(report) => {
+            let mut details: Vec<String> = report.errors.clone();
+            details.extend(report.issues.iter().map(|(name, issues)| format!("{}: {}", name, issues.join(", "))));
+            if details.is_empty() {
+                app.set_error_message(format!("✅ Exported {}", report.summary()));
+            } else {
+                app.set_error_message(format!("Exported {} ({})", report.summary(), details.join("; ")));
+            }
+            app.save_config();
+            app.print_export = None;
+        }
+        Err(e) => app.set_error_message(format!("Failed to export: {}", e)),
+    }
+}
@@ -102,12 +102,8 @@ pub fn render(app: &mut MyApp, ctx: &egui::Context) {
             }
         }
 
-        // Also update selected_svg if it was the renamed file
-        if let Some(selected) = &app.selected_svg {
-            if *selected == old_path {
-                app.selected_svg = Some(new_path);
-            }
-        }
+        // Also update open documents, selected_svg and history for the renamed file
+        app.on_asset_renamed(&old_path, &new_path);
     }
 
     // Check if window was closed via X button OR via should_close
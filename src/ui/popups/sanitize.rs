@@ -0,0 +1,88 @@
+use crate::models::gui::MyApp;
+use crate::utils::history::asset_key;
+use crate::utils::sanitize;
+use egui::RichText;
+use std::path::PathBuf;
+
+// Lists the vault files with active content and rewrites them on confirmation
+pub fn render(app: &mut MyApp, ctx: &egui::Context) {
+    let Some(found) = app.sanitize_scan.as_ref() else {
+        return;
+    };
+    let vault = PathBuf::from(&app.vault_path);
+    let mut open = true;
+    let mut should_run = false;
+
+    egui::Window::new("Sanitize Vault")
+        .resizable(false)
+        .collapsible(false)
+        .open(&mut open)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label(format!("{} SVGs contain scripts, event handlers or external references:", found.len()));
+            ui.add_space(10.0);
+
+            egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                for (path, findings) in found {
+                    egui::CollapsingHeader::new(RichText::new(asset_key(&vault, path)).strong())
+                        .id_salt(("sanitize", path))
+                        .default_open(found.len() <= 10)
+                        .show(ui, |ui| {
+                            for finding in findings {
+                                ui.label(
+                                    RichText::new(format!("⚠ {}", finding))
+                                        .size(12.0)
+                                        .color(egui::Color32::from_rgb(255, 200, 0)),
+                                );
+                            }
+                        });
+                }
+            });
+
+            ui.add_space(5.0);
+            ui.label(
+                RichText::new("Everything else in the files is kept as it is. Changes are recorded in the history.")
+                    .size(12.0)
+                    .color(egui::Color32::from_rgb(150, 150, 150)),
+            );
+
+            if app.has_dirty_documents() {
+                ui.add_space(5.0);
+                ui.label(
+                    RichText::new("⚠ Open documents with unsaved changes are not reloaded")
+                        .size(12.0)
+                        .color(egui::Color32::from_rgb(255, 200, 0)),
+                );
+            }
+
+            ui.add_space(10.0);
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.button(format!("Sanitize {} files", found.len())).clicked() {
+                    should_run = true;
+                }
+            });
+        });
+
+    if should_run {
+        let Some(found) = app.sanitize_scan.take() else {
+            return;
+        };
+        let paths: Vec<PathBuf> = found.into_iter().map(|(path, _)| path).collect();
+        let report = sanitize::sanitize_files(&paths);
+
+        for (path, before, _) in &report.cleaned {
+            if let Ok(after) = std::fs::read_to_string(path) {
+                app.record_history(path, Some(before), &after, "Sanitize");
+            }
+        }
+        if report.errors.is_empty() {
+            app.set_error_message(format!("✅ Sanitized: {}", report.summary()));
+        } else {
+            app.set_error_message(format!("Sanitized: {} ({})", report.summary(), report.errors.join("; ")));
+        }
+        app.reload_clean_documents();
+        app.rescan_current_directory();
+    } else if !open {
+        app.sanitize_scan = None;
+    }
+}
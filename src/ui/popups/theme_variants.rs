@@ -0,0 +1,143 @@
+use crate::models::gui::MyApp;
+use crate::utils::svg_parser;
+use crate::utils::theme_variants::{self, ColorMapping};
+use egui::RichText;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+pub fn render(app: &mut MyApp, ctx: &egui::Context) {
+    let mut open = true;
+    let mut should_generate = false;
+    let mut remove_row: Option<usize> = None;
+
+    egui::Window::new("Theme Variants")
+        .resizable(false)
+        .collapsible(false)
+        .open(&mut open)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label("Map each light color to its dark counterpart:");
+            ui.add_space(10.0);
+
+            egui::Grid::new("theme_mapping_grid")
+                .num_columns(4)
+                .spacing([10.0, 6.0])
+                .show(ui, |ui| {
+                    ui.label(RichText::new("Light").strong());
+                    ui.label("");
+                    ui.label(RichText::new("Dark").strong());
+                    ui.end_row();
+
+                    for (idx, mapping) in app.theme_color_map.iter_mut().enumerate() {
+                        render_color_field(ui, &mut mapping.light);
+                        ui.label("→");
+                        render_color_field(ui, &mut mapping.dark);
+
+                        if ui.small_button("✖").on_hover_text("Remove").clicked() {
+                            remove_row = Some(idx);
+                        }
+                        ui.end_row();
+                    }
+                });
+
+            ui.add_space(5.0);
+
+            ui.horizontal(|ui| {
+                if ui.button("+ Add color").clicked() {
+                    app.theme_color_map.push(ColorMapping {
+                        light: "#FFFFFF".to_string(),
+                        dark: "#000000".to_string(),
+                    });
+                }
+
+                // Seed the table with the colors of the selected icon
+                if let Some(svg_path) = app.selected_svg.clone() {
+                    if ui.button("+ Colors from selection").clicked() {
+                        if let Ok(info) = svg_parser::parse_svg_info(&svg_path) {
+                            for color in info.colors_used {
+                                let hex = format!("#{:02X}{:02X}{:02X}", color.red, color.green, color.blue);
+                                if !app.theme_color_map.iter().any(|m| m.light.eq_ignore_ascii_case(&hex)) {
+                                    app.theme_color_map.push(ColorMapping {
+                                        light: hex.clone(),
+                                        dark: hex,
+                                    });
+                                }
+                            }
+                        }
+                    }
+                }
+            });
+
+            ui.add_space(10.0);
+            ui.separator();
+            ui.add_space(5.0);
+
+            ui.label(
+                RichText::new(format!(
+                    "Variants are written to {}/{}/",
+                    folder_name(&app.current_path),
+                    theme_variants::VARIANT_DIR
+                ))
+                .size(12.0)
+                .color(egui::Color32::from_rgb(150, 150, 150)),
+            );
+
+            ui.add_space(5.0);
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.button("Generate").clicked() {
+                    should_generate = true;
+                }
+            });
+        });
+
+    if let Some(idx) = remove_row {
+        app.theme_color_map.remove(idx);
+    }
+
+    if should_generate {
+        let source_root = PathBuf::from(&app.current_path);
+        match theme_variants::generate_variants(&source_root, &app.theme_color_map) {
+            Ok(report) => {
+                if report.errors.is_empty() {
+                    app.set_error_message(format!("✅ Variants: {}", report.summary()));
+                } else {
+                    app.set_error_message(format!("Variants: {} ({})", report.summary(), report.errors.join("; ")));
+                }
+                app.save_config();
+                app.rescan_current_directory();
+            }
+            Err(e) => app.set_error_message(format!("Failed to generate variants: {}", e)),
+        }
+    }
+
+    if !open {
+        app.theme_variants_open = false;
+        app.save_config();
+    }
+}
+
+pub(crate) fn render_color_field(ui: &mut egui::Ui, hex: &mut String) {
+    let mut rgb = svgtypes::Color::from_str(hex.trim())
+        .map(|c| [c.red, c.green, c.blue])
+        .unwrap_or([0, 0, 0]);
+
+    ui.horizontal(|ui| {
+        if ui.color_edit_button_srgb(&mut rgb).changed() {
+            *hex = format!("#{:02X}{:02X}{:02X}", rgb[0], rgb[1], rgb[2]);
+        }
+
+        ui.add(
+            egui::TextEdit::singleline(hex)
+                .font(egui::TextStyle::Monospace)
+                .desired_width(70.0),
+        );
+    });
+}
+
+fn folder_name(path: &str) -> String {
+    std::path::Path::new(path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string())
+}
@@ -0,0 +1,146 @@
+use crate::models::editor::UnsavedPrompt;
+use crate::models::gui::MyApp;
+use crate::utils::svg_validator;
+
+enum Choice {
+    Save,
+    Discard,
+    KeepOpen,
+    Cancel,
+}
+
+pub fn render(app: &mut MyApp, ctx: &egui::Context) {
+    let Some(prompt) = app.unsaved_prompt.clone() else {
+        return;
+    };
+
+    // Documents the prompt is about
+    let affected: Vec<usize> = match &prompt {
+        UnsavedPrompt::Navigate(_) => app.active_document.into_iter().collect(),
+        UnsavedPrompt::Close(path) => app
+            .documents
+            .iter()
+            .position(|doc| &doc.path == path)
+            .into_iter()
+            .collect(),
+        UnsavedPrompt::Exit => (0..app.documents.len())
+            .filter(|&idx| app.documents[idx].is_dirty())
+            .collect(),
+    };
+
+    let names: Vec<String> = affected.iter().map(|&idx| app.documents[idx].name()).collect();
+    let has_invalid = affected
+        .iter()
+        .any(|&idx| svg_validator::has_errors(&svg_validator::validate(&app.documents[idx].content)));
+
+    let mut choice: Option<Choice> = None;
+
+    egui::Window::new("Unsaved Changes")
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.vertical_centered(|ui| {
+                ui.add_space(10.0);
+
+                ui.label(
+                    egui::RichText::new("⚠ Unsaved changes")
+                        .size(20.0)
+                        .color(egui::Color32::from_rgb(255, 200, 0))
+                );
+
+                ui.add_space(10.0);
+
+                ui.label("Do you want to save your changes to:");
+
+                ui.add_space(5.0);
+
+                for name in &names {
+                    ui.label(
+                        egui::RichText::new(name)
+                            .strong()
+                            .color(egui::Color32::WHITE)
+                    );
+                }
+
+                if has_invalid {
+                    ui.add_space(5.0);
+                    ui.label(
+                        egui::RichText::new("Some of these documents are not valid SVG")
+                            .size(12.0)
+                            .color(egui::Color32::from_rgb(255, 100, 100))
+                    );
+                }
+
+                ui.add_space(15.0);
+            });
+
+            ui.horizontal(|ui| {
+                if ui.button(egui::RichText::new("Cancel").size(14.0)).clicked()
+                    || ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                    choice = Some(Choice::Cancel);
+                }
+
+                ui.add_space(10.0);
+
+                if matches!(prompt, UnsavedPrompt::Navigate(_))
+                    && ui.button(egui::RichText::new("Keep in tab").size(14.0)).clicked()
+                {
+                    choice = Some(Choice::KeepOpen);
+                }
+
+                if ui.button(
+                    egui::RichText::new("Discard")
+                        .size(14.0)
+                        .color(egui::Color32::from_rgb(255, 100, 100))
+                ).clicked() {
+                    choice = Some(Choice::Discard);
+                }
+
+                let save_label = if prompt == UnsavedPrompt::Exit { "Save all" } else { "Save" };
+                if ui.button(egui::RichText::new(save_label).size(14.0)).clicked() {
+                    choice = Some(Choice::Save);
+                }
+            });
+        });
+
+    let Some(choice) = choice else {
+        return;
+    };
+    app.unsaved_prompt = None;
+
+    if let Choice::Cancel = choice {
+        return;
+    }
+
+    // Resolve the documents first, then carry on with what was interrupted
+    let mut resolved = true;
+    for &idx in &affected {
+        match choice {
+            Choice::Save => resolved &= app.save_document(idx),
+            Choice::Discard => {
+                app.documents[idx].revert();
+                app.documents[idx].pinned = false;
+            }
+            Choice::KeepOpen => app.documents[idx].pinned = true,
+            Choice::Cancel => {}
+        }
+    }
+
+    if !resolved {
+        return;
+    }
+
+    match prompt {
+        UnsavedPrompt::Navigate(path) => app.open_document(&path),
+        UnsavedPrompt::Close(_) => {
+            if let Some(&idx) = affected.first() {
+                app.close_document(idx);
+            }
+        }
+        UnsavedPrompt::Exit => {
+            app.exit_confirmed = true;
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use crate::models::gui::MyApp;
+use crate::models::lint::LintRulesDraft;
+use crate::utils::history::asset_key;
+use crate::utils::lint;
+use crate::utils::svg_validator::Severity;
+use egui::{RichText, TopBottomPanel};
+use std::path::{Path, PathBuf};
+
+pub fn severity_color(severity: Severity) -> egui::Color32 {
+    match severity {
+        Severity::Error => egui::Color32::from_rgb(255, 100, 100),
+        Severity::Warning => egui::Color32::from_rgb(255, 200, 0),
+    }
+}
+
+// Lint results of the whole vault, one collapsible group per file
+pub fn render(app: &mut MyApp, ctx: &egui::Context) {
+    let Some(results) = app.lint_results.as_ref() else {
+        return;
+    };
+    let vault = PathBuf::from(&app.vault_path);
+
+    let mut files: Vec<(String, &PathBuf, &Vec<lint::Problem>)> =
+        results.iter().map(|(path, problems)| (asset_key(&vault, path), path, problems)).collect();
+    files.sort_by_key(|(key, _, _)| key.to_lowercase());
+    let errors: usize = files.iter().map(|(_, _, problems)| lint::error_count(problems)).sum();
+    let warnings: usize = files.iter().map(|(_, _, problems)| problems.len()).sum::<usize>() - errors;
+
+    let mut open_file: Option<PathBuf> = None;
+    let mut rerun = false;
+    let mut edit_rules = false;
+    let mut close = false;
+
+    TopBottomPanel::bottom("problems_panel")
+        .resizable(true)
+        .default_height(180.0)
+        .min_height(80.0)
+        .frame(egui::Frame::default().inner_margin(egui::Margin::same(8)).fill(egui::Color32::from_rgb(30, 29, 25)))
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("Problems").strong());
+                ui.label(
+                    RichText::new(format!("{} errors, {} warnings in {} files", errors, warnings, files.len()))
+                        .color(egui::Color32::from_rgb(150, 150, 150)),
+                );
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button("✕").on_hover_text("Close").clicked() {
+                        close = true;
+                    }
+                    if ui.button("Rules…").clicked() {
+                        edit_rules = true;
+                    }
+                    if ui.button("⟳ Re-run").clicked() {
+                        rerun = true;
+                    }
+                });
+            });
+            ui.separator();
+
+            if files.is_empty() {
+                ui.label(RichText::new("✅ No problems").color(egui::Color32::from_rgb(100, 200, 100)));
+                return;
+            }
+
+            egui::ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+                for (key, path, problems) in &files {
+                    let header = format!("{}  ({})", key, problems.len());
+                    let response = egui::CollapsingHeader::new(RichText::new(header).strong())
+                        .id_salt(("problems", path))
+                        .default_open(files.len() <= 20)
+                        .show(ui, |ui| {
+                            for problem in problems.iter() {
+                                ui.horizontal(|ui| {
+                                    let icon = if problem.severity == Severity::Error { "⛔" } else { "⚠" };
+                                    ui.label(RichText::new(icon).color(severity_color(problem.severity)));
+                                    ui.label(
+                                        RichText::new(format!("Ln {}, Col {}", problem.line, problem.column))
+                                            .monospace()
+                                            .color(egui::Color32::from_rgb(150, 150, 150)),
+                                    );
+                                    if ui.link(&problem.message).clicked() {
+                                        open_file = Some(path.to_path_buf());
+                                    }
+                                    ui.label(RichText::new(problem.rule.id()).weak());
+                                });
+                            }
+                        });
+                    if response.header_response.double_clicked() {
+                        open_file = Some(path.to_path_buf());
+                    }
+                }
+            });
+        });
+
+    if let Some(path) = open_file {
+        open(app, &path);
+    }
+    if rerun {
+        app.run_lint();
+    }
+    if edit_rules {
+        app.lint_rules_edit = Some(LintRulesDraft::new(app.lint_rules.clone()));
+    }
+    if close {
+        app.problems_open = false;
+    }
+}
+
+fn open(app: &mut MyApp, path: &Path) {
+    app.load_svg(path);
+    app.show_sidebar_right = true;
+}
@@ -1,5 +1,7 @@
 use crate::models::gui::View;
 use crate::models::gui::MyApp;
+use crate::models::collection::CollectionDraft;
+use crate::utils::collections::{Collection, CollectionKind, SmartQuery};
 use egui::{RichText, SidePanel};
 
 pub fn render(app: &mut MyApp, ctx: &egui::Context) {
@@ -54,6 +56,41 @@ pub fn render(app: &mut MyApp, ctx: &egui::Context) {
                     app.current_view = View::Fonts;
                     app.refresh_directory();
                 }
+                ui.add_space(8.0);
+
+                // Favorites View
+                if ui
+                    .add_sized(
+                        [32.0, 32.0],
+                        egui::Button::new(RichText::new("★").size(20.0))
+                            .selected(app.current_view == View::Favorites),
+                    )
+                    .on_hover_text("Favorites")
+                    .clicked()
+                {
+                    app.open_list_view(View::Favorites);
+                }
+
+                ui.add_space(8.0);
+
+                // Recent View
+                if ui
+                    .add_sized(
+                        [32.0, 32.0],
+                        egui::Button::new(RichText::new("🕘").size(20.0))
+                            .selected(app.current_view == View::Recent),
+                    )
+                    .on_hover_text("Recent")
+                    .clicked()
+                {
+                    app.open_list_view(View::Recent);
+                }
+
+                ui.add_space(8.0);
+                ui.separator();
+
+                render_collections(app, ui);
+
                 //  @@TODO: pull SVG and Fonts from the web
                 // ui.add_space(8.0);
                 //
@@ -109,3 +146,70 @@ pub fn render(app: &mut MyApp, ctx: &egui::Context) {
             });
         });
 }
+
+// One entry per collection, plus a button to create new ones
+fn render_collections(app: &mut MyApp, ui: &mut egui::Ui) {
+    let mut open: Option<usize> = None;
+    let mut edit: Option<usize> = None;
+    let mut delete: Option<usize> = None;
+
+    for (index, collection) in app.collections.collections.iter().enumerate() {
+        ui.add_space(8.0);
+
+        let active = app.current_view == View::Collection && app.active_collection == Some(index);
+        let response = ui
+            .add_sized(
+                [32.0, 32.0],
+                egui::Button::new(RichText::new(collection.initials()).size(13.0).strong())
+                    .selected(active),
+            )
+            .on_hover_text(format!("{} {}", collection.icon(), collection.name));
+
+        if response.clicked() {
+            open = Some(index);
+        }
+
+        response.context_menu(|ui| {
+            if ui.button("Edit…").clicked() {
+                edit = Some(index);
+                ui.close();
+            }
+            if ui.button("Delete").clicked() {
+                delete = Some(index);
+                ui.close();
+            }
+        });
+    }
+
+    ui.add_space(8.0);
+
+    ui.menu_button(RichText::new("+").size(18.0), |ui| {
+        if ui.button("📌 New collection…").clicked() {
+            app.collection_edit = Some(CollectionDraft::new(Collection::manual("")));
+            ui.close();
+        }
+        if ui.button("🔎 New smart collection…").clicked() {
+            app.collection_edit = Some(CollectionDraft::new(Collection {
+                name: String::new(),
+                kind: CollectionKind::Smart { query: SmartQuery::default() },
+            }));
+            ui.close();
+        }
+    })
+    .response
+    .on_hover_text("New collection");
+
+    if let Some(index) = open {
+        app.open_collection(index);
+    }
+
+    if let Some(index) = edit {
+        if let Some(collection) = app.collections.collections.get(index).cloned() {
+            app.collection_edit = Some(CollectionDraft::edit(index, collection));
+        }
+    }
+
+    if let Some(index) = delete {
+        app.delete_collection(index);
+    }
+}
@@ -1,18 +1,23 @@
 use crate::models::gui::MyApp;
 use egui::{RichText};
-use egui_code_editor::{CodeEditor, ColorTheme, Syntax};
+use crate::ui::views::code_editor::svg_syntax;
+use egui_code_editor::{CodeEditor, ColorTheme};
 
 const BUTTON_HEIGHT: f32 = 35.0;
 
 pub fn render(app: &mut MyApp, ui: &mut egui::Ui) {
-    render_header(ui);
+    render_header(app, ui);
     render_code_editor(app, ui);
     render_buttons(app, ui);
 }
 
-fn render_header(ui: &mut egui::Ui) {
+fn render_header(app: &MyApp, ui: &mut egui::Ui) {
     ui.horizontal(|ui| {
         ui.label(RichText::new("Code").size(16.0).strong());
+
+        if app.active_document().map(|doc| doc.is_dirty()).unwrap_or(false) {
+            ui.label(RichText::new("● unsaved").size(11.0).color(egui::Color32::from_rgb(255, 200, 0)));
+        }
     });
     ui.add_space(5.0);
 }
@@ -20,20 +25,31 @@ fn render_header(ui: &mut egui::Ui) {
 fn render_code_editor(app: &mut MyApp, ui: &mut egui::Ui) {
     // Calculate remaining space for editor
     let available_height = ui.available_height() - BUTTON_HEIGHT - 10.0;
+    let mut edited = false;
+
+    let Some(doc) = app.active_document_mut() else {
+        return;
+    };
 
     egui::ScrollArea::both()
         .auto_shrink([false, false])
         .max_height(available_height)
         .show(ui, |ui| {
-            CodeEditor::default()
+            let output = CodeEditor::default()
                 .id_source("sidebar_code_editor")
                 .with_rows(15)
                 .with_fontsize(11.0)
                 .with_theme(ColorTheme::GRUVBOX)
-                .with_syntax(Syntax::python()) // Use xml() for better SVG syntax
+                .with_syntax(svg_syntax())
                 .with_numlines(true)
-                .show(ui, &mut app.svg_code);
+                .show(ui, &mut doc.content);
+            edited = output.response.changed();
         });
+
+    // Edited documents keep their tab
+    if edited {
+        app.pin_active_document();
+    }
 }
 
 fn render_buttons(app: &mut MyApp, ui: &mut egui::Ui) {
@@ -43,6 +59,12 @@ fn render_buttons(app: &mut MyApp, ui: &mut egui::Ui) {
         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
             if ui.button("Open Editor").clicked() {
                 app.current_view = crate::models::gui::View::Editor;
+                app.pin_active_document();
+            }
+
+            let dirty = app.active_document().map(|doc| doc.is_dirty()).unwrap_or(false);
+            if dirty && ui.button("Save").clicked() {
+                app.save_svg();
             }
 
             if ui.button("Copy Code").clicked() {
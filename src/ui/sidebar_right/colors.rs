@@ -31,21 +31,35 @@ pub fn render(app: &mut MyApp, ui: &mut egui::Ui) {
                             ui.horizontal(|ui| {
                                 // Color picker button (clickable!)
                                 if ui.color_edit_button_srgba(&mut color).changed() {
-                                    // Color was changed - update the SVG file
                                     let new_hex = format!("#{:02X}{:02X}{:02X}", color.r(), color.g(), color.b());
-                                    if let Err(e) = replace_color_in_svg(svg_path, &old_hex, &new_hex) {
-                                        app.set_error_message(format!("Failed to update color: {}", e));
+
+                                    // Unsaved edits stay in the buffer; the change is saved with them
+                                    let dirty = app.active_document().map(|doc| doc.is_dirty()).unwrap_or(false);
+                                    if dirty {
+                                        if let Some(doc) = app.active_document_mut() {
+                                            doc.content = replace_color(&doc.content, &old_hex, &new_hex);
+                                        }
+                                        app.set_error_message(format!("✅ Updated {} to {} (unsaved)", old_hex, new_hex));
                                     } else {
-                                        app.set_error_message(format!("✅ Updated {} to {}", old_hex, new_hex));
-                                        // Reload the SVG code
-                                        if let Ok(content) = fs::read_to_string(svg_path) {
-                                            app.svg_code = content;
+                                        match replace_color_in_svg(svg_path, &old_hex, &new_hex) {
+                                            Ok((before, after)) => {
+                                                let reason = format!("Replaced {} with {}", old_hex, new_hex);
+                                                app.record_history(svg_path, Some(&before), &after, &reason);
+                                                app.set_error_message(format!("✅ Updated {} to {}", old_hex, new_hex));
+                                                // Reload the SVG code
+                                                if let Some(doc) = app.active_document_mut() {
+                                                    let _ = doc.reload();
+                                                }
+                                                // Force image cache to refresh by forgetting the texture
+                                                let img_uri = format!("file://{}", svg_path.display());
+                                                ui.ctx().forget_image(&img_uri);
+                                                // Request repaint to show the updated image
+                                                ui.ctx().request_repaint();
+                                            }
+                                            Err(e) => {
+                                                app.set_error_message(format!("Failed to update color: {}", e));
+                                            }
                                         }
-                                        // Force image cache to refresh by forgetting the texture
-                                        let img_uri = format!("file://{}", svg_path.display());
-                                        ui.ctx().forget_image(&img_uri);
-                                        // Request repaint to show the updated image
-                                        ui.ctx().request_repaint();
                                     }
                                 }
 
@@ -77,19 +91,23 @@ pub fn render(app: &mut MyApp, ui: &mut egui::Ui) {
     });
 }
 
-fn replace_color_in_svg(path: &std::path::Path, old_color: &str, new_color: &str) -> Result<(), Box<dyn std::error::Error>> {
+// Returns the content before and after the replacement
+fn replace_color_in_svg(path: &std::path::Path, old_color: &str, new_color: &str) -> Result<(String, String), Box<dyn std::error::Error>> {
     let content = fs::read_to_string(path)?;
+    let new_content = replace_color(&content, old_color, new_color);
+    fs::write(path, &new_content)?;
+
+    Ok((content, new_content))
+}
 
+fn replace_color(content: &str, old_color: &str, new_color: &str) -> String {
     // Replace all occurrences of the old color with the new color (case-insensitive)
     let old_lower = old_color.to_lowercase();
     let old_upper = old_color.to_uppercase();
 
-    let mut new_content = content.clone();
+    let mut new_content = content.to_string();
     new_content = new_content.replace(&old_lower, new_color);
     new_content = new_content.replace(&old_upper, new_color);
     new_content = new_content.replace(old_color, new_color);
-
-    fs::write(path, new_content)?;
-
-    Ok(())
+    new_content
 }
\ No newline at end of file
@@ -0,0 +1,81 @@
+use crate::models::gui::MyApp;
+use crate::utils::contrast::{self, ContrastLevel};
+use crate::utils::svg_parser;
+use egui::{Color32, RichText};
+
+pub fn render(app: &mut MyApp, ui: &mut egui::Ui) {
+    ui.vertical(|ui| {
+        ui.label(RichText::new("Contrast").size(16.0).strong());
+        ui.add_space(5.0);
+
+        let Some(svg_path) = app.selected_svg.clone() else {
+            return;
+        };
+
+        let backgrounds = contrast::parse_backgrounds(&app.contrast_backgrounds);
+        if backgrounds.is_empty() {
+            ui.label(RichText::new("No backgrounds configured (see Settings)").weak());
+            return;
+        }
+
+        let cached = matches!(&app.contrast_colors, Some((path, _)) if *path == svg_path);
+        if !cached {
+            let colors = svg_parser::parse_svg_info(&svg_path).map(|info| info.colors_used).unwrap_or_default();
+            app.contrast_colors = Some((svg_path.clone(), colors));
+        }
+        let colors = app.contrast_colors.as_ref().map(|(_, colors)| colors.clone()).unwrap_or_default();
+
+        if colors.is_empty() {
+            ui.label("No colors to check");
+            return;
+        }
+
+        egui::Grid::new("contrast_grid")
+            .num_columns(backgrounds.len() + 1)
+            .spacing([8.0, 4.0])
+            .show(ui, |ui| {
+                // Header row with one swatch per background
+                ui.label("");
+                for background in &backgrounds {
+                    render_swatch(ui, background).on_hover_text(contrast::to_hex(background));
+                }
+                ui.end_row();
+
+                for color in &colors {
+                    render_swatch(ui, color).on_hover_text(contrast::to_hex(color));
+
+                    for result in contrast::check_colors(&[*color], &backgrounds) {
+                        ui.label(
+                            RichText::new(format!("{:.1} {}", result.ratio, result.level.label()))
+                                .size(11.0)
+                                .monospace()
+                                .color(level_color(result.level)),
+                        );
+                    }
+                    ui.end_row();
+                }
+            });
+
+        ui.add_space(10.0);
+    });
+}
+
+pub fn level_color(level: ContrastLevel) -> Color32 {
+    match level {
+        ContrastLevel::AAA => Color32::from_rgb(120, 200, 120),
+        ContrastLevel::AA => Color32::from_rgb(230, 200, 80),
+        ContrastLevel::Fail => Color32::from_rgb(255, 100, 100),
+    }
+}
+
+pub fn render_swatch(ui: &mut egui::Ui, color: &svgtypes::Color) -> egui::Response {
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(16.0, 16.0), egui::Sense::hover());
+    ui.painter().rect_filled(rect, 3.0, Color32::from_rgb(color.red, color.green, color.blue));
+    ui.painter().rect_stroke(
+        rect,
+        3.0,
+        egui::Stroke::new(1.0, Color32::from_gray(90)),
+        egui::StrokeKind::Inside,
+    );
+    response
+}
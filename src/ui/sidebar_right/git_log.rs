@@ -0,0 +1,77 @@
+use crate::models::gui::MyApp;
+use crate::ui::popups::git_commit::status_color;
+use crate::utils::git;
+use crate::utils::history::format_timestamp;
+use egui::RichText;
+use std::path::PathBuf;
+
+// Number of commits listed for the selected file
+const LOG_LIMIT: usize = 50;
+
+pub fn render(app: &mut MyApp, ui: &mut egui::Ui) {
+    let Some(svg_path) = app.selected_svg.clone() else {
+        return;
+    };
+
+    let cached = matches!(&app.git_log_cache, Some((path, _)) if *path == svg_path);
+    if !cached {
+        let vault = PathBuf::from(&app.vault_path);
+        let log = match git::open_repo(&vault) {
+            Some(repo) => git::file_log(&repo, &svg_path, LOG_LIMIT).map_err(|e| e.to_string()),
+            None => Err("The vault is not a git repository".to_string()),
+        };
+        app.git_log_cache = Some((svg_path.clone(), log));
+    }
+
+    ui.horizontal(|ui| {
+        ui.label(RichText::new("Commits").size(16.0).strong());
+
+        let status = app.git_status.as_ref().and_then(|status| status.get(&svg_path));
+        if let Some(status) = status {
+            ui.label(RichText::new(status.label()).size(11.0).color(status_color(*status)));
+        }
+    });
+    ui.add_space(5.0);
+
+    let Some((_, log)) = &app.git_log_cache else {
+        return;
+    };
+
+    let entries = match log {
+        Ok(entries) => entries,
+        Err(e) => {
+            ui.label(RichText::new(e).size(12.0).color(egui::Color32::from_rgb(150, 150, 150)));
+            return;
+        }
+    };
+
+    if entries.is_empty() {
+        ui.label(
+            RichText::new("This file has not been committed yet")
+                .size(12.0)
+                .color(egui::Color32::from_rgb(150, 150, 150)),
+        );
+        return;
+    }
+
+    egui::ScrollArea::vertical()
+        .id_salt("git_log_scroll")
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+            for entry in entries {
+                ui.label(RichText::new(&entry.summary).size(12.0).strong());
+                ui.label(
+                    RichText::new(format!(
+                        "{} · {} · {}",
+                        entry.id,
+                        entry.author,
+                        format_timestamp(entry.time.max(0) as u64)
+                    ))
+                    .size(11.0)
+                    .monospace()
+                    .color(egui::Color32::from_rgb(150, 150, 150)),
+                );
+                ui.add_space(6.0);
+            }
+        });
+}
@@ -0,0 +1,199 @@
+use crate::models::compare::{file_label, CompareSide};
+use crate::models::gui::MyApp;
+use crate::ui::views::compare;
+use crate::utils::history::{self, Snapshot};
+use crate::utils::recent::RecentAction;
+use egui::RichText;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+enum Action {
+    Restore(Snapshot),
+    SaveCopy(Snapshot),
+    Compare(Snapshot),
+    // Vault-relative key of a deleted asset
+    Undelete(String),
+}
+
+pub fn render(app: &mut MyApp, ui: &mut egui::Ui) {
+    let Some(svg_path) = app.selected_svg.clone() else {
+        return;
+    };
+    let vault = PathBuf::from(&app.vault_path);
+
+    let cached = matches!(&app.history_cache, Some((path, _, _)) if *path == svg_path);
+    if !cached {
+        app.history_cache = Some((
+            svg_path.clone(),
+            history::snapshots(&vault, &svg_path),
+            history::deleted_assets(&vault),
+        ));
+    }
+    let (snapshots, deleted) = app
+        .history_cache
+        .as_ref()
+        .map(|(_, snapshots, deleted)| (snapshots.clone(), deleted.clone()))
+        .unwrap_or_default();
+
+    ui.label(RichText::new("History").size(16.0).strong());
+    ui.add_space(5.0);
+
+    if snapshots.is_empty() {
+        ui.label(
+            RichText::new("No versions yet. A snapshot is kept whenever this file is saved, recolored, formatted or renamed.")
+                .size(12.0)
+                .color(egui::Color32::from_rgb(150, 150, 150)),
+        );
+    }
+
+    let mut action: Option<Action> = None;
+
+    egui::ScrollArea::vertical()
+        .id_salt("history_scroll")
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+            for (idx, snapshot) in snapshots.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    let img_uri = format!("file://{}", history::object_path(&vault, &snapshot.hash).display());
+                    ui.add(egui::Image::new(img_uri).fit_to_exact_size(egui::vec2(40.0, 40.0)));
+
+                    ui.vertical(|ui| {
+                        let mut title = RichText::new(history::format_timestamp(snapshot.timestamp)).size(12.0);
+                        if idx == 0 {
+                            title = title.strong();
+                        }
+                        ui.label(title);
+                        ui.label(
+                            RichText::new(format!("{} · {}", snapshot.reason, &snapshot.hash[..8]))
+                                .size(11.0)
+                                .color(egui::Color32::from_rgb(150, 150, 150)),
+                        );
+
+                        ui.horizontal(|ui| {
+                            if ui.small_button("Restore").clicked() {
+                                action = Some(Action::Restore(snapshot.clone()));
+                            }
+                            if ui.small_button("Save as copy").clicked() {
+                                action = Some(Action::SaveCopy(snapshot.clone()));
+                            }
+                            if ui.small_button("Compare").on_hover_text("Compare with the file on disk").clicked() {
+                                action = Some(Action::Compare(snapshot.clone()));
+                            }
+                        });
+                    });
+                });
+                ui.add_space(6.0);
+            }
+
+            if !deleted.is_empty() {
+                ui.add_space(5.0);
+                egui::CollapsingHeader::new(RichText::new(format!("Deleted assets ({})", deleted.len())).strong())
+                    .id_salt("history_deleted")
+                    .show(ui, |ui| {
+                        for (key, snapshot) in &deleted {
+                            ui.horizontal(|ui| {
+                                let img_uri = format!("file://{}", history::object_path(&vault, &snapshot.hash).display());
+                                ui.add(egui::Image::new(img_uri).fit_to_exact_size(egui::vec2(40.0, 40.0)));
+
+                                ui.vertical(|ui| {
+                                    ui.label(RichText::new(key).size(12.0));
+                                    ui.label(
+                                        RichText::new(format!("Deleted {}", history::format_timestamp(snapshot.timestamp)))
+                                            .size(11.0)
+                                            .color(egui::Color32::from_rgb(150, 150, 150)),
+                                    );
+                                    if ui.small_button("Restore").clicked() {
+                                        action = Some(Action::Undelete(key.clone()));
+                                    }
+                                });
+                            });
+                            ui.add_space(6.0);
+                        }
+                    });
+            }
+        });
+
+    match action {
+        Some(Action::Restore(snapshot)) => restore(app, ui.ctx(), &vault, &svg_path, &snapshot),
+        Some(Action::SaveCopy(snapshot)) => save_copy(app, &vault, &svg_path, &snapshot),
+        Some(Action::Compare(snapshot)) => {
+            let before = history::read_snapshot(&vault, &snapshot.hash).map(|content| CompareSide {
+                label: format!("{} @ {}", file_label(&svg_path), history::format_timestamp(snapshot.timestamp)),
+                content,
+            });
+            match (before, CompareSide::from_file(&svg_path)) {
+                (Ok(before), Ok(after)) => compare::open(app, before, after),
+                (Err(e), _) | (_, Err(e)) => app.set_error_message(format!("Failed to read file: {}", e)),
+            }
+        }
+        Some(Action::Undelete(key)) => match history::restore_deleted(&vault, &key) {
+            Ok(path) => {
+                app.history_cache = None;
+                app.rescan_current_directory();
+                app.refresh_git_status();
+                app.set_error_message(format!("✅ Restored {}", file_label(&path)));
+            }
+            Err(e) => app.set_error_message(format!("Failed to restore: {}", e)),
+        },
+        None => {}
+    }
+}
+
+fn restore(app: &mut MyApp, ctx: &egui::Context, vault: &Path, svg_path: &Path, snapshot: &Snapshot) {
+    let content = match history::read_snapshot(vault, &snapshot.hash) {
+        Ok(content) => content,
+        Err(e) => {
+            app.set_error_message(format!("Failed to read snapshot: {}", e));
+            return;
+        }
+    };
+
+    let current = fs::read_to_string(svg_path).ok();
+    if current.as_deref() == Some(content.as_str()) {
+        app.set_error_message("This version is already the current one".to_string());
+        return;
+    }
+
+    if let Err(e) = fs::write(svg_path, &content) {
+        app.set_error_message(format!("Failed to restore: {}", e));
+        return;
+    }
+
+    let reason = format!("Restored {}", history::format_timestamp(snapshot.timestamp));
+    app.record_history(svg_path, current.as_deref(), &content, &reason);
+    app.reload_clean_documents();
+    ctx.forget_image(&format!("file://{}", svg_path.display()));
+    app.set_error_message(format!("✅ {}", reason));
+}
+
+fn save_copy(app: &mut MyApp, vault: &Path, svg_path: &Path, snapshot: &Snapshot) {
+    let content = match history::read_snapshot(vault, &snapshot.hash) {
+        Ok(content) => content,
+        Err(e) => {
+            app.set_error_message(format!("Failed to read snapshot: {}", e));
+            return;
+        }
+    };
+
+    let stem = svg_path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let date = history::format_timestamp(snapshot.timestamp)
+        .trim_end_matches(" UTC")
+        .replace([' ', ':'], "-");
+    let parent = svg_path.parent().unwrap_or(Path::new("."));
+
+    let mut target = parent.join(format!("{}-{}.svg", stem, date));
+    let mut counter = 2;
+    while target.exists() {
+        target = parent.join(format!("{}-{}-{}.svg", stem, date, counter));
+        counter += 1;
+    }
+
+    match fs::write(&target, content) {
+        Ok(_) => {
+            app.record_recent(&target, RecentAction::Exported);
+            app.set_error_message(format!("✅ Saved copy as {}", file_label(&target)));
+            app.rescan_current_directory();
+        }
+        Err(e) => app.set_error_message(format!("Failed to save copy: {}", e)),
+    }
+}
@@ -0,0 +1,247 @@
+use crate::models::editor::EmbeddedDraft;
+use crate::models::gui::MyApp;
+use crate::utils::embedded_metadata;
+use crate::utils::metadata::{AssetMetadata, MetadataField};
+use egui::RichText;
+use std::fs;
+use std::path::Path;
+
+// Tags shown on a gallery tile before collapsing into "+N"
+pub(crate) const TILE_TAG_LIMIT: usize = 2;
+
+pub fn render(app: &mut MyApp, ui: &mut egui::Ui) {
+    let Some(svg_path) = app.selected_svg.clone() else {
+        return;
+    };
+
+    ui.add_space(2.0);
+    ui.label(RichText::new("Metadata").size(16.0).strong());
+    ui.add_space(5.0);
+
+    let suggestions = app.metadata.all_tags();
+    let metadata = app.metadata.entry(&svg_path);
+    if render_fields(ui, metadata, &mut app.metadata_tag_input, &suggestions) {
+        app.persist_metadata(&svg_path);
+    }
+
+    ui.add_space(5.0);
+    render_embedded(app, ui, &svg_path);
+
+    ui.add_space(10.0);
+}
+
+// <title>, <desc> and RDF fields stored in the file itself
+fn render_embedded(app: &mut MyApp, ui: &mut egui::Ui, svg_path: &Path) {
+    let content = match app.active_document().filter(|doc| doc.path == svg_path) {
+        Some(doc) => doc.content.clone(),
+        None => fs::read_to_string(svg_path).unwrap_or_default(),
+    };
+    let embedded = embedded_metadata::read(&content);
+
+    // Follow the file unless the user is in the middle of editing
+    let stale = match &app.embedded_draft {
+        Some(draft) => draft.path != svg_path || (!draft.is_modified() && draft.original != embedded),
+        None => true,
+    };
+    if stale {
+        app.embedded_draft = Some(EmbeddedDraft::new(svg_path, embedded));
+    }
+
+    let mut write = false;
+    let mut revert = false;
+
+    egui::CollapsingHeader::new(RichText::new("Embedded in file").strong())
+        .id_salt("embedded_metadata")
+        .show(ui, |ui| {
+            let Some(draft) = app.embedded_draft.as_mut() else {
+                return;
+            };
+
+            egui::Grid::new("embedded_metadata_grid")
+                .num_columns(2)
+                .spacing([8.0, 4.0])
+                .show(ui, |ui| {
+                    ui.label("Title");
+                    ui.text_edit_singleline(&mut draft.edited.title);
+                    ui.end_row();
+
+                    ui.label("Description");
+                    ui.add(egui::TextEdit::multiline(&mut draft.edited.desc).desired_rows(2));
+                    ui.end_row();
+
+                    ui.label("Creator");
+                    ui.text_edit_singleline(&mut draft.edited.creator);
+                    ui.end_row();
+
+                    ui.label("Rights");
+                    ui.text_edit_singleline(&mut draft.edited.rights);
+                    ui.end_row();
+
+                    ui.label("Date");
+                    ui.add(egui::TextEdit::singleline(&mut draft.edited.date).hint_text("2024-01-31"));
+                    ui.end_row();
+
+                    ui.label("Keywords");
+                    ui.add(egui::TextEdit::singleline(&mut draft.keywords).hint_text("icon, arrow"));
+                    ui.end_row();
+                });
+
+            ui.add_space(4.0);
+
+            ui.horizontal(|ui| {
+                let modified = draft.is_modified();
+                if ui.add_enabled(modified, egui::Button::new("Write to file")).clicked() {
+                    write = true;
+                }
+                if ui.add_enabled(modified, egui::Button::new("Revert")).clicked() {
+                    revert = true;
+                }
+                if let Some(metadata) = app.metadata.get(svg_path) {
+                    if ui.button("From tags").on_hover_text("Copy tags, description, author and license").clicked() {
+                        draft.edited.mirror(metadata);
+                        draft.keywords = draft.edited.keywords.join(", ");
+                    }
+                }
+            });
+
+            if app.mirror_metadata {
+                ui.label(RichText::new("Tags and details are mirrored into the file on change").size(10.0).weak());
+            }
+        });
+
+    if revert {
+        app.embedded_draft = None;
+    }
+
+    if write {
+        let Some(draft) = app.embedded_draft.as_ref() else {
+            return;
+        };
+        let mut edited = draft.edited.clone();
+        edited.keywords = embedded_metadata::split_keywords(&draft.keywords);
+        for field in [&mut edited.title, &mut edited.desc, &mut edited.creator, &mut edited.rights, &mut edited.date] {
+            *field = field.trim().to_string();
+        }
+
+        if app.update_svg_content(svg_path, "Edited embedded metadata", |content| embedded_metadata::write(content, &edited)) {
+            app.embedded_draft = None;
+        }
+    }
+}
+
+pub(crate) fn render_tag_chip(ui: &mut egui::Ui, tag: &str, size: f32) -> egui::Response {
+    egui::Frame::new()
+        .fill(egui::Color32::from_rgb(60, 70, 90))
+        .corner_radius(8.0)
+        .inner_margin(egui::Margin::symmetric(5, 1))
+        .show(ui, |ui| {
+            ui.label(RichText::new(tag).size(size).color(egui::Color32::from_rgb(200, 210, 230)));
+        })
+        .response
+}
+
+// Editor for one asset's metadata. Returns true when the change should be
+// written to disk: tags and fields right away, text once it loses focus.
+pub(crate) fn render_fields(
+    ui: &mut egui::Ui,
+    metadata: &mut AssetMetadata,
+    tag_input: &mut String,
+    suggestions: &[String],
+) -> bool {
+    let mut persist = false;
+
+    // Tags
+    let mut remove_tag: Option<usize> = None;
+    ui.horizontal_wrapped(|ui| {
+        for (idx, tag) in metadata.tags.iter().enumerate() {
+            let chip = render_tag_chip(ui, tag, 11.0).on_hover_text("Click to remove");
+            if chip.interact(egui::Sense::click()).clicked() {
+                remove_tag = Some(idx);
+            }
+        }
+    });
+    if let Some(idx) = remove_tag {
+        metadata.tags.remove(idx);
+        persist = true;
+    }
+
+    ui.horizontal(|ui| {
+        let response = ui.add(
+            egui::TextEdit::singleline(tag_input)
+                .hint_text("Add tag")
+                .desired_width(120.0),
+        );
+        let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+        if (submitted || ui.small_button("+").clicked()) && metadata.add_tag(tag_input) {
+            tag_input.clear();
+            persist = true;
+        }
+    });
+
+    // Existing tags that match what is being typed
+    let typed = tag_input.trim().to_lowercase();
+    if !typed.is_empty() {
+        let matching: Vec<&String> = suggestions
+            .iter()
+            .filter(|t| t.to_lowercase().starts_with(&typed) && !metadata.has_tag(t))
+            .take(6)
+            .collect();
+
+        ui.horizontal_wrapped(|ui| {
+            for tag in matching {
+                if ui.small_button(tag).clicked() {
+                    metadata.add_tag(tag);
+                    tag_input.clear();
+                    persist = true;
+                }
+            }
+        });
+    }
+
+    ui.add_space(5.0);
+
+    egui::Grid::new(ui.id().with("metadata_grid"))
+        .num_columns(2)
+        .spacing([8.0, 4.0])
+        .show(ui, |ui| {
+            ui.label("Description");
+            persist |= ui
+                .add(egui::TextEdit::multiline(&mut metadata.description).desired_rows(2))
+                .lost_focus();
+            ui.end_row();
+
+            ui.label("Author");
+            persist |= ui.text_edit_singleline(&mut metadata.author).lost_focus();
+            ui.end_row();
+
+            ui.label("License");
+            persist |= ui.text_edit_singleline(&mut metadata.license).lost_focus();
+            ui.end_row();
+        });
+
+    // Custom key/value fields
+    let mut remove_field: Option<usize> = None;
+    for (idx, field) in metadata.fields.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            persist |= ui
+                .add(egui::TextEdit::singleline(&mut field.key).hint_text("Key").desired_width(80.0))
+                .lost_focus();
+            persist |= ui
+                .add(egui::TextEdit::singleline(&mut field.value).hint_text("Value").desired_width(120.0))
+                .lost_focus();
+            if ui.small_button("✖").on_hover_text("Remove").clicked() {
+                remove_field = Some(idx);
+            }
+        });
+    }
+    if let Some(idx) = remove_field {
+        metadata.fields.remove(idx);
+        persist = true;
+    }
+
+    if ui.small_button("+ Field").clicked() {
+        metadata.fields.push(MetadataField::default());
+    }
+
+    persist
+}
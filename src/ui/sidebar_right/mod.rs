@@ -2,8 +2,12 @@
 mod preview;
 mod code_view;
 mod colors;
+pub(crate) mod contrast;
+mod history;
+mod git_log;
+pub(crate) mod metadata;
 
-use crate::models::gui::MyApp;
+use crate::models::gui::{MyApp, SidebarTab};
 use egui::SidePanel;
 
 pub fn render(app: &mut MyApp, ctx: &egui::Context) {
@@ -18,6 +22,28 @@ pub fn render(app: &mut MyApp, ctx: &egui::Context) {
 
             ui.separator();
 
+            ui.horizontal(|ui| {
+                ui.selectable_value(&mut app.sidebar_tab, SidebarTab::Details, "Details");
+                ui.selectable_value(&mut app.sidebar_tab, SidebarTab::History, "History");
+                if app.git_status.is_some() {
+                    ui.selectable_value(&mut app.sidebar_tab, SidebarTab::Commits, "Commits");
+                }
+            });
+
+            ui.separator();
+
+            match app.sidebar_tab {
+                SidebarTab::History => {
+                    history::render(app, ui);
+                    return;
+                }
+                SidebarTab::Commits if app.git_status.is_some() => {
+                    git_log::render(app, ui);
+                    return;
+                }
+                _ => {}
+            }
+
             // Calculate available height for colors
             // Reserve space for code editor (minimum 200px)
             let min_code_height = 200.0;
@@ -29,7 +55,9 @@ pub fn render(app: &mut MyApp, ctx: &egui::Context) {
                 .max_height(available_for_colors)
                 .auto_shrink([false, false])
                 .show(ui, |ui| {
+                    metadata::render(app, ui);
                     colors::render(app, ui);
+                    contrast::render(app, ui);
                 });
 
             ui.separator();
@@ -1,4 +1,5 @@
 use crate::models::gui::MyApp;
+use crate::utils::embedded_metadata::EmbeddedMetadata;
 use crate::utils::svg_parser;
 use egui::RichText;
 
@@ -106,6 +107,8 @@ fn render_stats(app: &MyApp, ui: &mut egui::Ui) {
                                 ui.label(RichText::new("⚡").size(12.0));
                                 ui.label(RichText::new(format!("{} commands", info.total_path_commands)).size(11.0));
                             });
+
+                            render_embedded(ui, &info.embedded);
                         });
                     });
             }
@@ -116,4 +119,34 @@ fn render_stats(app: &MyApp, ui: &mut egui::Ui) {
     }
 
     ui.add_space(8.0); // Small spacing after stats before colors
-}
\ No newline at end of file
+}
+
+// Title, description and credits carried inside the file
+fn render_embedded(ui: &mut egui::Ui, embedded: &EmbeddedMetadata) {
+    if embedded.is_empty() {
+        return;
+    }
+
+    ui.add_space(4.0);
+
+    if !embedded.title.is_empty() {
+        ui.horizontal(|ui| {
+            ui.label(RichText::new("🏷").size(12.0));
+            ui.label(RichText::new(&embedded.title).size(11.0).strong());
+        });
+    }
+    if !embedded.desc.is_empty() {
+        ui.label(RichText::new(&embedded.desc).size(11.0).weak());
+    }
+
+    let credits: Vec<&str> = [embedded.creator.as_str(), embedded.rights.as_str(), embedded.date.as_str()]
+        .into_iter()
+        .filter(|s| !s.is_empty())
+        .collect();
+    if !credits.is_empty() {
+        ui.label(RichText::new(credits.join(" · ")).size(10.0).weak());
+    }
+    if !embedded.keywords.is_empty() {
+        ui.label(RichText::new(embedded.keywords.join(", ")).size(10.0).weak());
+    }
+}
@@ -1,38 +1,83 @@
 use crate::models::gui::MyApp;
+use crate::models::compare::{file_label, CompareSide};
 use crate::models::gui::View;
-use egui::ScrollArea;
+use crate::ui::views::{compare, live_preview};
+use crate::utils::svg_format::{self, FormatAction};
+use crate::utils::svg_validator::{Diagnostic, Severity};
+use egui::{RichText, ScrollArea};
 use egui_code_editor::{CodeEditor, ColorTheme, Syntax};
 
+const DIAGNOSTICS_HEIGHT: f32 = 90.0;
+
+// XML highlighting tuned for SVG: element names as keywords, common attributes as types
+pub fn svg_syntax() -> Syntax {
+    Syntax::new("svg")
+        .with_case_sensitive(true)
+        // XML has no line comments, but the builder needs a marker and its default "//"
+        // would swallow the rest of every line with a URL. NUL can't appear in XML.
+        .with_comment("\u{0}")
+        .with_comment_multiline(["<!--", "-->"])
+        .with_keywords([
+            "svg", "g", "path", "rect", "circle", "ellipse", "line", "polyline", "polygon",
+            "text", "tspan", "defs", "use", "symbol", "clipPath", "mask", "pattern", "image",
+            "linearGradient", "radialGradient", "stop", "filter", "style", "title", "desc",
+            "metadata", "marker", "foreignObject", "script", "a",
+        ])
+        .with_types([
+            "xmlns", "viewBox", "width", "height", "x", "y", "d", "fill", "stroke",
+            "stroke-width", "stroke-linecap", "stroke-linejoin", "opacity", "fill-opacity",
+            "fill-rule", "clip-rule", "transform", "id", "class", "style", "cx", "cy", "r",
+            "rx", "ry", "x1", "y1", "x2", "y2", "points", "offset", "href", "version",
+        ])
+        .with_special(["xml", "xlink", "DOCTYPE", "CDATA"])
+}
+
 // For when called with Ui (already inside a panel)
 pub fn render_editor(app: &mut MyApp, ui: &mut egui::Ui) {
-    // Determine which code to edit and which syntax to use
-    let (code_buffer, syntax, is_svg) = if app.selected_svg.is_some() {
-        (&mut app.svg_code, Syntax::python(), true) // Changed to xml() for SVG syntax
-    } else {
-        (&mut app.code, Syntax::rust(), false)
-    };
-
-    // Show file name if editing SVG
-    if is_svg {
-        if let Some(svg_path) = &app.selected_svg {
-            ui.horizontal(|ui| {
-                ui.label("📄");
-                ui.label(svg_path.file_name().unwrap().to_string_lossy());
+    // Live preview of the unsaved buffer next to the code
+    if app.active_document.is_some() {
+        egui::SidePanel::right("live_preview_panel")
+            .resizable(true)
+            .default_width(ui.available_width() / 2.0)
+            .min_width(150.0)
+            .show_inside(ui, |ui| {
+                live_preview::render(app, ui);
             });
-            ui.add_space(8.0);
-        }
     }
 
-    // Reserve space for buttons at the bottom
-    let button_height = if is_svg { 40.0 } else { 0.0 };
+    // Tabs for every open document
+    if !app.documents.is_empty() {
+        render_tabs(app, ui);
+        render_disk_change_warning(app, ui);
+        ui.add_space(8.0);
+    }
+
+    // Validate the buffer as it is now, before the editor gets to change it
+    let active = app.active_document;
+    let diagnostics = active
+        .and_then(|idx| app.documents.get_mut(idx))
+        .map(|doc| doc.diagnostics())
+        .unwrap_or_default();
+
+    // Determine which code to edit and which syntax to use
+    let (code_buffer, syntax, is_svg) = match active.and_then(|idx| app.documents.get_mut(idx)) {
+        Some(doc) => (&mut doc.content, svg_syntax(), true),
+        None => (&mut app.code, Syntax::rust(), false),
+    };
+
+    // Reserve space for diagnostics and buttons at the bottom
+    let button_height = if is_svg { 40.0 + DIAGNOSTICS_HEIGHT } else { 0.0 };
     let available_height = ui.available_height() - button_height;
 
+    let mut edited = false;
+
     // Code editor with fixed height
     ScrollArea::both() // Allow both horizontal and vertical scrolling
         .auto_shrink([false, false])
         .max_height(available_height)
         .show(ui, |ui| {
-            CodeEditor::default()
+            let code_before = code_buffer.clone();
+            let output = CodeEditor::default()
                 .id_source("code editor")
                 .with_rows(20)
                 .with_fontsize(14.0)
@@ -40,10 +85,30 @@ pub fn render_editor(app: &mut MyApp, ui: &mut egui::Ui) {
                 .with_syntax(syntax)
                 .with_numlines(true)
                 .show(ui, code_buffer);
+
+            // Markers are only meaningful while the text still matches the diagnostics.
+            // After an edit, repaint so the preview picks up the new buffer right away.
+            if *code_buffer == code_before {
+                paint_diagnostic_markers(ui, &output, code_buffer, &diagnostics);
+            } else {
+                ui.ctx().request_repaint();
+                if is_svg {
+                    edited = true;
+                }
+            }
         });
 
+    // Edited documents keep their tab
+    if edited {
+        app.pin_active_document();
+    }
+
     ui.add_space(8.0);
 
+    if is_svg {
+        render_diagnostics(ui, &diagnostics);
+    }
+
     // Save button (only show when editing SVG)
     if is_svg {
         ui.horizontal(|ui| {
@@ -51,6 +116,19 @@ pub fn render_editor(app: &mut MyApp, ui: &mut egui::Ui) {
                 app.save_svg();
             }
 
+            if ui.button("⇆ Compare with disk").clicked() {
+                compare_with_disk(app);
+            }
+
+            ui.menu_button("Format", |ui| {
+                for action in FormatAction::ALL {
+                    if ui.button(action.label()).clicked() {
+                        format_active_document(app, action);
+                        ui.close();
+                    }
+                }
+            });
+
             if ui.button("✖ Close").clicked() {
                 // Go back to Gallery view, keeping the SVG selected
                 // so the svg_overview panel stays open
@@ -58,4 +136,215 @@ pub fn render_editor(app: &mut MyApp, ui: &mut egui::Ui) {
             }
         });
     }
-}
\ No newline at end of file
+}
+
+// Rewrite the buffer; the result stays unsaved like any other edit
+fn format_active_document(app: &mut MyApp, action: FormatAction) {
+    let options = app.format_options.clone();
+    let Some(doc) = app.active_document_mut() else {
+        return;
+    };
+
+    match svg_format::apply(action, &doc.content, &options) {
+        Ok(formatted) => {
+            doc.content = formatted;
+            app.pin_active_document();
+        }
+        Err(e) => app.set_error_message(format!("Can't {}: {}", action.label().to_lowercase(), e)),
+    }
+}
+
+fn compare_with_disk(app: &mut MyApp) {
+    let Some(doc) = app.active_document() else {
+        return;
+    };
+    let name = file_label(&doc.path);
+    let buffer = CompareSide {
+        label: format!("{} (unsaved)", name),
+        content: doc.content.clone(),
+    };
+
+    match CompareSide::from_file(&doc.path) {
+        Ok(mut disk) => {
+            disk.label = format!("{} (on disk)", name);
+            compare::open(app, disk, buffer);
+        }
+        Err(e) => app.set_error_message(format!("Failed to read file: {}", e)),
+    }
+}
+
+fn render_tabs(app: &mut MyApp, ui: &mut egui::Ui) {
+    let mut activate: Option<usize> = None;
+    let mut close: Option<usize> = None;
+    let mut save_all = false;
+
+    ui.horizontal(|ui| {
+        ScrollArea::horizontal()
+            .id_salt("editor_tabs")
+            .max_width(ui.available_width() - 90.0)
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    for (idx, doc) in app.documents.iter().enumerate() {
+                        let is_active = app.active_document == Some(idx);
+                        let marker = if doc.is_dirty() { "● " } else { "" };
+
+                        let tab = ui.selectable_label(is_active, format!("{}{}", marker, doc.name()));
+                        if tab.clicked() {
+                            activate = Some(idx);
+                        }
+                        if tab.middle_clicked() {
+                            close = Some(idx);
+                        }
+                        tab.on_hover_text(doc.path.display().to_string());
+
+                        if ui.small_button("✖").on_hover_text("Close").clicked() {
+                            close = Some(idx);
+                        }
+
+                        ui.add_space(6.0);
+                    }
+                });
+            });
+
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            let any_dirty = app.has_dirty_documents();
+            if ui.add_enabled(any_dirty, egui::Button::new("💾 Save all")).clicked() {
+                save_all = true;
+            }
+        });
+    });
+
+    if let Some(idx) = activate {
+        app.activate_document(idx);
+    }
+    if let Some(idx) = close {
+        app.request_close_document(idx);
+    }
+    if save_all {
+        app.save_all_documents();
+    }
+}
+
+// Warn when the file was changed by another program since it was opened
+fn render_disk_change_warning(app: &mut MyApp, ui: &mut egui::Ui) {
+    let Some(doc) = app.active_document_mut() else {
+        return;
+    };
+    if !doc.poll_disk_change() {
+        return;
+    }
+
+    let mut reload = false;
+    let mut keep = false;
+
+    egui::Frame::new()
+        .fill(egui::Color32::from_rgb(70, 55, 20))
+        .inner_margin(egui::Margin::same(5))
+        .corner_radius(4.0)
+        .show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(
+                    RichText::new("⚠ This file changed on disk since it was opened")
+                        .color(egui::Color32::from_rgb(255, 200, 0)),
+                );
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button("Keep mine").clicked() {
+                        keep = true;
+                    }
+                    if ui.button("Reload").clicked() {
+                        reload = true;
+                    }
+                });
+            });
+        });
+
+    if let Some(doc) = app.active_document_mut() {
+        if reload {
+            if let Err(e) = doc.reload() {
+                app.set_error_message(format!("Failed to reload: {}", e));
+            }
+        } else if keep {
+            doc.acknowledge_disk_change();
+        }
+    }
+}
+
+fn severity_color(severity: Severity) -> egui::Color32 {
+    match severity {
+        Severity::Error => egui::Color32::from_rgb(255, 100, 100),
+        Severity::Warning => egui::Color32::from_rgb(230, 200, 80),
+    }
+}
+
+// Underline each reported position, from the column to the end of its line
+fn paint_diagnostic_markers(
+    ui: &egui::Ui,
+    output: &egui::text_edit::TextEditOutput,
+    text: &str,
+    diagnostics: &[Diagnostic],
+) {
+    let line_starts: Vec<usize> = std::iter::once(0)
+        .chain(text.char_indices().filter(|(_, c)| *c == '\n').map(|(i, _)| i + 1))
+        .map(|byte| text[..byte].chars().count())
+        .collect();
+    let total_chars = text.chars().count();
+
+    for diagnostic in diagnostics {
+        let Some(&line_start) = line_starts.get(diagnostic.line.saturating_sub(1)) else {
+            continue;
+        };
+        let line_end = line_starts
+            .get(diagnostic.line)
+            .map(|next| next - 1)
+            .unwrap_or(total_chars);
+        let start = (line_start + diagnostic.column.saturating_sub(1)).min(line_end);
+
+        let from = output.galley.pos_from_cursor(egui::text::CCursor::new(start));
+        let to = output.galley.pos_from_cursor(egui::text::CCursor::new(line_end));
+        let offset = output.galley_pos.to_vec2();
+
+        let y = from.bottom() + offset.y;
+        let x_start = from.left() + offset.x;
+        let x_end = (to.right() + offset.x).max(x_start + 8.0);
+
+        ui.painter().line_segment(
+            [egui::pos2(x_start, y), egui::pos2(x_end, y)],
+            egui::Stroke::new(2.0, severity_color(diagnostic.severity)),
+        );
+    }
+}
+
+fn render_diagnostics(ui: &mut egui::Ui, diagnostics: &[Diagnostic]) {
+    egui::Frame::new()
+        .fill(egui::Color32::from_rgb(30, 29, 25))
+        .inner_margin(egui::Margin::same(5))
+        .show(ui, |ui| {
+            ui.set_height(DIAGNOSTICS_HEIGHT - 18.0);
+
+            if diagnostics.is_empty() {
+                ui.label(RichText::new("✅ Valid SVG").size(12.0).color(egui::Color32::GREEN));
+                return;
+            }
+
+            ScrollArea::vertical()
+                .id_salt("diagnostics_scroll")
+                .auto_shrink([false, false])
+                .show(ui, |ui| {
+                    for diagnostic in diagnostics {
+                        let icon = match diagnostic.severity {
+                            Severity::Error => "⛔",
+                            Severity::Warning => "⚠",
+                        };
+                        ui.label(
+                            RichText::new(format!("{} {}", icon, diagnostic))
+                                .size(12.0)
+                                .monospace()
+                                .color(severity_color(diagnostic.severity)),
+                        );
+                    }
+                });
+        });
+
+    ui.add_space(8.0);
+}
@@ -0,0 +1,237 @@
+use crate::models::compare::{CompareMode, CompareResult, CompareSide};
+use crate::models::gui::{MyApp, View};
+use crate::ui::views::live_preview::paint_checkerboard;
+use crate::utils::svg_diff::{self, Change};
+use crate::utils::svg_render;
+use egui::{Color32, RichText, ScrollArea};
+
+// Square size both sides are rasterized at for the pixel comparison
+const RENDER_SIZE: u32 = 512;
+
+// Channel difference below which two pixels count as the same (antialiasing noise)
+const PIXEL_TOLERANCE: u8 = 8;
+
+// Show two versions of an SVG next to each other
+pub fn open(app: &mut MyApp, before: CompareSide, after: CompareSide) {
+    if app.current_view != View::Compare {
+        app.compare.return_view = app.current_view;
+    }
+    app.compare.before = Some(before);
+    app.compare.after = Some(after);
+    app.compare.result = None;
+    app.current_view = View::Compare;
+}
+
+pub fn render(app: &mut MyApp, ui: &mut egui::Ui) {
+    egui::Frame::new()
+        .inner_margin(egui::Margin::same(12))
+        .show(ui, |ui| {
+            render_toolbar(app, ui);
+            ui.add_space(8.0);
+
+            if app.compare.before.is_none() || app.compare.after.is_none() {
+                ui.label(RichText::new("Pick two SVGs to compare").weak());
+                return;
+            }
+
+            if app.compare.result.is_none() {
+                app.compare.result = Some(compute(app, ui.ctx()));
+            }
+            let Some(result) = &app.compare.result else {
+                return;
+            };
+
+            let images_height = (ui.available_height() * 0.6).max(150.0);
+            match app.compare.mode {
+                CompareMode::SideBySide => {
+                    ui.columns(2, |columns| {
+                        render_image(&mut columns[0], &result.before, images_height);
+                        render_image(&mut columns[1], &result.after, images_height);
+                    });
+                }
+                CompareMode::Difference => match &result.difference {
+                    Some(texture) => render_image(ui, &Ok(texture.clone()), images_height),
+                    None => {
+                        ui.label(RichText::new("Both sides must render to show a difference").weak());
+                    }
+                },
+            }
+
+            ui.add_space(5.0);
+            if result.difference.is_some() {
+                let percent = result.changed_pixels as f32 / result.total_pixels.max(1) as f32 * 100.0;
+                ui.label(
+                    RichText::new(format!("{} pixels differ ({:.2}%)", result.changed_pixels, percent))
+                        .size(12.0)
+                        .color(Color32::from_rgb(150, 150, 150)),
+                );
+            }
+
+            ui.separator();
+            render_structure(ui, &result.structure);
+        });
+}
+
+fn render_toolbar(app: &mut MyApp, ui: &mut egui::Ui) {
+    let mut pick_before = false;
+    let mut pick_after = false;
+    let mut swap = false;
+    let mut close = false;
+
+    ui.horizontal(|ui| {
+        ui.label(RichText::new("Compare").size(20.0).strong());
+        ui.add_space(10.0);
+
+        let label = |side: &Option<CompareSide>| side.as_ref().map(|s| s.label.clone()).unwrap_or("—".to_string());
+
+        ui.label(RichText::new("A:").strong());
+        ui.label(label(&app.compare.before));
+        if ui.small_button("Open…").clicked() {
+            pick_before = true;
+        }
+
+        if ui.button("⇆").on_hover_text("Swap").clicked() {
+            swap = true;
+        }
+
+        ui.label(RichText::new("B:").strong());
+        ui.label(label(&app.compare.after));
+        if ui.small_button("Open…").clicked() {
+            pick_after = true;
+        }
+
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            if ui.button("✖ Close").clicked() {
+                close = true;
+            }
+
+            ui.selectable_value(&mut app.compare.mode, CompareMode::Difference, "Difference");
+            ui.selectable_value(&mut app.compare.mode, CompareMode::SideBySide, "Side by side");
+        });
+    });
+
+    if swap {
+        std::mem::swap(&mut app.compare.before, &mut app.compare.after);
+        app.compare.result = None;
+    }
+
+    if pick_before || pick_after {
+        if let Some(path) = rfd::FileDialog::new()
+            .add_filter("SVG", &["svg"])
+            .set_directory(&app.current_path)
+            .pick_file()
+        {
+            match CompareSide::from_file(&path) {
+                Ok(side) => {
+                    if pick_before {
+                        app.compare.before = Some(side);
+                    } else {
+                        app.compare.after = Some(side);
+                    }
+                    app.compare.result = None;
+                }
+                Err(e) => app.set_error_message(format!("Failed to read file: {}", e)),
+            }
+        }
+    }
+
+    if close {
+        app.current_view = app.compare.return_view;
+    }
+}
+
+fn compute(app: &MyApp, ctx: &egui::Context) -> CompareResult {
+    let before = app.compare.before.as_ref().map(|s| s.content.as_str()).unwrap_or_default();
+    let after = app.compare.after.as_ref().map(|s| s.content.as_str()).unwrap_or_default();
+
+    let render = |content: &str| {
+        svg_render::parse_tree(content).and_then(|tree| svg_render::rasterize_fit(&tree, RENDER_SIZE))
+    };
+    let before_pixmap = render(before);
+    let after_pixmap = render(after);
+
+    let mut difference = None;
+    let mut changed_pixels = 0;
+    if let (Ok(a), Ok(b)) = (&before_pixmap, &after_pixmap) {
+        let (image, changed) = svg_render::difference_image(a, b, PIXEL_TOLERANCE);
+        difference = Some(ctx.load_texture("compare_difference", image, egui::TextureOptions::LINEAR));
+        changed_pixels = changed;
+    }
+
+    let texture = |name: &str, pixmap: Result<_, String>| {
+        pixmap.map(|p| ctx.load_texture(name, svg_render::to_color_image(&p), egui::TextureOptions::LINEAR))
+    };
+
+    CompareResult {
+        before: texture("compare_before", before_pixmap),
+        after: texture("compare_after", after_pixmap),
+        difference,
+        changed_pixels,
+        total_pixels: (RENDER_SIZE * RENDER_SIZE) as usize,
+        structure: svg_diff::diff(before, after).map_err(|e| e.to_string()),
+    }
+}
+
+fn render_image(ui: &mut egui::Ui, texture: &Result<egui::TextureHandle, String>, height: f32) {
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(ui.available_width(), height), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    paint_checkerboard(&painter, rect);
+
+    match texture {
+        Ok(texture) => {
+            let side = rect.width().min(rect.height());
+            let image_rect = egui::Rect::from_center_size(rect.center(), egui::vec2(side, side));
+            painter.image(
+                texture.id(),
+                image_rect,
+                egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+                Color32::WHITE,
+            );
+        }
+        Err(e) => {
+            painter.text(
+                rect.center(),
+                egui::Align2::CENTER_CENTER,
+                format!("⛔ {}", e),
+                egui::FontId::proportional(13.0),
+                Color32::from_rgb(255, 100, 100),
+            );
+        }
+    }
+}
+
+fn render_structure(ui: &mut egui::Ui, structure: &Result<Vec<svg_diff::DiffEntry>, String>) {
+    ui.label(RichText::new("Structure").size(16.0).strong());
+    ui.add_space(5.0);
+
+    let entries = match structure {
+        Ok(entries) => entries,
+        Err(e) => {
+            ui.label(RichText::new(format!("⛔ {}", e)).color(Color32::from_rgb(255, 100, 100)));
+            return;
+        }
+    };
+
+    if entries.is_empty() {
+        ui.label(RichText::new("✅ No differences in elements or attributes").color(Color32::GREEN));
+        return;
+    }
+
+    ScrollArea::vertical()
+        .id_salt("compare_structure")
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+            for entry in entries {
+                let (marker, color) = match entry.change {
+                    Change::Added => ("+", Color32::from_rgb(100, 220, 100)),
+                    Change::Removed => ("−", Color32::from_rgb(255, 100, 100)),
+                    Change::Modified => ("~", Color32::from_rgb(230, 200, 80)),
+                };
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(marker).monospace().strong().color(color));
+                    ui.label(RichText::new(&entry.path).monospace().color(Color32::from_rgb(150, 150, 150)));
+                    ui.label(RichText::new(&entry.detail).monospace().color(color));
+                });
+            }
+        });
+}
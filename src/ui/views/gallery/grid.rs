@@ -4,6 +4,7 @@ use crate::models::file_items::FileSystemItem;
 use crate::utils::file_finder::{scan_directory_recursive, FileFilter};
 use egui::ScrollArea;
 use std::path::PathBuf;
+use super::helpers::PendingActions;
 use super::items;
 
 pub fn render(
@@ -11,10 +12,7 @@ pub fn render(
     ui: &mut egui::Ui,
     navigate_to: &mut Option<String>,
     load_svg: &mut Option<PathBuf>,
-    pending_edit: &mut Option<PathBuf>,
-    pending_rename: &mut Option<(PathBuf, String)>,
-    pending_delete: &mut Option<PathBuf>,
-    pending_error: &mut Option<String>,
+    pending: &mut PendingActions,
 ) {
     // Extract all needed data upfront to avoid borrow issues
     let thumbnail_size = app.get_thumbnail_size();
@@ -35,38 +33,31 @@ pub fn render(
     if search_active && !search_query.is_empty() {
         let query = search_query.to_lowercase();
 
-        let (filter, root_path) = match current_view {
-            View::Gallery => (FileFilter::Svg, vault_path),
-            View::Fonts => (FileFilter::Font, font_path),
-            _ => {
-                let items_clone: Vec<FileSystemItem> = app.current_items.clone();
-                return render_grid(ui, &items_clone, thumbnail_size, num_columns, grid_reset_counter,
-                                   navigate_to, load_svg, pending_edit, pending_rename,
-                                   pending_delete, pending_error, app);
-            }
+        let search_results = match current_view {
+            View::Gallery => scan_directory_recursive(&vault_path, FileFilter::Svg).unwrap_or_default(),
+            View::Fonts => scan_directory_recursive(&font_path, FileFilter::Font).unwrap_or_default(),
+            // Collections search within their own assets
+            _ => app.current_items.clone(),
         };
 
-        let search_results = scan_directory_recursive(&root_path, filter).unwrap_or_default();
-
         filtered_items = search_results.into_iter()
             .filter(|item| {
-                let name = match item {
-                    FileSystemItem::SvgFile { name, .. } => name,
-                    FileSystemItem::FontFile { name, .. } => name,
-                    FileSystemItem::Directory { name, .. } => name,
+                let (name, path) = match item {
+                    FileSystemItem::SvgFile { name, path } => (name, path),
+                    FileSystemItem::FontFile { name, path } => (name, path),
+                    FileSystemItem::Directory { name, path } => (name, path),
                 };
-                name.to_lowercase().contains(&query)
+                // Tags, description and custom fields are searchable too
+                name.to_lowercase().contains(&query) || app.metadata.matches(path, &query)
             })
             .collect();
 
         render_grid(ui, &filtered_items, thumbnail_size, num_columns, grid_reset_counter,
-                    navigate_to, load_svg, pending_edit, pending_rename,
-                    pending_delete, pending_error, app);
+                    navigate_to, load_svg, pending, app);
     } else {
         let items_clone: Vec<FileSystemItem> = app.current_items.clone();
         render_grid(ui, &items_clone, thumbnail_size, num_columns, grid_reset_counter,
-                    navigate_to, load_svg, pending_edit, pending_rename,
-                    pending_delete, pending_error, app);
+                    navigate_to, load_svg, pending, app);
     }
 }
 
@@ -78,10 +69,7 @@ fn render_grid(
     grid_reset_counter: usize,
     navigate_to: &mut Option<String>,
     load_svg: &mut Option<PathBuf>,
-    pending_edit: &mut Option<PathBuf>,
-    pending_rename: &mut Option<(PathBuf, String)>,
-    pending_delete: &mut Option<PathBuf>,
-    pending_error: &mut Option<String>,
+    pending: &mut PendingActions,
     app: &mut MyApp,  // Move to end
 ) {
     let mut pending_show_sidebar = false;
@@ -117,10 +105,7 @@ fn render_grid(
                                             ui,
                                             navigate_to,
                                             load_svg,
-                                            pending_edit,
-                                            pending_rename,
-                                            pending_delete,
-                                            pending_error,
+                                            pending,
                                             &mut pending_show_sidebar,
                                         );
 
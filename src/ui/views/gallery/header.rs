@@ -1,4 +1,6 @@
-use crate::models::gui::MyApp;
+use crate::models::collection::CollectionDraft;
+use crate::models::gui::{MyApp, View};
+use crate::utils::collections::CollectionKind;
 use crate::egui::RichText;
 use super::helpers;
 
@@ -65,6 +67,95 @@ pub fn render(
                         app.gallery_options = !app.gallery_options;
                     }
 
+                    ui.menu_button(RichText::new("🛠").size(16.0), |ui| {
+                        if ui.button("Import…").clicked() {
+                            app.open_import(&[]);
+                            ui.close();
+                        }
+
+                        if app.current_view == View::Gallery && ui.button("Export bundle…").clicked() {
+                            app.bundle_current_view();
+                            ui.close();
+                        }
+
+                        if app.current_view == View::Gallery && ui.button("Export for Android / iOS…").clicked() {
+                            let (assets, _) = app.current_view_assets();
+                            app.open_mobile_export(assets);
+                            ui.close();
+                        }
+
+                        if app.current_view == View::Gallery && ui.button("Export PDF / EPS…").clicked() {
+                            let (assets, name) = app.current_view_assets();
+                            app.open_print_export(assets, &name);
+                            ui.close();
+                        }
+
+                        if app.current_view == View::Gallery && ui.button("Generate catalog…").clicked() {
+                            let (assets, name) = app.current_view_assets();
+                            app.open_catalog(assets, &name);
+                            ui.close();
+                        }
+
+                        if ui.button("Theme variants…").clicked() {
+                            app.theme_variants_open = true;
+                            ui.close();
+                        }
+
+                        if ui.button("Contrast report…").clicked() {
+                            app.contrast_report_open = true;
+                            ui.close();
+                        }
+
+                        if ui.button("Format folder…").clicked() {
+                            app.format_folder_open = true;
+                            ui.close();
+                        }
+
+                        if ui.button("Component library…").clicked() {
+                            app.component_library_open = true;
+                            ui.close();
+                        }
+
+                        if ui.button("Lint vault").clicked() {
+                            app.run_lint();
+                            ui.close();
+                        }
+
+                        if ui.button("Sanitize vault…").clicked() {
+                            app.scan_vault_for_active_content();
+                            ui.close();
+                        }
+
+                        if ui.button("Find duplicates…").clicked() {
+                            app.duplicates_open = true;
+                            ui.close();
+                        }
+
+                        if app.git_status.is_some() && ui.button("Commit changes…").clicked() {
+                            app.refresh_git_status();
+                            app.git_commit_selection = app
+                                .git_status
+                                .iter()
+                                .flat_map(|status| status.keys().cloned())
+                                .collect();
+                            app.git_commit_open = true;
+                            ui.close();
+                        }
+                    })
+                    .response
+                    .on_hover_text("Tools");
+
+                    // Switch between a folder and its generated theme variant
+                    if let Some(counterpart) = helpers::variant_counterpart(&app.current_path, root_path) {
+                        let in_variant = counterpart.len() < app.current_path.len();
+                        if ui.button(RichText::new("🌗").size(16.0))
+                            .on_hover_text(if in_variant { "Show source" } else { "Show variant" })
+                            .clicked()
+                        {
+                            *navigate_to = Some(counterpart);
+                        }
+                    }
+
                     let sort_icon = if app.sort_ascending { "⬆" } else { "⬇" };
                     if ui.button(RichText::new(sort_icon).size(16.0))
                         .on_hover_text(if app.sort_ascending { "Sorted A-Z" } else { "Sorted Z-A" })
@@ -83,6 +174,147 @@ pub fn render(
                             app.search_query.clear();
                         }
                     }
+
+                    render_selection(app, ui);
+                });
+            });
+        });
+}
+
+// Number of Ctrl+clicked tiles, click to clear
+fn render_selection(app: &mut MyApp, ui: &mut egui::Ui) {
+    if app.selected_assets.is_empty() {
+        return;
+    }
+    if ui.button(RichText::new(format!("✕ {} selected", app.selected_assets.len())).size(12.0))
+        .on_hover_text("Clear selection")
+        .clicked()
+    {
+        app.selected_assets.clear();
+    }
+}
+
+// Header for collections, favorites and recent assets: no folder navigation, just the list and its controls
+pub fn render_list(app: &mut MyApp, ui: &mut egui::Ui) {
+    let (title, summary, edit) = match app.current_view {
+        View::Collection => {
+            let Some((index, collection)) = app
+                .active_collection
+                .and_then(|index| Some((index, app.collections.collections.get(index)?.clone())))
+            else {
+                return;
+            };
+            let summary = match &collection.kind {
+                CollectionKind::Smart { query } => query.describe(),
+                CollectionKind::Manual { .. } => "Hand-picked".to_string(),
+            };
+            (
+                format!("{} {}", collection.icon(), collection.name),
+                summary,
+                Some(CollectionDraft::edit(index, collection)),
+            )
+        }
+        View::Favorites => ("★ Favorites".to_string(), "Starred assets".to_string(), None),
+        View::Recent => ("🕘 Recent".to_string(), "Copied, opened, exported or edited".to_string(), None),
+        _ => return,
+    };
+
+    egui::Frame::new()
+        .fill(egui::Color32::from_rgb(30, 29, 25))
+        .inner_margin(egui::Margin::same(5))
+        .show(ui, |ui| {
+            ui.style_mut().visuals.widgets.inactive.corner_radius = egui::CornerRadius::same(10);
+            ui.style_mut().visuals.widgets.hovered.corner_radius = egui::CornerRadius::same(10);
+            ui.style_mut().visuals.widgets.active.corner_radius = egui::CornerRadius::same(10);
+
+            ui.style_mut().visuals.widgets.inactive.bg_fill = egui::Color32::TRANSPARENT;
+            ui.style_mut().visuals.widgets.inactive.weak_bg_fill = egui::Color32::TRANSPARENT;
+
+            ui.style_mut().visuals.widgets.hovered.bg_fill = egui::Color32::from_rgba_premultiplied(255, 255, 255, 20);
+            ui.style_mut().visuals.widgets.active.bg_fill = egui::Color32::from_rgba_premultiplied(255, 255, 255, 30);
+
+            ui.horizontal(|ui| {
+                if app.search_active {
+                    let text_edit = ui.add(
+                        egui::TextEdit::singleline(&mut app.search_query)
+                            .hint_text("Search...")
+                            .desired_width(300.0)
+                    );
+                    text_edit.request_focus();
+
+                    if ui.button("x").clicked() {
+                        app.search_active = false;
+                        app.search_query.clear();
+                    }
+                } else {
+                    ui.label(RichText::from(title).size(20.0));
+                    ui.label(
+                        RichText::new(format!("{} · {} assets", summary, app.current_items.len()))
+                            .size(12.0)
+                            .weak(),
+                    );
+                }
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button(RichText::new("⚙").size(16.0))
+                        .on_hover_text("View Options")
+                        .clicked()
+                    {
+                        app.gallery_options = !app.gallery_options;
+                    }
+
+                    if let Some(edit) = edit {
+                        if ui.button(RichText::new("✏").size(16.0))
+                            .on_hover_text("Edit collection")
+                            .clicked()
+                        {
+                            app.collection_edit = Some(edit);
+                        }
+                    }
+
+                    if app.current_view == View::Recent && ui.button(RichText::new("🗑").size(16.0))
+                        .on_hover_text("Clear recent")
+                        .clicked()
+                    {
+                        app.recent.clear();
+                        app.save_config();
+                        app.refresh_list_view();
+                    }
+
+                    if ui.button(RichText::new("⟳").size(16.0))
+                        .on_hover_text("Refresh")
+                        .clicked()
+                    {
+                        app.refresh_list_view();
+                    }
+
+                    if ui.button(RichText::new("📦").size(16.0))
+                        .on_hover_text("Export bundle")
+                        .clicked()
+                    {
+                        app.bundle_current_view();
+                    }
+
+                    let sort_icon = if app.sort_ascending { "⬆" } else { "⬇" };
+                    if ui.button(RichText::new(sort_icon).size(16.0))
+                        .on_hover_text(if app.sort_ascending { "Sorted A-Z" } else { "Sorted Z-A" })
+                        .clicked()
+                    {
+                        app.sort_ascending = !app.sort_ascending;
+                        helpers::sort_items(&mut app.current_items, app.sort_ascending);
+                    }
+
+                    if ui.button(RichText::new("🔍").size(16.0))
+                        .on_hover_text("Search")
+                        .clicked()
+                    {
+                        app.search_active = !app.search_active;
+                        if !app.search_active {
+                            app.search_query.clear();
+                        }
+                    }
+
+                    render_selection(app, ui);
                 });
             });
         });
@@ -1,5 +1,12 @@
 use crate::models::gui::MyApp;
 use crate::models::gui::View;
+use crate::models::compare::CompareSide;
+use crate::models::collection::CollectionDraft;
+use crate::ui::views::compare;
+use crate::utils::collections::{Collection, CollectionKind};
+use crate::utils::history::asset_key;
+use crate::utils::recent::RecentAction;
+use crate::utils::snippets::{self, SnippetKind};
 use std::path::PathBuf;
 
 pub fn get_root_path(app: &MyApp) -> &String {
@@ -40,30 +47,175 @@ pub fn get_parent_path(current: &str) -> String {
         .unwrap_or_else(|| current.to_string())
 }
 
+// Map a folder to its generated theme variant folder and back.
+// Returns None when no counterpart exists on disk.
+pub fn variant_counterpart(current_path: &str, root_path: &str) -> Option<String> {
+    use crate::utils::theme_variants::VARIANT_DIR;
+    use std::path::{Component, Path, PathBuf};
+
+    let current = Path::new(current_path);
+    let relative = current.strip_prefix(root_path).ok()?;
+
+    // Inside a variant tree: drop the variant component to get back to the source
+    let components: Vec<Component> = relative.components().collect();
+    if let Some(pos) = components.iter().position(|c| c.as_os_str() == VARIANT_DIR) {
+        let mut source = PathBuf::from(root_path);
+        for (idx, component) in components.iter().enumerate() {
+            if idx != pos {
+                source.push(component.as_os_str());
+            }
+        }
+        return source.is_dir().then(|| source.to_string_lossy().to_string());
+    }
+
+    // Inside a source tree: find the closest ancestor that has a variant folder
+    let mut ancestor = current;
+    loop {
+        let candidate = ancestor.join(VARIANT_DIR);
+        if candidate.is_dir() {
+            let rest = current.strip_prefix(ancestor).ok()?;
+            let target = candidate.join(rest);
+            return target.is_dir().then(|| target.to_string_lossy().to_string());
+        }
+
+        if ancestor == Path::new(root_path) {
+            return None;
+        }
+        ancestor = ancestor.parent()?;
+    }
+}
+
+// Actions requested by gallery tiles, applied once the grid has been drawn
+#[derive(Default)]
+pub struct PendingActions {
+    pub edit: Option<PathBuf>,
+    pub rename: Option<(PathBuf, String)>,
+    pub delete: Option<PathBuf>,
+    pub compare: Option<(PathBuf, PathBuf)>,
+    pub metadata: Option<PathBuf>,
+    // Add to or remove from a manual collection
+    pub collection_toggle: Option<(usize, PathBuf)>,
+    pub new_collection: Option<PathBuf>,
+    pub favorite_toggle: Option<PathBuf>,
+    pub recent: Option<(PathBuf, RecentAction)>,
+    // Ctrl+click adds to or removes from the multi-selection
+    pub select_toggle: Option<PathBuf>,
+    pub bundle: Option<PathBuf>,
+    pub copy_as: Option<(PathBuf, SnippetKind)>,
+    pub mobile_export: Option<PathBuf>,
+    pub print_export: Option<PathBuf>,
+    pub error: Option<String>,
+}
+
 pub fn apply_pending_actions(
     app: &mut MyApp,
     load_svg: &mut Option<PathBuf>,
-    pending_edit: Option<PathBuf>,
-    pending_rename: Option<(PathBuf, String)>,
-    pending_delete: Option<PathBuf>,
-    pending_error: Option<String>,
+    pending: PendingActions,
 ) {
-    if let Some(path) = pending_edit {
+    if let Some(path) = pending.edit {
         *load_svg = Some(path);
         app.current_view = View::Editor;
     }
 
-    if let Some((path, name)) = pending_rename {
+    if let Some((path, name)) = pending.rename {
         app.rename_file_path = Some(path);
         app.rename_input = name;
         app.rename_just_opened = true;
     }
 
-    if let Some(path) = pending_delete {
+    if let Some(path) = pending.delete {
         app.delete_file_path = Some(path);
     }
 
-    if let Some(error) = pending_error {
+    if let Some((before, after)) = pending.compare {
+        match (CompareSide::from_file(&before), CompareSide::from_file(&after)) {
+            (Ok(before), Ok(after)) => compare::open(app, before, after),
+            (Err(e), _) | (_, Err(e)) => app.set_error_message(format!("Failed to read file: {}", e)),
+        }
+    }
+
+    if let Some(path) = pending.metadata {
+        app.metadata_tag_input.clear();
+        app.metadata_edit_path = Some(path);
+    }
+
+    if let Some((index, path)) = pending.collection_toggle {
+        if app.collections.contains(index, &path) {
+            app.collections.remove_asset(index, &path);
+        } else {
+            app.collections.add_asset(index, &path);
+        }
+        app.save_collections();
+        if app.current_view == View::Collection && app.active_collection == Some(index) {
+            app.refresh_list_view();
+        }
+    }
+
+    if let Some(path) = pending.new_collection {
+        let mut collection = Collection::manual("");
+        collection.kind = CollectionKind::Manual {
+            assets: vec![asset_key(std::path::Path::new(&app.vault_path), &path)],
+        };
+        app.collection_edit = Some(CollectionDraft::new(collection));
+    }
+
+    if let Some(path) = pending.favorite_toggle {
+        app.toggle_favorite(&path);
+    }
+
+    if let Some((path, action)) = pending.recent {
+        app.record_recent(&path, action);
+    }
+
+    if let Some(path) = pending.select_toggle {
+        if !app.selected_assets.remove(&path) {
+            app.selected_assets.insert(path);
+        }
+    }
+
+    // The whole selection when the asset is part of it, otherwise just the asset
+    if let Some(path) = pending.bundle {
+        if app.selected_assets.contains(&path) {
+            app.bundle_current_view();
+        } else {
+            let name = path.file_stem().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            app.open_bundle_export(vec![path], &name);
+        }
+    }
+
+    if let Some(path) = pending.mobile_export {
+        if app.selected_assets.contains(&path) {
+            let (assets, _) = app.current_view_assets();
+            app.open_mobile_export(assets);
+        } else {
+            app.open_mobile_export(vec![path]);
+        }
+    }
+
+    if let Some(path) = pending.print_export {
+        if app.selected_assets.contains(&path) {
+            let (assets, name) = app.current_view_assets();
+            app.open_print_export(assets, &name);
+        } else {
+            let name = path.file_stem().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            app.open_print_export(vec![path], &name);
+        }
+    }
+
+    if let Some((path, kind)) = pending.copy_as {
+        let snippet = std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|source| snippets::generate(kind, &source, &path));
+        match snippet.and_then(|snippet| app.clipboard.set_text(snippet).map_err(|e| e.to_string())) {
+            Ok(_) => {
+                app.record_recent(&path, RecentAction::Copied);
+                app.set_error_message(format!("✅ Copied as {}", kind.label()));
+            }
+            Err(e) => app.set_error_message(format!("Failed to copy: {}", e)),
+        }
+    }
+
+    if let Some(error) = pending.error {
         app.set_error_message(error);
     }
 
@@ -1,8 +1,17 @@
 use crate::models::file_items::FileSystemItem;
-use crate::models::gui::MyApp;
+use crate::models::gui::{MyApp, View};
+use crate::utils::collections::Collection;
+use crate::ui::popups::git_commit::status_color;
+use crate::ui::problems::severity_color;
+use crate::utils::lint;
+use crate::ui::sidebar_right::metadata::{render_tag_chip, TILE_TAG_LIMIT};
 use crate::utils::file_actions;
+use crate::utils::recent::{self, RecentAction};
+use crate::utils::snippets::SnippetKind;
+use crate::utils::svg_validator::Severity;
 use crate::egui::RichText;
 use std::path::PathBuf;
+use super::helpers::PendingActions;
 
 pub fn render(
     app: &MyApp,  // Changed back to immutable
@@ -10,15 +19,12 @@ pub fn render(
     ui: &mut egui::Ui,
     navigate_to: &mut Option<String>,
     load_svg: &mut Option<PathBuf>,
-    pending_edit: &mut Option<PathBuf>,
-    pending_rename: &mut Option<(PathBuf, String)>,
-    pending_delete: &mut Option<PathBuf>,
-    pending_error: &mut Option<String>,
+    pending: &mut PendingActions,
     pending_show_sidebar: &mut bool,  // Add this parameter
 ) {
     match item {
         FileSystemItem::Directory { name, path } => {
-            render_directory(app, ui, name, path, navigate_to);
+            render_directory(app, ui, name, path, navigate_to, pending);
         }
         FileSystemItem::SvgFile { name, path } => {
             render_svg(
@@ -27,10 +33,7 @@ pub fn render(
                 name,
                 path,
                 load_svg,
-                pending_edit,
-                pending_rename,
-                pending_delete,
-                pending_error,
+                pending,
                 pending_show_sidebar,
             );
         }
@@ -40,10 +43,7 @@ pub fn render(
                 ui,
                 name,
                 path,
-                pending_edit,
-                pending_rename,
-                pending_delete,
-                pending_error,
+                pending,
             );
         }
     }
@@ -55,6 +55,7 @@ fn render_directory(
     name: &str,
     path: &PathBuf,
     navigate_to: &mut Option<String>,
+    pending: &mut PendingActions,
 ) {
     let thumbnail_size = app.get_thumbnail_size();
 
@@ -72,7 +73,15 @@ fn render_directory(
             *navigate_to = Some(path.to_string_lossy().to_string());
         }
 
+        button.context_menu(|ui| {
+            if ui.button("Metadata…").clicked() {
+                pending.metadata = Some(path.clone());
+                ui.close();
+            }
+        });
+
         ui.label(RichText::from(name).size(11.0));
+        render_tags(app, ui, path);
     });
 }
 
@@ -82,10 +91,7 @@ fn render_svg(
     name: &str,
     path: &PathBuf,
     load_svg: &mut Option<PathBuf>,
-    pending_edit: &mut Option<PathBuf>,
-    pending_rename: &mut Option<(PathBuf, String)>,
-    pending_delete: &mut Option<PathBuf>,
-    pending_error: &mut Option<String>,
+    pending: &mut PendingActions,
     pending_show_sidebar: &mut bool,  // Add this parameter
 ) {
     let thumbnail_size = app.get_thumbnail_size();
@@ -108,24 +114,117 @@ fn render_svg(
             );
         }
 
-        if button.clicked() {
-            *pending_show_sidebar = true;  // Set flag instead of mutating app
-            *load_svg = Some(path.clone());
+        if app.selected_assets.contains(path) {
+            ui.painter().rect_stroke(
+                button.rect.expand(5.0),
+                10.0,
+                egui::Stroke::new(2.0, egui::Color32::from_rgb(90, 160, 255)),
+                egui::StrokeKind::Outside,
+            );
+        }
+
+        // Git status badge in the top right corner
+        if let Some(status) = app.git_status.as_ref().and_then(|status| status.get(path)) {
+            let center = button.rect.right_top() + egui::vec2(-8.0, 8.0);
+            ui.painter().circle_filled(center, 7.0, status_color(*status));
+            ui.painter().text(
+                center,
+                egui::Align2::CENTER_CENTER,
+                status.badge(),
+                egui::FontId::monospace(9.0),
+                egui::Color32::BLACK,
+            );
+            button.clone().on_hover_text(status.label());
+        }
+
+        // Lint problem count in the bottom right corner, red when any is an error
+        if let Some(problems) = app.lint_results.as_ref().and_then(|results| results.get(path)) {
+            let center = button.rect.right_bottom() + egui::vec2(-9.0, -9.0);
+            let severity = if lint::error_count(problems) > 0 { Severity::Error } else { Severity::Warning };
+            ui.painter().circle_filled(center, 8.0, severity_color(severity));
+            ui.painter().text(
+                center,
+                egui::Align2::CENTER_CENTER,
+                problems.len().min(99).to_string(),
+                egui::FontId::monospace(9.0),
+                egui::Color32::BLACK,
+            );
+            let summary: Vec<String> = problems.iter().map(|problem| problem.message.clone()).collect();
+            button.clone().on_hover_text(summary.join("\n"));
+        }
+
+        // Active content warning in the bottom left corner
+        if let Some((_, found)) = app.active_content.get(path).filter(|(_, found)| !found.is_empty()) {
+            let center = button.rect.left_bottom() + egui::vec2(9.0, -9.0);
+            ui.painter().circle_filled(center, 8.0, severity_color(Severity::Warning));
+            ui.painter().text(
+                center,
+                egui::Align2::CENTER_CENTER,
+                "!",
+                egui::FontId::monospace(10.0),
+                egui::Color32::BLACK,
+            );
+            button.clone().on_hover_text(format!("Active content:\n{}", found.join("\n")));
+        }
+
+        // Favorite star in the top left corner, shown on hover or when starred
+        let favorite = app.is_favorite(path);
+        if favorite || button.hovered() {
+            let star_rect = egui::Rect::from_center_size(button.rect.left_top() + egui::vec2(10.0, 10.0), egui::vec2(18.0, 18.0));
+            let star = ui
+                .interact(star_rect, ui.id().with(("favorite", path)), egui::Sense::click())
+                .on_hover_text(if favorite { "Remove from favorites" } else { "Add to favorites" });
+            let color = if favorite {
+                egui::Color32::from_rgb(255, 200, 60)
+            } else if star.hovered() {
+                egui::Color32::WHITE
+            } else {
+                egui::Color32::from_gray(140)
+            };
+            ui.painter().text(
+                star_rect.center(),
+                egui::Align2::CENTER_CENTER,
+                if favorite { "★" } else { "☆" },
+                egui::FontId::proportional(14.0),
+                color,
+            );
+            if star.clicked() {
+                pending.favorite_toggle = Some(path.clone());
+            }
+        }
+
+        if button.clicked() && pending.favorite_toggle.is_none() {
+            if ui.input(|i| i.modifiers.command) {
+                pending.select_toggle = Some(path.clone());
+            } else {
+                *pending_show_sidebar = true;  // Set flag instead of mutating app
+                *load_svg = Some(path.clone());
+            }
         }
 
         show_context_menu(
+            app,
             button,
-            ui,
             path,
             name,
             true,
-            pending_edit,
-            pending_rename,
-            pending_delete,
-            pending_error,
+            pending,
         );
 
         ui.label(RichText::from(name).size(11.0));
+
+        // When and how the asset was last used
+        if app.current_view == View::Recent {
+            if let Some(entry) = app.recent.iter().find(|entry| entry.path == *path) {
+                ui.label(
+                    RichText::new(format!("{} {}", entry.action.label(), recent::format_age(entry.timestamp)))
+                        .size(9.0)
+                        .weak(),
+                );
+            }
+        }
+
+        render_tags(app, ui, path);
     });
 }
 
@@ -134,10 +233,7 @@ fn render_font(
     ui: &mut egui::Ui,
     name: &str,
     path: &PathBuf,
-    pending_edit: &mut Option<PathBuf>,
-    pending_rename: &mut Option<(PathBuf, String)>,
-    pending_delete: &mut Option<PathBuf>,
-    pending_error: &mut Option<String>,
+    pending: &mut PendingActions,
 ) {
     let thumbnail_size = app.get_thumbnail_size();
 
@@ -160,40 +256,151 @@ fn render_font(
         );
 
         show_context_menu(
+            app,
             button,
-            ui,
             path,
             name,
             false,
-            pending_edit,
-            pending_rename,
-            pending_delete,
-            pending_error,
+            pending,
         );
 
         ui.label(RichText::from(name).size(11.0));
+        render_tags(app, ui, path);
     });
 }
 
+// Toggle membership of the manual collections
+fn render_collection_menu(app: &MyApp, ui: &mut egui::Ui, path: &PathBuf, pending: &mut PendingActions) {
+    let manual: Vec<(usize, &Collection)> = app
+        .collections
+        .collections
+        .iter()
+        .enumerate()
+        .filter(|(_, collection)| !collection.is_smart())
+        .collect();
+
+    // Quick removal while browsing the collection itself
+    if let Some(active) = app.active_collection.filter(|_| app.current_view == View::Collection) {
+        if app.collections.contains(active, path) && ui.button("Remove from collection").clicked() {
+            pending.collection_toggle = Some((active, path.clone()));
+            ui.close();
+        }
+    }
+
+    ui.menu_button("Collections", |ui| {
+        if manual.is_empty() {
+            ui.label(RichText::new("No manual collections yet").weak());
+        }
+
+        for (index, collection) in manual {
+            let mut member = app.collections.contains(index, path);
+            if ui.checkbox(&mut member, &collection.name).clicked() {
+                pending.collection_toggle = Some((index, path.clone()));
+                ui.close();
+            }
+        }
+
+        ui.separator();
+
+        if ui.button("New collection with this asset…").clicked() {
+            pending.new_collection = Some(path.clone());
+            ui.close();
+        }
+    });
+}
+
+// Tag chips under the tile name
+fn render_tags(app: &MyApp, ui: &mut egui::Ui, path: &PathBuf) {
+    let Some(metadata) = app.metadata.get(path) else {
+        return;
+    };
+    if metadata.tags.is_empty() {
+        return;
+    }
+
+    ui.horizontal(|ui| {
+        ui.spacing_mut().item_spacing.x = 3.0;
+        for tag in metadata.tags.iter().take(TILE_TAG_LIMIT) {
+            render_tag_chip(ui, tag, 9.0);
+        }
+        if metadata.tags.len() > TILE_TAG_LIMIT {
+            ui.label(RichText::new(format!("+{}", metadata.tags.len() - TILE_TAG_LIMIT)).size(9.0).weak());
+        }
+    })
+    .response
+    .on_hover_text(metadata.tags.join(", "));
+}
+
 fn show_context_menu(
+    app: &MyApp,
     response: egui::Response,
-    _ui: &mut egui::Ui,
     path: &PathBuf,
     name: &str,
     is_svg: bool,
-    pending_edit: &mut Option<PathBuf>,
-    pending_rename: &mut Option<(PathBuf, String)>,
-    pending_delete: &mut Option<PathBuf>,
-    pending_error: &mut Option<String>,
+    pending: &mut PendingActions,
 ) {
+    // Offer to compare against the icon currently open in the sidebar
+    let compare_with = app
+        .selected_svg
+        .as_ref()
+        .filter(|selected| is_svg && *selected != path);
+
     response.context_menu(|ui| {
         if is_svg && ui.button("Edit").clicked() {
-            *pending_edit = Some(path.clone());
+            pending.edit = Some(path.clone());
+            ui.close();
+        }
+
+        if let Some(selected) = compare_with {
+            if ui.button("Compare with selected").clicked() {
+                pending.compare = Some((selected.clone(), path.clone()));
+                ui.close();
+            }
+        }
+
+        if is_svg {
+            let favorite = app.is_favorite(path);
+            if ui.button(if favorite { "Remove from favorites" } else { "Add to favorites" }).clicked() {
+                pending.favorite_toggle = Some(path.clone());
+                ui.close();
+            }
+
+            render_collection_menu(app, ui, path, pending);
+
+            let selected = app.selected_assets.contains(path);
+            if ui.button(if selected { "Deselect" } else { "Select" }).clicked() {
+                pending.select_toggle = Some(path.clone());
+                ui.close();
+            }
+
+            let label = if selected && app.selected_assets.len() > 1 {
+                format!("Export {} selected as bundle…", app.selected_assets.len())
+            } else {
+                "Export as bundle…".to_string()
+            };
+            if ui.button(label).clicked() {
+                pending.bundle = Some(path.clone());
+                ui.close();
+            }
+
+            if ui.button("Export for Android / iOS…").clicked() {
+                pending.mobile_export = Some(path.clone());
+                ui.close();
+            }
+
+            if ui.button("Export as PDF / EPS…").clicked() {
+                pending.print_export = Some(path.clone());
+                ui.close();
+            }
+        }
+
+        if ui.button("Metadata…").clicked() {
+            pending.metadata = Some(path.clone());
             ui.close();
         }
 
         if ui.button("Rename").clicked() {
-            *pending_rename = Some((path.clone(), name.to_string()));
+            pending.rename = Some((path.clone(), name.to_string()));
             ui.close();
         }
 
@@ -201,12 +408,26 @@ fn show_context_menu(
 
         if ui.button("Copy").clicked() {
             match file_actions::copy_file_to_clipboard(path) {
-                Ok(_) => *pending_error = Some("✅ File copied to clipboard".to_string()),
-                Err(e) => *pending_error = Some(format!("Failed to copy file: {}", e)),
+                Ok(_) => {
+                    pending.recent = Some((path.clone(), RecentAction::Copied));
+                    pending.error = Some("✅ File copied to clipboard".to_string());
+                }
+                Err(e) => pending.error = Some(format!("Failed to copy file: {}", e)),
             }
             ui.close();
         }
 
+        if is_svg {
+            ui.menu_button("Copy as…", |ui| {
+                for kind in SnippetKind::ALL {
+                    if ui.button(kind.label()).clicked() {
+                        pending.copy_as = Some((path.clone(), kind));
+                        ui.close();
+                    }
+                }
+            });
+        }
+
         if ui.button("Open file").clicked() {
             file_actions::reveal_in_explorer(path);
             ui.close();
@@ -215,7 +436,7 @@ fn show_context_menu(
         ui.separator();
 
         if ui.button("Delete").clicked() {
-            *pending_delete = Some(path.clone());
+            pending.delete = Some(path.clone());
             ui.close();
         }
     });
@@ -5,15 +5,13 @@ pub mod helpers;
 
 use crate::models::gui::MyApp;
 use std::path::PathBuf;
+use helpers::PendingActions;
 
 // Render only content (without header) - for when header is rendered separately
 pub fn render_content(app: &mut MyApp, ui: &mut egui::Ui) -> (Option<String>, Option<PathBuf>) {
     let mut navigate_to: Option<String> = None;
     let mut load_svg: Option<PathBuf> = None;
-    let mut pending_error: Option<String> = None;
-    let mut pending_edit: Option<PathBuf> = None;
-    let mut pending_rename: Option<(PathBuf, String)> = None;
-    let mut pending_delete: Option<PathBuf> = None;
+    let mut pending = PendingActions::default();
 
     // Render the file grid only (no header)
     grid::render(
@@ -21,21 +19,11 @@ pub fn render_content(app: &mut MyApp, ui: &mut egui::Ui) -> (Option<String>, Op
         ui,
         &mut navigate_to,
         &mut load_svg,
-        &mut pending_edit,
-        &mut pending_rename,
-        &mut pending_delete,
-        &mut pending_error,
+        &mut pending,
     );
 
     // Apply pending actions
-    helpers::apply_pending_actions(
-        app,
-        &mut load_svg,
-        pending_edit,
-        pending_rename,
-        pending_delete,
-        pending_error,
-    );
+    helpers::apply_pending_actions(app, &mut load_svg, pending);
 
     (navigate_to, load_svg)
 }
@@ -59,6 +59,10 @@ pub fn render(_app: &mut MyApp, ui: &mut egui::Ui) {
                             ui.label("Settings");
                             ui.label(RichText::new("Ctrl + ,").monospace());
                             ui.end_row();
+
+                            ui.label("Select several assets");
+                            ui.label(RichText::new("Ctrl + Click").monospace());
+                            ui.end_row();
                         });
                 });
         });
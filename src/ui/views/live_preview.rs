@@ -0,0 +1,163 @@
+use crate::models::gui::MyApp;
+use crate::utils::svg_render;
+use egui::{Color32, RichText, Sense};
+use std::hash::{Hash, Hasher};
+
+const MIN_ZOOM: f32 = 0.1;
+const MAX_ZOOM: f32 = 32.0;
+const CHECKER_SIZE: f32 = 10.0;
+
+pub fn render(app: &mut MyApp, ui: &mut egui::Ui) {
+    update_tree(app);
+
+    render_toolbar(app, ui);
+    ui.add_space(5.0);
+
+    let (rect, response) = ui.allocate_exact_size(ui.available_size(), Sense::click_and_drag());
+    let painter = ui.painter_at(rect);
+    paint_checkerboard(&painter, rect);
+
+    let preview = &mut app.live_preview;
+    let tree_size = match &preview.tree {
+        Some(Ok(tree)) => egui::vec2(tree.size().width(), tree.size().height()),
+        Some(Err(e)) => {
+            painter.text(
+                rect.center(),
+                egui::Align2::CENTER_CENTER,
+                format!("⛔ {}", e),
+                egui::FontId::proportional(13.0),
+                Color32::from_rgb(255, 100, 100),
+            );
+            return;
+        }
+        None => return,
+    };
+
+    // Zoom with the scroll wheel while hovering, pan by dragging, double-click to fit
+    let fit_zoom = (rect.width() / tree_size.x).min(rect.height() / tree_size.y) * 0.9;
+    let mut zoom = preview.zoom.unwrap_or(fit_zoom);
+
+    if response.hovered() {
+        let scroll = ui.input(|i| i.smooth_scroll_delta.y);
+        if scroll != 0.0 {
+            zoom = (zoom * (1.0 + scroll * 0.002)).clamp(MIN_ZOOM, MAX_ZOOM);
+            preview.zoom = Some(zoom);
+        }
+    }
+    if response.dragged() {
+        preview.pan += response.drag_delta();
+    }
+    if response.double_clicked() {
+        preview.zoom = None;
+        preview.pan = egui::Vec2::ZERO;
+        zoom = fit_zoom;
+    }
+
+    // Rasterize at screen resolution so zooming stays sharp
+    let scale = zoom * ui.ctx().pixels_per_point();
+    let needs_render = preview
+        .texture
+        .as_ref()
+        .map(|(rendered_scale, _)| (rendered_scale - scale).abs() > f32::EPSILON)
+        .unwrap_or(true);
+
+    if needs_render {
+        if let Some(Ok(tree)) = &preview.tree {
+            match svg_render::rasterize_tree(tree, scale) {
+                Ok(pixmap) => {
+                    let texture = ui.ctx().load_texture(
+                        "live_preview",
+                        svg_render::to_color_image(&pixmap),
+                        egui::TextureOptions::LINEAR,
+                    );
+                    preview.texture = Some((scale, texture));
+                }
+                Err(e) => preview.tree = Some(Err(e)),
+            }
+        }
+    }
+
+    if let Some((_, texture)) = &preview.texture {
+        let image_rect = egui::Rect::from_center_size(rect.center() + preview.pan, tree_size * zoom);
+        painter.image(
+            texture.id(),
+            image_rect,
+            egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+            Color32::WHITE,
+        );
+    }
+}
+
+// Re-parse the buffer whenever it changed since the last frame
+fn update_tree(app: &mut MyApp) {
+    let content = app
+        .active_document
+        .and_then(|idx| app.documents.get(idx))
+        .map(|doc| doc.content.as_str())
+        .unwrap_or_default();
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    content.hash(&mut hasher);
+    let hash = hasher.finish();
+
+    let preview = &mut app.live_preview;
+    if preview.tree.is_some() && preview.source_hash == hash {
+        return;
+    }
+
+    preview.source_hash = hash;
+    preview.tree = Some(svg_render::parse_tree(content));
+    preview.texture = None;
+}
+
+fn render_toolbar(app: &mut MyApp, ui: &mut egui::Ui) {
+    let preview = &mut app.live_preview;
+
+    ui.horizontal(|ui| {
+        ui.label(RichText::new("Preview").size(16.0).strong());
+
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            if ui.button("Fit").clicked() {
+                preview.zoom = None;
+                preview.pan = egui::Vec2::ZERO;
+            }
+
+            if ui.button("1:1").clicked() {
+                preview.zoom = Some(1.0);
+                preview.pan = egui::Vec2::ZERO;
+            }
+
+            if ui.button("+").clicked() {
+                preview.zoom = Some((preview.zoom.unwrap_or(1.0) * 1.25).min(MAX_ZOOM));
+            }
+
+            let label = match preview.zoom {
+                Some(zoom) => format!("{:.0}%", zoom * 100.0),
+                None => "Fit".to_string(),
+            };
+            ui.label(RichText::new(label).monospace());
+
+            if ui.button("-").clicked() {
+                preview.zoom = Some((preview.zoom.unwrap_or(1.0) / 1.25).max(MIN_ZOOM));
+            }
+        });
+    });
+}
+
+pub(crate) fn paint_checkerboard(painter: &egui::Painter, rect: egui::Rect) {
+    painter.rect_filled(rect, 0.0, Color32::from_gray(60));
+
+    let columns = (rect.width() / CHECKER_SIZE).ceil() as usize;
+    let rows = (rect.height() / CHECKER_SIZE).ceil() as usize;
+
+    for row in 0..rows {
+        for column in 0..columns {
+            if (row + column) % 2 == 0 {
+                continue;
+            }
+            let min = rect.min + egui::vec2(column as f32 * CHECKER_SIZE, row as f32 * CHECKER_SIZE);
+            let square = egui::Rect::from_min_size(min, egui::vec2(CHECKER_SIZE, CHECKER_SIZE));
+            painter.rect_filled(square, 0.0, Color32::from_gray(90));
+        }
+    }
+}
@@ -1,4 +1,6 @@
 pub mod code_editor;
 pub mod settings;
 pub mod help;
-pub mod gallery;
\ No newline at end of file
+pub mod gallery;
+pub mod live_preview;
+pub mod compare;
\ No newline at end of file
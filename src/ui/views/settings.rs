@@ -1,57 +1,330 @@
 use crate::models::gui::MyApp;
+use crate::ui::popups::theme_variants::render_color_field;
+use crate::utils::collections::CollectionStore;
+use crate::utils::lint::LintRules;
+use crate::utils::metadata::MetadataStore;
+use crate::utils::server::ServerOptions;
+use crate::utils::svg_format::{AttributeOrder, FormatOptions, QuoteStyle};
 use egui::{RichText, Align};
 
 pub fn render(app: &mut MyApp, ui: &mut egui::Ui) {
     // Track which action to take after rendering
     let mut svg_save_clicked = false;
     let mut font_save_clicked = false;
+    let mut backgrounds_save_clicked = false;
+    let mut format_save_clicked = false;
+    let mut metadata_changed = false;
+    let mut server_action = ServerAction::None;
 
-    // Add padding and center content
-    egui::Frame::new()
-        .inner_margin(egui::Margin::symmetric(40, 30))
+    egui::ScrollArea::vertical()
+        .auto_shrink([false, false])
         .show(ui, |ui| {
-            // Header
-            ui.vertical_centered(|ui| {
-                ui.heading(RichText::new("Settings").size(32.0).strong());
-            });
+            // Add padding and center content
+            egui::Frame::new()
+                .inner_margin(egui::Margin::symmetric(40, 30))
+                .show(ui, |ui| {
+                    // Header
+                    ui.vertical_centered(|ui| {
+                        ui.heading(RichText::new("Settings").size(32.0).strong());
+                    });
 
-            ui.add_space(20.0);
-            ui.separator();
-            ui.add_space(30.0);
+                    ui.add_space(20.0);
+                    ui.separator();
+                    ui.add_space(30.0);
 
-            // SVG Path Section
-            svg_save_clicked = render_path_section(
-                ui,
-                "SVG Vault Path",
-                "Choose the folder where your SVG files are stored",
-                &mut app.vault_path_input,
-            );
+                    // SVG Path Section
+                    svg_save_clicked = render_path_section(
+                        ui,
+                        "SVG Vault Path",
+                        "Choose the folder where your SVG files are stored",
+                        &mut app.vault_path_input,
+                    );
 
-            ui.add_space(40.0);
+                    ui.add_space(40.0);
 
-            // Font Path Section
-            font_save_clicked = render_path_section(
-                ui,
-                "Font Vault Path",
-                "Choose the folder where your font files are stored",
-                &mut app.current_font_input,
-            );
+                    // Font Path Section
+                    font_save_clicked = render_path_section(
+                        ui,
+                        "Font Vault Path",
+                        "Choose the folder where your font files are stored",
+                        &mut app.current_font_input,
+                    );
+
+                    ui.add_space(40.0);
+
+                    // Contrast Backgrounds Section
+                    backgrounds_save_clicked = render_backgrounds_section(ui, &mut app.contrast_backgrounds);
+
+                    ui.add_space(40.0);
+
+                    // SVG Formatting Section
+                    format_save_clicked = render_format_section(ui, &mut app.format_options);
+
+                    ui.add_space(40.0);
 
-            ui.add_space(40.0);
+                    // Metadata Section
+                    metadata_changed = render_metadata_section(ui, &mut app.mirror_metadata);
+
+                    ui.add_space(40.0);
+
+                    // Local Server Section
+                    let running = app.server.as_ref().map(|server| server.address.clone());
+                    server_action = render_server_section(ui, &mut app.server_options, running.as_deref());
+
+                    ui.add_space(40.0);
+                });
         });
 
     // Execute actions after rendering
     if svg_save_clicked {
         app.vault_path = app.vault_path_input.clone();
+        app.metadata = MetadataStore::load(std::path::Path::new(&app.vault_path));
+        app.collections = CollectionStore::load(std::path::Path::new(&app.vault_path));
+        app.lint_rules = LintRules::load(std::path::Path::new(&app.vault_path));
+        app.lint_results = None;
+        app.active_collection = None;
+        app.history_cache = None;
         app.save_config();
         app.current_view = crate::models::gui::View::Gallery;
         app.refresh_directory();
+        // Keep serving, from the new vault
+        if app.server.is_some() {
+            app.start_server();
+        }
     }
 
     if font_save_clicked {
         app.font_path = app.current_font_input.clone();
         app.save_config();
     }
+
+    if metadata_changed {
+        app.save_config();
+    }
+
+    if backgrounds_save_clicked {
+        app.save_config();
+        app.contrast_report = None;
+    }
+
+    if format_save_clicked {
+        app.save_config();
+    }
+
+    match server_action {
+        ServerAction::None => {}
+        ServerAction::Changed => app.save_config(),
+        ServerAction::Start => {
+            app.save_config();
+            app.start_server();
+        }
+        ServerAction::Stop => {
+            app.server = None;
+            app.set_error_message("Server stopped".to_string());
+        }
+    }
+}
+
+enum ServerAction {
+    None,
+    Changed,
+    Start,
+    Stop,
+}
+
+fn render_server_section(ui: &mut egui::Ui, options: &mut ServerOptions, running: Option<&str>) -> ServerAction {
+    let mut action = ServerAction::None;
+
+    egui::Frame::new()
+        .fill(egui::Color32::from_rgb(35, 39, 42))
+        .inner_margin(egui::Margin::same(20))
+        .corner_radius(8.0)
+        .show(ui, |ui| {
+            ui.label(RichText::new("Local Server").size(20.0).strong());
+            ui.add_space(5.0);
+
+            ui.label(
+                RichText::new("Serve the vault to dev servers and Storybook on this machine, e.g. /icons/arrow.svg?color=ff0000&size=24")
+                    .size(14.0)
+                    .color(egui::Color32::from_rgb(150, 150, 150))
+            );
+
+            ui.add_space(10.0);
+
+            ui.horizontal(|ui| {
+                ui.label("Port");
+                ui.add_enabled(running.is_none(), egui::DragValue::new(&mut options.port).range(1024..=65535));
+
+                ui.add_space(10.0);
+                match running {
+                    Some(address) => {
+                        if ui.button("Stop").clicked() {
+                            action = ServerAction::Stop;
+                        }
+                        ui.hyperlink_to(address, format!("{}/api/icons", address));
+                    }
+                    None => {
+                        if ui.button("Start").clicked() {
+                            action = ServerAction::Start;
+                        }
+                    }
+                }
+            });
+
+            if ui.checkbox(&mut options.autostart, "Start when the app opens").changed() {
+                action = ServerAction::Changed;
+            }
+        });
+
+    action
+}
+
+fn render_backgrounds_section(ui: &mut egui::Ui, backgrounds: &mut Vec<String>) -> bool {
+    let mut save_clicked = false;
+    let mut remove_row: Option<usize> = None;
+
+    egui::Frame::new()
+        .fill(egui::Color32::from_rgb(35, 39, 42))
+        .inner_margin(egui::Margin::same(20))
+        .corner_radius(8.0)
+        .show(ui, |ui| {
+            ui.label(RichText::new("Contrast Backgrounds").size(20.0).strong());
+            ui.add_space(5.0);
+
+            ui.label(
+                RichText::new("Icon colors are checked against each of these backgrounds")
+                    .size(14.0)
+                    .color(egui::Color32::from_rgb(150, 150, 150))
+            );
+
+            ui.add_space(10.0);
+
+            for (idx, background) in backgrounds.iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                    render_color_field(ui, background);
+                    if ui.small_button("✖").on_hover_text("Remove").clicked() {
+                        remove_row = Some(idx);
+                    }
+                });
+            }
+
+            ui.add_space(5.0);
+
+            ui.horizontal(|ui| {
+                if ui.button("+ Add background").clicked() {
+                    backgrounds.push("#808080".to_string());
+                }
+
+                ui.with_layout(egui::Layout::right_to_left(Align::Center), |ui| {
+                    if ui.add_sized(
+                        [100.0, 35.0],
+                        egui::Button::new(RichText::new("Save").size(16.0))
+                    ).clicked() {
+                        save_clicked = true;
+                    }
+                });
+            });
+        });
+
+    if let Some(idx) = remove_row {
+        backgrounds.remove(idx);
+    }
+
+    save_clicked
+}
+
+fn render_metadata_section(ui: &mut egui::Ui, mirror_metadata: &mut bool) -> bool {
+    let mut changed = false;
+
+    egui::Frame::new()
+        .fill(egui::Color32::from_rgb(35, 39, 42))
+        .inner_margin(egui::Margin::same(20))
+        .corner_radius(8.0)
+        .show(ui, |ui| {
+            ui.label(RichText::new("Metadata").size(20.0).strong());
+            ui.add_space(5.0);
+
+            ui.label(
+                RichText::new("Tags and details live in the vault's .asset_manager folder")
+                    .size(14.0)
+                    .color(egui::Color32::from_rgb(150, 150, 150))
+            );
+
+            ui.add_space(10.0);
+
+            changed = ui
+                .checkbox(mirror_metadata, "Mirror tags, description, author and license into the SVG's <metadata>")
+                .changed();
+        });
+
+    changed
+}
+
+fn render_format_section(ui: &mut egui::Ui, options: &mut FormatOptions) -> bool {
+    let mut save_clicked = false;
+
+    egui::Frame::new()
+        .fill(egui::Color32::from_rgb(35, 39, 42))
+        .inner_margin(egui::Margin::same(20))
+        .corner_radius(8.0)
+        .show(ui, |ui| {
+            ui.label(RichText::new("SVG Formatting").size(20.0).strong());
+            ui.add_space(5.0);
+
+            ui.label(
+                RichText::new("Used by the editor's Format menu and the Format folder tool")
+                    .size(14.0)
+                    .color(egui::Color32::from_rgb(150, 150, 150))
+            );
+
+            ui.add_space(10.0);
+
+            egui::Grid::new("format_options_grid")
+                .num_columns(2)
+                .spacing([20.0, 8.0])
+                .show(ui, |ui| {
+                    ui.label("Indentation");
+                    ui.horizontal(|ui| {
+                        ui.selectable_value(&mut options.use_tabs, false, "Spaces");
+                        ui.selectable_value(&mut options.use_tabs, true, "Tabs");
+                        if !options.use_tabs {
+                            ui.add(egui::DragValue::new(&mut options.indent).range(1..=8));
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label("Attribute order");
+                    ui.horizontal(|ui| {
+                        ui.selectable_value(&mut options.attribute_order, AttributeOrder::Source, "As written");
+                        ui.selectable_value(&mut options.attribute_order, AttributeOrder::Alphabetical, "Alphabetical");
+                    });
+                    ui.end_row();
+
+                    ui.label("Quotes");
+                    ui.horizontal(|ui| {
+                        ui.selectable_value(&mut options.quote_style, QuoteStyle::Double, "Double");
+                        ui.selectable_value(&mut options.quote_style, QuoteStyle::Single, "Single");
+                    });
+                    ui.end_row();
+
+                    ui.label("Number precision");
+                    ui.add(egui::DragValue::new(&mut options.precision).range(0..=8).suffix(" decimals"));
+                    ui.end_row();
+                });
+
+            ui.add_space(5.0);
+
+            ui.with_layout(egui::Layout::right_to_left(Align::Center), |ui| {
+                if ui.add_sized(
+                    [100.0, 35.0],
+                    egui::Button::new(RichText::new("Save").size(16.0))
+                ).clicked() {
+                    save_clicked = true;
+                }
+            });
+        });
+
+    save_clicked
 }
 
 fn render_path_section(
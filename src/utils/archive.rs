@@ -0,0 +1,167 @@
+use crate::utils::import::{self, AssetKind};
+use flate2::read::GzDecoder;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+// Entries above this are reported instead of being read into memory
+const MAX_ENTRY_SIZE: u64 = 16 * 1024 * 1024;
+const UNSAFE_PATH: &str = "Unsafe path";
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ArchiveFormat {
+    Zip,
+    TarGz,
+}
+
+pub fn archive_format(path: &Path) -> Option<ArchiveFormat> {
+    let name = path.file_name()?.to_string_lossy().to_lowercase();
+    if name.ends_with(".zip") {
+        Some(ArchiveFormat::Zip)
+    } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        Some(ArchiveFormat::TarGz)
+    } else {
+        None
+    }
+}
+
+// An SVG or font inside an archive
+pub struct ArchiveEntry {
+    // Relative path inside the archive
+    pub path: PathBuf,
+    pub kind: AssetKind,
+    // Shared so previews can hand it to the image loader every frame
+    pub data: Arc<[u8]>,
+    // Why the entry can't be imported, e.g. broken markup or not UTF-8
+    pub problem: Option<String>,
+}
+
+// Paths that would escape the destination folder are not accepted
+fn safe_path(name: &str) -> Option<PathBuf> {
+    let path = PathBuf::from(name);
+    path.components()
+        .all(|component| matches!(component, Component::Normal(_)))
+        .then_some(path)
+}
+
+// Skip folders, hidden files and the __MACOSX resource forks zipped on macOS
+fn is_hidden(path: &Path) -> bool {
+    path.components().any(|component| {
+        let Component::Normal(name) = component else {
+            return false;
+        };
+        let name = name.to_string_lossy();
+        name.starts_with('.') || name == "__MACOSX"
+    })
+}
+
+// Listed with the reason, so the wizard shows what was left out
+fn unsafe_entry(name: &str) -> Option<ArchiveEntry> {
+    let path = PathBuf::from(name);
+    if is_hidden(&path) {
+        return None;
+    }
+    let kind = import::asset_kind(&path)?;
+    Some(ArchiveEntry { path, kind, data: Arc::from(Vec::new()), problem: Some(UNSAFE_PATH.to_string()) })
+}
+
+fn read_entry(path: PathBuf, size: u64, reader: impl Read) -> Option<ArchiveEntry> {
+    if is_hidden(&path) {
+        return None;
+    }
+    let kind = import::asset_kind(&path)?;
+
+    if size > MAX_ENTRY_SIZE {
+        return Some(ArchiveEntry {
+            path,
+            kind,
+            data: Arc::from(Vec::new()),
+            problem: Some(format!("Larger than {} MB", MAX_ENTRY_SIZE / 1024 / 1024)),
+        });
+    }
+
+    let mut data = Vec::new();
+    let problem = match reader.take(MAX_ENTRY_SIZE).read_to_end(&mut data) {
+        Ok(_) => import::check_asset(kind, &data).err(),
+        Err(e) => Some(e.to_string()),
+    };
+
+    Some(ArchiveEntry { path, kind, data: Arc::from(data), problem })
+}
+
+// All SVGs and fonts in a .zip or .tar.gz, in archive order
+pub fn read_archive(path: &Path) -> Result<Vec<ArchiveEntry>, String> {
+    let format = archive_format(path).ok_or_else(|| "Not a .zip or .tar.gz archive".to_string())?;
+    let file = File::open(path).map_err(|e| e.to_string())?;
+
+    let mut entries = Vec::new();
+    match format {
+        ArchiveFormat::Zip => {
+            let mut archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
+            for index in 0..archive.len() {
+                let entry = archive.by_index(index).map_err(|e| e.to_string())?;
+                if entry.is_dir() {
+                    continue;
+                }
+                let Some(entry_path) = safe_path(entry.name()) else {
+                    entries.extend(unsafe_entry(entry.name()));
+                    continue;
+                };
+                let size = entry.size();
+                entries.extend(read_entry(entry_path, size, entry));
+            }
+        }
+        ArchiveFormat::TarGz => {
+            let mut archive = tar::Archive::new(GzDecoder::new(file));
+            for entry in archive.entries().map_err(|e| e.to_string())? {
+                let entry = entry.map_err(|e| e.to_string())?;
+                if !entry.header().entry_type().is_file() {
+                    continue;
+                }
+                let name = entry.path().map(|p| p.to_string_lossy().to_string()).unwrap_or_default();
+                let Some(entry_path) = safe_path(&name) else {
+                    entries.extend(unsafe_entry(&name));
+                    continue;
+                };
+                let size = entry.size();
+                entries.extend(read_entry(entry_path, size, entry));
+            }
+        }
+    }
+
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_paths_outside_the_destination() {
+        assert_eq!(safe_path("icons/add.svg"), Some(PathBuf::from("icons/add.svg")));
+        assert_eq!(safe_path("../add.svg"), None);
+        assert_eq!(safe_path("icons/../../add.svg"), None);
+        assert_eq!(safe_path("/etc/add.svg"), None);
+        assert_eq!(safe_path("./add.svg"), None);
+    }
+
+    #[test]
+    fn lists_unsafe_entries() {
+        let entry = unsafe_entry("../../add.svg").unwrap();
+        assert_eq!(entry.problem.as_deref(), Some(UNSAFE_PATH));
+        assert!(unsafe_entry("../notes.txt").is_none());
+    }
+
+    #[test]
+    fn reports_oversized_entries_without_reading_them() {
+        let entry = read_entry(PathBuf::from("add.svg"), MAX_ENTRY_SIZE + 1, std::io::empty()).unwrap();
+        assert_eq!(entry.problem.as_deref(), Some("Larger than 16 MB"));
+        assert!(entry.data.is_empty());
+
+        let svg = br#"<svg xmlns="http://www.w3.org/2000/svg"/>"#;
+        let entry = read_entry(PathBuf::from("add.svg"), svg.len() as u64, &svg[..]).unwrap();
+        assert_eq!(entry.problem, None);
+        assert_eq!(&entry.data[..], &svg[..]);
+    }
+}
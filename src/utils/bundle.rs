@@ -0,0 +1,334 @@
+use crate::models::file_items::FileSystemItem;
+use crate::utils::file_finder::{scan_directory_recursive, FileFilter};
+use crate::utils::history::asset_key;
+use crate::utils::metadata::MetadataStore;
+use crate::utils::svg_format::{self, FormatOptions};
+use crate::utils::svg_parser::parse_svg_info;
+use crate::utils::svg_render;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use svgtypes::Color;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+pub const MANIFEST_FILE: &str = "manifest.json";
+pub const LICENSE_FILE: &str = "LICENSE.txt";
+
+// What goes into a bundle
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(default)]
+pub struct BundleOptions {
+    // Original files in svg/
+    pub svg: bool,
+    // Minified copies in svg-min/
+    pub minified_svg: bool,
+    // Longest side in pixels, one png/<size>/ folder each
+    pub png_sizes: Vec<u32>,
+    // Written to LICENSE.txt. Empty lists the licenses from the asset metadata instead.
+    pub license: String,
+}
+
+impl Default for BundleOptions {
+    fn default() -> Self {
+        Self {
+            svg: true,
+            minified_svg: false,
+            png_sizes: Vec::new(),
+            license: String::new(),
+        }
+    }
+}
+
+impl BundleOptions {
+    pub fn has_formats(&self) -> bool {
+        self.svg || self.minified_svg || !self.png_sizes.is_empty()
+    }
+}
+
+#[derive(Default)]
+pub struct BundleReport {
+    pub assets: usize,
+    pub files: usize,
+    pub errors: Vec<String>,
+}
+
+impl BundleReport {
+    pub fn summary(&self) -> String {
+        let mut summary = format!("{} assets, {} files", self.assets, self.files);
+        if !self.errors.is_empty() {
+            summary.push_str(&format!(", {} failed", self.errors.len()));
+        }
+        summary
+    }
+}
+
+// "32, 64 128" -> [32, 64, 128]. Zero, duplicates and anything that isn't a number are dropped.
+pub fn parse_sizes(input: &str) -> Vec<u32> {
+    let mut sizes: Vec<u32> = input
+        .split([',', ' '])
+        .filter_map(|size| size.trim().trim_end_matches("px").parse().ok())
+        .filter(|size| *size > 0)
+        .collect();
+    sizes.sort_unstable();
+    sizes.dedup();
+    sizes
+}
+
+// SVGs below the given files and folders, each once, in the order given
+pub fn collect_assets(paths: &[PathBuf]) -> Vec<PathBuf> {
+    let mut assets: Vec<PathBuf> = Vec::new();
+    for path in paths {
+        let found = if path.is_dir() {
+            scan_directory_recursive(&path.to_string_lossy(), FileFilter::Svg)
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|item| match item {
+                    FileSystemItem::SvgFile { path, .. } => Some(path),
+                    _ => None,
+                })
+                .collect()
+        } else {
+            vec![path.clone()]
+        };
+
+        for asset in found {
+            if !assets.contains(&asset) {
+                assets.push(asset);
+            }
+        }
+    }
+    assets
+}
+
+pub(crate) fn color_hex(color: &Color) -> String {
+    if color.alpha == 255 {
+        format!("#{:02x}{:02x}{:02x}", color.red, color.green, color.blue)
+    } else {
+        format!("#{:02x}{:02x}{:02x}{:02x}", color.red, color.green, color.blue, color.alpha)
+    }
+}
+
+fn manifest_entry(path: &Path, key: &str, files: Vec<String>, metadata: &MetadataStore) -> Value {
+    let mut entry = Map::new();
+    let name = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    entry.insert("name".into(), json!(name));
+    entry.insert("path".into(), json!(key));
+    entry.insert("files".into(), json!(files));
+
+    if let Ok(tree) = fs::read_to_string(path).map_err(|e| e.to_string()).and_then(|s| svg_render::parse_tree(&s)) {
+        entry.insert("width".into(), json!(tree.size().width()));
+        entry.insert("height".into(), json!(tree.size().height()));
+    }
+    if let Ok(info) = parse_svg_info(path) {
+        let colors: Vec<String> = info.colors_used.iter().map(color_hex).collect();
+        entry.insert("colors".into(), json!(colors));
+        if !info.embedded.title.is_empty() {
+            entry.insert("title".into(), json!(info.embedded.title));
+        }
+    }
+
+    if let Some(asset) = metadata.get(path) {
+        entry.insert("tags".into(), json!(asset.tags));
+        for (field, value) in [("description", &asset.description), ("author", &asset.author), ("license", &asset.license)] {
+            if !value.trim().is_empty() {
+                entry.insert(field.into(), json!(value.trim()));
+            }
+        }
+        let fields: Map<String, Value> = asset
+            .fields
+            .iter()
+            .filter(|f| !f.key.trim().is_empty())
+            .map(|f| (f.key.trim().to_string(), json!(f.value)))
+            .collect();
+        if !fields.is_empty() {
+            entry.insert("fields".into(), Value::Object(fields));
+        }
+    }
+
+    Value::Object(entry)
+}
+
+// Licenses from the asset metadata, with the assets each one covers
+fn generated_license(keys: &[(PathBuf, String)], metadata: &MetadataStore) -> String {
+    let mut by_license: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (path, key) in keys {
+        let asset = metadata.get(path);
+        let license = asset.map(|a| a.license.trim()).filter(|l| !l.is_empty()).unwrap_or("Unspecified");
+        let line = match asset.map(|a| a.author.trim()).filter(|a| !a.is_empty()) {
+            Some(author) => format!("  - {} (by {})", key, author),
+            None => format!("  - {}", key),
+        };
+        by_license.entry(license.to_string()).or_default().push(line);
+    }
+
+    let mut text = format!("This bundle contains {} assets under the following licenses.\n", keys.len());
+    for (license, lines) in by_license {
+        text.push_str(&format!("\n{}\n{}\n", license, lines.join("\n")));
+    }
+    text
+}
+
+// Write the assets, manifest.json and LICENSE.txt into a ZIP at `output`.
+// Paths inside the bundle mirror the vault. Assets that fail to convert are reported.
+pub fn export_bundle(
+    vault: &Path,
+    assets: &[PathBuf],
+    output: &Path,
+    options: &BundleOptions,
+    metadata: &MetadataStore,
+    format_options: &FormatOptions,
+) -> Result<BundleReport, String> {
+    if !options.has_formats() {
+        return Err("No formats selected".to_string());
+    }
+
+    let mut zip = ZipWriter::new(File::create(output).map_err(|e| e.to_string())?);
+    let file_options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let add = |zip: &mut ZipWriter<File>, name: &str, bytes: &[u8]| -> Result<(), String> {
+        zip.start_file(name, file_options).map_err(|e| e.to_string())?;
+        zip.write_all(bytes).map_err(|e| e.to_string())
+    };
+
+    let mut report = BundleReport::default();
+    let mut entries = Vec::new();
+    let mut keys = Vec::new();
+
+    for path in assets {
+        // Files from outside the vault go to the top level
+        let key = if path.starts_with(vault) {
+            asset_key(vault, path)
+        } else {
+            path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default()
+        };
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) => {
+                report.errors.push(format!("{}: {}", key, e));
+                continue;
+            }
+        };
+
+        let mut files = Vec::new();
+        let mut output_file = |zip: &mut ZipWriter<File>, name: String, bytes: Result<Vec<u8>, String>| match bytes
+            .and_then(|bytes| add(zip, &name, &bytes))
+        {
+            Ok(_) => files.push(name),
+            Err(e) => report.errors.push(format!("{}: {}", name, e)),
+        };
+
+        if options.svg {
+            output_file(&mut zip, format!("svg/{}", key), Ok(content.clone().into_bytes()));
+        }
+        if options.minified_svg {
+            let minified = svg_format::minify(&content, format_options).map(String::into_bytes).map_err(|e| e.to_string());
+            output_file(&mut zip, format!("svg-min/{}", key), minified);
+        }
+        if !options.png_sizes.is_empty() {
+            let tree = svg_render::parse_tree(&content);
+            let png_key = Path::new(&key).with_extension("png").to_string_lossy().replace('\\', "/");
+            for size in &options.png_sizes {
+                let png = tree.as_ref().map_err(|e| e.clone()).and_then(|tree| {
+                    let scale = *size as f32 / tree.size().width().max(tree.size().height());
+                    svg_render::rasterize_tree(tree, scale)?.encode_png().map_err(|e| e.to_string())
+                });
+                output_file(&mut zip, format!("png/{}/{}", size, png_key), png);
+            }
+        }
+
+        entries.push(manifest_entry(path, &key, files, metadata));
+        keys.push((path.clone(), key));
+    }
+
+    report.assets = entries.len();
+    report.files = entries
+        .iter()
+        .filter_map(|entry| entry["files"].as_array().map(|files| files.len()))
+        .sum();
+
+    let created = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let manifest = json!({
+        "generator": "Asset Manager",
+        "created": created,
+        "license": LICENSE_FILE,
+        "assets": entries,
+    });
+    let manifest = serde_json::to_string_pretty(&manifest).map_err(|e| e.to_string())?;
+    add(&mut zip, MANIFEST_FILE, manifest.as_bytes())?;
+
+    let license = if options.license.trim().is_empty() {
+        generated_license(&keys, metadata)
+    } else {
+        options.license.clone()
+    };
+    add(&mut zip, LICENSE_FILE, license.as_bytes())?;
+
+    zip.finish().map_err(|e| e.to_string())?;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    const ICON: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" width="24" height="16"><title>Add</title><path fill="#ff0000" d="M0 0h24v16H0z"/></svg>"##;
+
+    fn read(archive: &mut zip::ZipArchive<File>, name: &str) -> String {
+        let mut text = String::new();
+        archive.by_name(name).unwrap().read_to_string(&mut text).unwrap();
+        text
+    }
+
+    #[test]
+    fn parses_sizes() {
+        assert_eq!(parse_sizes("64, 32 128px, 0, 32, big"), [32, 64, 128]);
+        assert!(parse_sizes("").is_empty());
+    }
+
+    #[test]
+    fn writes_manifest_and_license() {
+        let vault = std::env::temp_dir().join(format!("bundle-manifest-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&vault);
+        fs::create_dir_all(vault.join("icons")).unwrap();
+        let add = vault.join("icons").join("add.svg");
+        let plain = vault.join("plain.svg");
+        fs::write(&add, ICON).unwrap();
+        fs::write(&plain, ICON).unwrap();
+
+        let mut metadata = MetadataStore::load(&vault);
+        let asset = metadata.entry(&add);
+        asset.tags = vec!["ui".to_string()];
+        asset.author = "Jo".to_string();
+        asset.license = " MIT ".to_string();
+
+        let output = vault.join("bundle.zip");
+        let options = BundleOptions { minified_svg: true, ..Default::default() };
+        let report = export_bundle(&vault, &[add, plain], &output, &options, &metadata, &FormatOptions::default()).unwrap();
+        assert_eq!((report.assets, report.files, report.errors.len()), (2, 4, 0));
+
+        let mut archive = zip::ZipArchive::new(File::open(&output).unwrap()).unwrap();
+        let manifest: Value = serde_json::from_str(&read(&mut archive, MANIFEST_FILE)).unwrap();
+        assert_eq!(manifest["license"], LICENSE_FILE);
+        let entry = &manifest["assets"][0];
+        assert_eq!(entry["name"], "add");
+        assert_eq!(entry["path"], "icons/add.svg");
+        assert_eq!(entry["files"], json!(["svg/icons/add.svg", "svg-min/icons/add.svg"]));
+        assert_eq!((entry["width"].as_f64(), entry["height"].as_f64()), (Some(24.0), Some(16.0)));
+        assert_eq!(entry["colors"], json!(["#ff0000"]));
+        assert_eq!(entry["title"], "Add");
+        assert_eq!(entry["tags"], json!(["ui"]));
+        assert_eq!(entry["license"], "MIT");
+        assert!(manifest["assets"][1].get("tags").is_none());
+
+        let license = read(&mut archive, LICENSE_FILE);
+        assert!(license.contains("\nMIT\n  - icons/add.svg (by Jo)\n"));
+        assert!(license.contains("\nUnspecified\n  - plain.svg\n"));
+
+        fs::remove_dir_all(&vault).unwrap();
+    }
+}
@@ -0,0 +1,281 @@
+use crate::utils::bundle::color_hex;
+use crate::utils::history::asset_key;
+use crate::utils::pdf::{self, Canvas, Dialect, PdfDocument};
+use crate::utils::print_export::{truncate, PageSize};
+use crate::utils::snippets::data_uri;
+use crate::utils::svg_format::format_number;
+use crate::utils::svg_parser::parse_svg_info;
+use crate::utils::svg_render;
+use resvg::tiny_skia::Transform;
+use resvg::usvg::{self, Tree};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Page margin, in points
+const MARGIN: f32 = 36.0;
+const HEADING_SIZE: f32 = 10.0;
+const TEXT_SIZE: f32 = 6.5;
+// Colors listed per icon, the rest are counted
+const MAX_SWATCHES: usize = 8;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(default)]
+pub struct CatalogOptions {
+    pub columns: usize,
+    // Icon size in pixels in the HTML, points in the PDF
+    pub thumbnail_size: f32,
+    pub group_by_folder: bool,
+    pub html: bool,
+    pub pdf: bool,
+    pub page_size: PageSize,
+}
+
+impl Default for CatalogOptions {
+    fn default() -> Self {
+        Self {
+            columns: 6,
+            thumbnail_size: 64.0,
+            group_by_folder: true,
+            html: true,
+            pdf: true,
+            page_size: PageSize::A4,
+        }
+    }
+}
+
+pub struct CatalogEntry {
+    pub name: String,
+    // Folder relative to the vault, empty for the top level
+    pub folder: String,
+    pub source: String,
+    pub tree: Option<Tree>,
+    pub colors: Vec<svgtypes::Color>,
+}
+
+impl CatalogEntry {
+    fn dimensions(&self) -> String {
+        match &self.tree {
+            Some(tree) => format!(
+                "{} × {}",
+                format_number(tree.size().width() as f64, 2),
+                format_number(tree.size().height() as f64, 2)
+            ),
+            None => "unreadable".to_string(),
+        }
+    }
+}
+
+// Read every asset once, sorted by folder and name
+pub fn collect_entries(vault: &Path, assets: &[PathBuf]) -> Vec<CatalogEntry> {
+    let mut entries: Vec<CatalogEntry> = assets
+        .iter()
+        .map(|path| {
+            let source = fs::read_to_string(path).unwrap_or_default();
+            let folder = path
+                .parent()
+                .filter(|parent| parent.starts_with(vault) && *parent != vault)
+                .map(|parent| asset_key(vault, parent))
+                .unwrap_or_default();
+            CatalogEntry {
+                name: path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
+                folder,
+                tree: svg_render::parse_tree(&source).ok(),
+                colors: parse_svg_info(path).map(|info| info.colors_used).unwrap_or_default(),
+                source,
+            }
+        })
+        .collect();
+    entries.sort_by(|a, b| {
+        a.folder.cmp(&b.folder).then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+    entries
+}
+
+// Entries by folder, or a single unnamed group
+fn groups<'a>(entries: &'a [CatalogEntry], options: &CatalogOptions) -> Vec<(String, Vec<&'a CatalogEntry>)> {
+    if !options.group_by_folder {
+        return vec![(String::new(), entries.iter().collect())];
+    }
+    let mut groups: Vec<(String, Vec<&CatalogEntry>)> = Vec::new();
+    for entry in entries {
+        match groups.last_mut() {
+            Some((folder, members)) if *folder == entry.folder => members.push(entry),
+            _ => groups.push((entry.folder.clone(), vec![entry])),
+        }
+    }
+    groups
+}
+
+pub(crate) fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+// Self-contained page, icons are embedded as data URIs
+pub fn write_html(entries: &[CatalogEntry], title: &str, output: &Path, options: &CatalogOptions) -> Result<(), String> {
+    let size = format_number(options.thumbnail_size as f64, 0);
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str(&format!("<title>{}</title>\n", escape_html(title)));
+    html.push_str("<style>\n");
+    html.push_str("body { font-family: system-ui, sans-serif; margin: 2rem; color: #222; }\n");
+    html.push_str(&format!(
+        ".grid {{ display: grid; grid-template-columns: repeat({}, minmax(0, 1fr)); gap: 1rem; }}\n",
+        options.columns.max(1)
+    ));
+    html.push_str(".icon { border: 1px solid #e3e3e3; border-radius: 6px; padding: .75rem; break-inside: avoid; }\n");
+    html.push_str(&format!(
+        ".icon img {{ display: block; margin: 0 auto .5rem; width: {size}px; height: {size}px; object-fit: contain; }}\n"
+    ));
+    html.push_str(".name { font-weight: 600; font-size: .85rem; overflow-wrap: anywhere; }\n");
+    html.push_str(".meta { color: #777; font-size: .75rem; }\n");
+    html.push_str(".swatch { display: inline-block; width: .8rem; height: .8rem; margin-right: 2px; border: 1px solid #ccc; border-radius: 2px; }\n");
+    html.push_str("h2 { font-size: 1rem; margin-top: 2rem; border-bottom: 1px solid #ddd; padding-bottom: .25rem; }\n");
+    html.push_str("@media print { body { margin: 0; } h2 { break-after: avoid; } }\n");
+    html.push_str("</style>\n</head>\n<body>\n");
+    html.push_str(&format!("<h1>{}</h1>\n<p class=\"meta\">{} assets</p>\n", escape_html(title), entries.len()));
+
+    for (folder, members) in groups(entries, options) {
+        if options.group_by_folder {
+            let heading = if folder.is_empty() { "/".to_string() } else { folder };
+            html.push_str(&format!("<h2>{} <span class=\"meta\">({})</span></h2>\n", escape_html(&heading), members.len()));
+        }
+        html.push_str("<div class=\"grid\">\n");
+        for entry in members {
+            let image = data_uri(&entry.source).unwrap_or_default();
+            html.push_str("<div class=\"icon\">\n");
+            html.push_str(&format!("<img src=\"{}\" alt=\"{}\">\n", image, escape_html(&entry.name)));
+            html.push_str(&format!("<div class=\"name\">{}</div>\n", escape_html(&entry.name)));
+            html.push_str(&format!("<div class=\"meta\">{}</div>\n", entry.dimensions()));
+            html.push_str("<div>");
+            for color in entry.colors.iter().take(MAX_SWATCHES) {
+                let hex = color_hex(color);
+                html.push_str(&format!("<span class=\"swatch\" style=\"background: {hex}\" title=\"{hex}\"></span>"));
+            }
+            if entry.colors.len() > MAX_SWATCHES {
+                html.push_str(&format!("<span class=\"meta\">+{}</span>", entry.colors.len() - MAX_SWATCHES));
+            }
+            html.push_str("</div>\n</div>\n");
+        }
+        html.push_str("</div>\n");
+    }
+    html.push_str("</body>\n</html>\n");
+
+    fs::write(output, html).map_err(|e| e.to_string())
+}
+
+// Assets the PDF can't show as they look, with what is left out
+pub fn pdf_issues(entries: &[CatalogEntry]) -> Vec<(String, Vec<String>)> {
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let issues = pdf::unsupported_features(&entry.source, entry.tree.as_ref()?);
+            (!issues.is_empty()).then(|| (entry.name.clone(), issues))
+        })
+        .collect()
+}
+
+// Paged version of the same grid, returns the number of pages
+pub fn write_pdf(entries: &[CatalogEntry], title: &str, output: &Path, options: &CatalogOptions) -> Result<usize, String> {
+    let (width, height) = options.page_size.dimensions().unwrap_or((595.0, 842.0));
+    let columns = options.columns.max(1);
+    let cell_width = (width - MARGIN * 2.0) / columns as f32;
+    let icon = options.thumbnail_size.min(cell_width - 8.0).max(8.0);
+    // Icon, name, dimensions and a row of swatches
+    let cell_height = icon + 8.0 + TEXT_SIZE * 5.0;
+
+    let mut document = PdfDocument::new();
+    let mut pages = 0;
+    let mut canvas = Canvas::new(Dialect::Pdf, height);
+    let mut y = MARGIN;
+    canvas.text(MARGIN, y + HEADING_SIZE * 1.4, HEADING_SIZE * 1.4, &format!("{} ({} assets)", title, entries.len()));
+    y += HEADING_SIZE * 3.0;
+
+    for (folder, members) in groups(entries, options) {
+        let heading_height = if options.group_by_folder { HEADING_SIZE * 2.2 } else { 0.0 };
+        // Keep the heading together with the first row
+        if y + heading_height + cell_height > height - MARGIN {
+            document.add_page(width, height, std::mem::replace(&mut canvas, Canvas::new(Dialect::Pdf, height)));
+            pages += 1;
+            y = MARGIN;
+        }
+        if options.group_by_folder {
+            let heading = if folder.is_empty() { "/".to_string() } else { folder };
+            canvas.text(MARGIN, y + HEADING_SIZE, HEADING_SIZE, &format!("{} ({})", heading, members.len()));
+            y += heading_height;
+        }
+
+        for row in members.chunks(columns) {
+            if y + cell_height > height - MARGIN {
+                document.add_page(width, height, std::mem::replace(&mut canvas, Canvas::new(Dialect::Pdf, height)));
+                pages += 1;
+                y = MARGIN;
+            }
+            for (column, entry) in row.iter().enumerate() {
+                let x = MARGIN + column as f32 * cell_width;
+                if let Some(tree) = &entry.tree {
+                    let size = tree.size();
+                    let scale = icon / size.width().max(size.height());
+                    let dx = x + (cell_width - size.width() * scale) / 2.0;
+                    let dy = y + (icon - size.height() * scale) / 2.0;
+                    canvas.draw_tree(tree, Transform::from_scale(scale, scale).post_translate(dx, dy));
+                }
+
+                let text_y = y + icon + 4.0 + TEXT_SIZE;
+                canvas.text(x + 2.0, text_y, TEXT_SIZE, &truncate(&entry.name, cell_width - 4.0, TEXT_SIZE));
+                canvas.text(x + 2.0, text_y + TEXT_SIZE * 1.4, TEXT_SIZE, &entry.dimensions().replace('×', "x"));
+
+                let swatch = TEXT_SIZE;
+                let fitting = (((cell_width - 4.0) / (swatch + 2.0)) as usize).min(MAX_SWATCHES);
+                for (index, color) in entry.colors.iter().take(fitting).enumerate() {
+                    let color = usvg::Color::new_rgb(color.red, color.green, color.blue);
+                    canvas.swatch(x + 2.0 + index as f32 * (swatch + 2.0), text_y + TEXT_SIZE * 2.2, swatch, color);
+                }
+            }
+            y += cell_height;
+        }
+        y += HEADING_SIZE;
+    }
+
+    document.add_page(width, height, canvas);
+    pages += 1;
+    fs::write(output, document.finish()).map_err(|e| e.to_string())?;
+    Ok(pages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names<'a>(members: &[&'a CatalogEntry]) -> Vec<&'a str> {
+        members.iter().map(|entry| entry.name.as_str()).collect()
+    }
+
+    #[test]
+    fn groups_entries_by_folder() {
+        let vault = std::env::temp_dir().join(format!("catalog-groups-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&vault);
+        fs::create_dir_all(vault.join("arrows")).unwrap();
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="4" height="2"/>"#;
+        let arrows = vault.join("arrows");
+        let assets = [arrows.join("up.svg"), vault.join("Zoom.svg"), arrows.join("Down.svg"), vault.join("add.svg")];
+        for path in &assets {
+            fs::write(path, svg).unwrap();
+        }
+
+        let entries = collect_entries(&vault, &assets);
+        let options = CatalogOptions::default();
+        let grouped = groups(&entries, &options);
+        assert_eq!(grouped.len(), 2);
+        assert_eq!((grouped[0].0.as_str(), names(&grouped[0].1)), ("", vec!["add.svg", "Zoom.svg"]));
+        assert_eq!((grouped[1].0.as_str(), names(&grouped[1].1)), ("arrows", vec!["Down.svg", "up.svg"]));
+        assert_eq!(entries[0].dimensions(), "4 × 2");
+
+        let options = CatalogOptions { group_by_folder: false, ..Default::default() };
+        let grouped = groups(&entries, &options);
+        assert_eq!(grouped.len(), 1);
+        assert_eq!(names(&grouped[0].1), ["add.svg", "Zoom.svg", "Down.svg", "up.svg"]);
+
+        fs::remove_dir_all(&vault).unwrap();
+    }
+}
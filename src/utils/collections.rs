@@ -0,0 +1,322 @@
+use crate::models::file_items::FileSystemItem;
+use crate::utils::file_finder::{scan_directory_recursive, FileFilter};
+use crate::utils::history::{asset_key, STORE_DIR};
+use crate::utils::metadata::MetadataStore;
+use crate::utils::svg_parser::parse_svg_info;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+use svgtypes::Color;
+
+const COLLECTIONS_FILE: &str = "collections.json";
+
+// Saved search over the vault. Empty criteria are ignored.
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, Debug)]
+pub struct SmartQuery {
+    // Part of the file name, case-insensitive
+    #[serde(default)]
+    pub name: String,
+    // Assets must have all of these tags
+    #[serde(default)]
+    pub tags: Vec<String>,
+    // Any color the asset uses, e.g. "#ff0000" or "red"
+    #[serde(default)]
+    pub color: String,
+    #[serde(default)]
+    pub min_size_kb: Option<f32>,
+    #[serde(default)]
+    pub max_size_kb: Option<f32>,
+    #[serde(default)]
+    pub modified_within_days: Option<u32>,
+}
+
+impl SmartQuery {
+    pub fn matches(&self, path: &Path, metadata: &MetadataStore) -> bool {
+        let name = path.file_name().map(|n| n.to_string_lossy().to_lowercase()).unwrap_or_default();
+        if !name.contains(&self.name.trim().to_lowercase()) {
+            return false;
+        }
+
+        if !self.tags.is_empty() {
+            let Some(asset) = metadata.get(path) else {
+                return false;
+            };
+            if !self.tags.iter().all(|tag| asset.has_tag(tag)) {
+                return false;
+            }
+        }
+
+        if self.min_size_kb.is_some() || self.max_size_kb.is_some() || self.modified_within_days.is_some() {
+            let Ok(file) = fs::metadata(path) else {
+                return false;
+            };
+
+            let size_kb = file.len() as f32 / 1024.0;
+            if self.min_size_kb.is_some_and(|min| size_kb < min) || self.max_size_kb.is_some_and(|max| size_kb > max) {
+                return false;
+            }
+
+            if let Some(days) = self.modified_within_days {
+                let age = file.modified().ok().and_then(|modified| modified.elapsed().ok());
+                if age.is_none_or(|age| age > Duration::from_secs(days as u64 * 86_400)) {
+                    return false;
+                }
+            }
+        }
+
+        // Colors need the file parsed, so they are checked last
+        if !self.color.trim().is_empty() {
+            let Ok(wanted) = Color::from_str(self.color.trim()) else {
+                return false;
+            };
+            let Ok(info) = parse_svg_info(path) else {
+                return false;
+            };
+            if !info.colors_used.contains(&wanted) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    // Short human-readable summary, e.g. for the collection header
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.name.trim().is_empty() {
+            parts.push(format!("name contains \"{}\"", self.name.trim()));
+        }
+        for tag in &self.tags {
+            parts.push(format!("#{}", tag));
+        }
+        if !self.color.trim().is_empty() {
+            parts.push(format!("uses {}", self.color.trim()));
+        }
+        if let Some(min) = self.min_size_kb {
+            parts.push(format!("≥ {} KB", min));
+        }
+        if let Some(max) = self.max_size_kb {
+            parts.push(format!("≤ {} KB", max));
+        }
+        if let Some(days) = self.modified_within_days {
+            parts.push(format!("modified in the last {} days", days));
+        }
+
+        if parts.is_empty() {
+            "All assets".to_string()
+        } else {
+            parts.join(" · ")
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum CollectionKind {
+    // Hand-picked assets, stored relative to the vault
+    Manual { assets: Vec<String> },
+    Smart { query: SmartQuery },
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct Collection {
+    pub name: String,
+    #[serde(flatten)]
+    pub kind: CollectionKind,
+}
+
+impl Collection {
+    pub fn manual(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            kind: CollectionKind::Manual { assets: Vec::new() },
+        }
+    }
+
+    pub fn is_smart(&self) -> bool {
+        matches!(self.kind, CollectionKind::Smart { .. })
+    }
+
+    pub fn icon(&self) -> &'static str {
+        if self.is_smart() { "🔎" } else { "📌" }
+    }
+
+    // Up to two letters for the sidebar button, e.g. "Brand icons" -> "BI"
+    pub fn initials(&self) -> String {
+        let initials: String = self
+            .name
+            .split_whitespace()
+            .filter_map(|word| word.chars().next())
+            .take(2)
+            .collect();
+        if initials.is_empty() {
+            "?".to_string()
+        } else {
+            initials.to_uppercase()
+        }
+    }
+}
+
+// User-defined collections, stored inside the vault next to the metadata
+#[derive(Serialize, Deserialize, Default)]
+pub struct CollectionStore {
+    #[serde(skip)]
+    vault: PathBuf,
+    pub collections: Vec<Collection>,
+}
+
+impl CollectionStore {
+    fn file_path(vault: &Path) -> PathBuf {
+        vault.join(STORE_DIR).join(COLLECTIONS_FILE)
+    }
+
+    pub fn load(vault: &Path) -> Self {
+        let mut store: Self = fs::read_to_string(Self::file_path(vault))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        store.vault = vault.to_path_buf();
+        store
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Self::file_path(&self.vault);
+        if self.collections.is_empty() && !path.exists() {
+            return Ok(());
+        }
+
+        fs::create_dir_all(path.parent().unwrap_or(&self.vault))?;
+        fs::write(path, serde_json::to_string_pretty(&self)?)?;
+        Ok(())
+    }
+
+    pub fn contains(&self, index: usize, path: &Path) -> bool {
+        let key = asset_key(&self.vault, path);
+        matches!(
+            self.collections.get(index).map(|c| &c.kind),
+            Some(CollectionKind::Manual { assets }) if assets.contains(&key)
+        )
+    }
+
+    // Returns false when the asset was already in the collection
+    pub fn add_asset(&mut self, index: usize, path: &Path) -> bool {
+        let key = asset_key(&self.vault, path);
+        match self.collections.get_mut(index).map(|c| &mut c.kind) {
+            Some(CollectionKind::Manual { assets }) if !assets.contains(&key) => {
+                assets.push(key);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn remove_asset(&mut self, index: usize, path: &Path) {
+        let key = asset_key(&self.vault, path);
+        if let Some(CollectionKind::Manual { assets }) = self.collections.get_mut(index).map(|c| &mut c.kind) {
+            assets.retain(|asset| *asset != key);
+        }
+    }
+
+    // Put `into` in every manual collection that holds `from`
+    pub fn merge(&mut self, from: &Path, into: &Path) {
+        let from_key = asset_key(&self.vault, from);
+        let into_key = asset_key(&self.vault, into);
+
+        for collection in &mut self.collections {
+            if let CollectionKind::Manual { assets } = &mut collection.kind {
+                if assets.contains(&from_key) && !assets.contains(&into_key) {
+                    assets.push(into_key.clone());
+                }
+            }
+        }
+    }
+
+    // Follow a renamed file, or every file below a renamed folder
+    pub fn rename(&mut self, old_path: &Path, new_path: &Path) {
+        let old_key = asset_key(&self.vault, old_path);
+        let new_key = asset_key(&self.vault, new_path);
+
+        for collection in &mut self.collections {
+            if let CollectionKind::Manual { assets } = &mut collection.kind {
+                for asset in assets.iter_mut() {
+                    if *asset == old_key || asset.starts_with(&format!("{}/", old_key)) {
+                        *asset = format!("{}{}", new_key, &asset[old_key.len()..]);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn remove(&mut self, path: &Path) {
+        let key = asset_key(&self.vault, path);
+        for collection in &mut self.collections {
+            if let CollectionKind::Manual { assets } = &mut collection.kind {
+                assets.retain(|asset| *asset != key && !asset.starts_with(&format!("{}/", key)));
+            }
+        }
+    }
+
+    // Assets of a collection as gallery items. Missing files are left out.
+    pub fn items(&self, index: usize, metadata: &MetadataStore) -> Vec<FileSystemItem> {
+        let Some(collection) = self.collections.get(index) else {
+            return Vec::new();
+        };
+
+        match &collection.kind {
+            CollectionKind::Manual { assets } => assets
+                .iter()
+                .filter_map(|asset| FileSystemItem::from_file(&self.vault.join(asset)))
+                .collect(),
+            CollectionKind::Smart { query } => {
+                scan_directory_recursive(&self.vault.to_string_lossy(), FileFilter::Svg)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|item| match item {
+                        FileSystemItem::SvgFile { path, .. } => query.matches(path, metadata),
+                        _ => false,
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store(assets: &[&str]) -> CollectionStore {
+        let assets = assets.iter().map(|asset| asset.to_string()).collect();
+        let collection = Collection { name: "Icons".to_string(), kind: CollectionKind::Manual { assets } };
+        CollectionStore { vault: PathBuf::from("/vault"), collections: vec![collection] }
+    }
+
+    fn assets(store: &CollectionStore) -> Vec<String> {
+        match &store.collections[0].kind {
+            CollectionKind::Manual { assets } => assets.clone(),
+            CollectionKind::Smart { .. } => Vec::new(),
+        }
+    }
+
+    #[test]
+    fn rename_follows_files_and_folders() {
+        let mut store = store(&["ui/add.svg", "ui/arrows/left.svg", "ui-old/add.svg", "add.svg"]);
+        store.rename(Path::new("/vault/ui"), Path::new("/vault/interface"));
+        assert_eq!(assets(&store), ["interface/add.svg", "interface/arrows/left.svg", "ui-old/add.svg", "add.svg"]);
+
+        store.rename(Path::new("/vault/add.svg"), Path::new("/vault/plus.svg"));
+        assert_eq!(assets(&store)[3], "plus.svg");
+    }
+
+    #[test]
+    fn remove_drops_files_and_folder_contents() {
+        let mut store = store(&["ui/add.svg", "ui/arrows/left.svg", "ui-old/add.svg", "add.svg"]);
+        store.remove(Path::new("/vault/ui"));
+        assert_eq!(assets(&store), ["ui-old/add.svg", "add.svg"]);
+
+        store.remove(Path::new("/vault/add.svg"));
+        assert_eq!(assets(&store), ["ui-old/add.svg"]);
+    }
+}
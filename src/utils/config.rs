@@ -1,3 +1,12 @@
+use crate::utils::bundle::BundleOptions;
+use crate::utils::mobile_export::MobileExportOptions;
+use crate::utils::print_export::PrintOptions;
+use crate::utils::catalog::CatalogOptions;
+use crate::utils::server::ServerOptions;
+use crate::utils::import::ImportOptions;
+use crate::utils::recent::RecentEntry;
+use crate::utils::svg_format::FormatOptions;
+use crate::utils::theme_variants::ColorMapping;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
@@ -6,7 +15,32 @@ use std::path::PathBuf;
 pub struct AppConfig {
     pub vault_path: String,
     pub font_path: String,
-    pub thumbnail_size: f32
+    pub thumbnail_size: f32,
+    #[serde(default)]
+    pub theme_color_map: Vec<ColorMapping>,
+    #[serde(default = "AppConfig::get_default_contrast_backgrounds")]
+    pub contrast_backgrounds: Vec<String>,
+    #[serde(default)]
+    pub format_options: FormatOptions,
+    #[serde(default)]
+    pub favorites: Vec<PathBuf>,
+    #[serde(default)]
+    pub recent: Vec<RecentEntry>,
+    // Write tags, description, author and license into the SVG as well
+    #[serde(default)]
+    pub mirror_metadata: bool,
+    #[serde(default)]
+    pub import_options: ImportOptions,
+    #[serde(default)]
+    pub bundle_options: BundleOptions,
+    #[serde(default)]
+    pub mobile_export_options: MobileExportOptions,
+    #[serde(default)]
+    pub print_options: PrintOptions,
+    #[serde(default)]
+    pub catalog_options: CatalogOptions,
+    #[serde(default)]
+    pub server_options: ServerOptions,
 }
 
 impl Default for AppConfig {
@@ -14,7 +48,19 @@ impl Default for AppConfig {
         Self {
             vault_path: Self::get_default_vault_path(),
             font_path: Self::get_default_font_path(),
-            thumbnail_size: 8.0
+            thumbnail_size: 8.0,
+            theme_color_map: Vec::new(),
+            contrast_backgrounds: Self::get_default_contrast_backgrounds(),
+            format_options: FormatOptions::default(),
+            favorites: Vec::new(),
+            recent: Vec::new(),
+            mirror_metadata: false,
+            import_options: ImportOptions::default(),
+            bundle_options: BundleOptions::default(),
+            mobile_export_options: MobileExportOptions::default(),
+            print_options: PrintOptions::default(),
+            catalog_options: CatalogOptions::default(),
+            server_options: ServerOptions::default(),
         }
     }
 }
@@ -72,6 +118,10 @@ impl AppConfig {
         }
     }
 
+    fn get_default_contrast_backgrounds() -> Vec<String> {
+        vec!["#FFFFFF".to_string(), "#000000".to_string()]
+    }
+
     pub fn load() -> Self {
         let config_path = Self::get_config_path().join("config.json");
 
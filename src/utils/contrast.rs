@@ -0,0 +1,132 @@
+use crate::utils::file_finder::{scan_directory_recursive, FileFilter};
+use crate::models::FileSystemItem;
+use crate::utils::svg_parser;
+use std::path::PathBuf;
+use std::str::FromStr;
+use svgtypes::Color;
+
+// WCAG 2.1 SC 1.4.11 asks for 3:1 on graphical objects such as UI icons.
+// We treat the 4.5:1 text threshold as the enhanced (AAA) level for icons.
+pub const AA_THRESHOLD: f64 = 3.0;
+pub const AAA_THRESHOLD: f64 = 4.5;
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum ContrastLevel {
+    Fail,
+    AA,
+    AAA,
+}
+
+impl ContrastLevel {
+    pub fn from_ratio(ratio: f64) -> Self {
+        if ratio >= AAA_THRESHOLD {
+            ContrastLevel::AAA
+        } else if ratio >= AA_THRESHOLD {
+            ContrastLevel::AA
+        } else {
+            ContrastLevel::Fail
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ContrastLevel::Fail => "Fail",
+            ContrastLevel::AA => "AA",
+            ContrastLevel::AAA => "AAA",
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ColorContrast {
+    pub color: Color,
+    pub background: Color,
+    pub ratio: f64,
+    pub level: ContrastLevel,
+}
+
+#[derive(Clone)]
+pub struct IconContrastReport {
+    pub path: PathBuf,
+    pub name: String,
+    pub failures: Vec<ColorContrast>,
+}
+
+fn channel_to_linear(channel: u8) -> f64 {
+    let c = channel as f64 / 255.0;
+    if c <= 0.03928 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+pub fn relative_luminance(color: &Color) -> f64 {
+    0.2126 * channel_to_linear(color.red)
+        + 0.7152 * channel_to_linear(color.green)
+        + 0.0722 * channel_to_linear(color.blue)
+}
+
+pub fn contrast_ratio(a: &Color, b: &Color) -> f64 {
+    let la = relative_luminance(a);
+    let lb = relative_luminance(b);
+    let (lighter, darker) = if la > lb { (la, lb) } else { (lb, la) };
+    (lighter + 0.05) / (darker + 0.05)
+}
+
+// Parse the configured background list, ignoring entries that aren't valid colors
+pub fn parse_backgrounds(backgrounds: &[String]) -> Vec<Color> {
+    backgrounds
+        .iter()
+        .filter_map(|bg| Color::from_str(bg.trim()).ok())
+        .collect()
+}
+
+pub fn check_colors(colors: &[Color], backgrounds: &[Color]) -> Vec<ColorContrast> {
+    let mut results = Vec::new();
+    for color in colors {
+        for background in backgrounds {
+            let ratio = contrast_ratio(color, background);
+            results.push(ColorContrast {
+                color: *color,
+                background: *background,
+                ratio,
+                level: ContrastLevel::from_ratio(ratio),
+            });
+        }
+    }
+    results
+}
+
+// Check every SVG of the vault and keep the icons that have at least one color below `minimum`
+pub fn scan_vault(
+    vault_path: &str,
+    backgrounds: &[Color],
+    minimum: ContrastLevel,
+) -> Result<Vec<IconContrastReport>, std::io::Error> {
+    let mut reports = Vec::new();
+
+    for item in scan_directory_recursive(vault_path, FileFilter::Svg)? {
+        if let FileSystemItem::SvgFile { name, path } = item {
+            let Ok(info) = svg_parser::parse_svg_info(&path) else {
+                continue;
+            };
+
+            let failures: Vec<ColorContrast> = check_colors(&info.colors_used, backgrounds)
+                .into_iter()
+                .filter(|c| c.level < minimum)
+                .collect();
+
+            if !failures.is_empty() {
+                reports.push(IconContrastReport { path, name, failures });
+            }
+        }
+    }
+
+    Ok(reports)
+}
+
+pub fn to_hex(color: &Color) -> String {
+    format!("#{:02X}{:02X}{:02X}", color.red, color.green, color.blue)
+}
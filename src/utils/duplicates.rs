@@ -0,0 +1,211 @@
+use crate::models::file_items::FileSystemItem;
+use crate::utils::file_finder::{scan_directory_recursive, FileFilter};
+use crate::utils::history::content_hash;
+use crate::utils::svg_format::{self, AttributeOrder, FormatOptions};
+use crate::utils::svg_render;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+// Render size used for the perceptual hash, downsampled to 9x8 afterwards
+const HASH_RENDER_SIZE: u32 = 64;
+
+pub const DEFAULT_SIMILARITY: u32 = 6;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MatchKind {
+    // Byte-identical files
+    Exact,
+    // Identical once comments, whitespace, attribute order and number precision are ignored
+    Normalized,
+    // Look alike when rendered
+    Similar,
+}
+
+impl MatchKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            MatchKind::Exact => "Exact copies",
+            MatchKind::Normalized => "Same markup after normalizing",
+            MatchKind::Similar => "Look similar",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DuplicateGroup {
+    pub kind: MatchKind,
+    pub assets: Vec<PathBuf>,
+    // Largest perceptual hash distance inside a Similar group, 0 otherwise
+    pub distance: u32,
+}
+
+struct Fingerprint {
+    path: PathBuf,
+    exact: String,
+    normalized: Option<String>,
+    perceptual: Option<u64>,
+}
+
+fn fingerprint(path: PathBuf) -> Option<Fingerprint> {
+    let content = fs::read_to_string(&path).ok()?;
+
+    let options = FormatOptions {
+        attribute_order: AttributeOrder::Alphabetical,
+        ..FormatOptions::default()
+    };
+    let normalized = svg_format::canonicalize(&content, &options)
+        .and_then(|canonical| svg_format::minify(&canonical, &options))
+        .ok()
+        .map(|minified| content_hash(&minified));
+
+    let perceptual = svg_render::parse_tree(&content)
+        .ok()
+        .and_then(|tree| perceptual_hash(&tree));
+
+    Some(Fingerprint {
+        exact: content_hash(&content),
+        path,
+        normalized,
+        perceptual,
+    })
+}
+
+// Difference hash: 9x8 grayscale thumbnail, one bit per horizontal neighbour pair.
+// Transparent pixels count as mid gray, so both dark and light icons keep their shape.
+// Blank renders have no hash.
+pub fn perceptual_hash(tree: &resvg::usvg::Tree) -> Option<u64> {
+    let pixmap = svg_render::rasterize_fit(tree, HASH_RENDER_SIZE).ok()?;
+    let size = HASH_RENDER_SIZE as usize;
+
+    let mut cells = [[0.0f32; 9]; 8];
+    let mut counts = [[0u32; 9]; 8];
+    for (idx, pixel) in pixmap.pixels().iter().enumerate() {
+        let (x, y) = (idx % size, idx / size);
+        // Premultiplied, so adding the uncovered part composites over gray
+        let gray = (255 - pixel.alpha() as u32) as f32 * 0.5;
+        let luma = 0.299 * (pixel.red() as f32 + gray)
+            + 0.587 * (pixel.green() as f32 + gray)
+            + 0.114 * (pixel.blue() as f32 + gray);
+
+        let (column, row) = (x * 9 / size, y * 8 / size);
+        cells[row][column] += luma;
+        counts[row][column] += 1;
+    }
+
+    let mut averages = [[0.0f32; 9]; 8];
+    for row in 0..8 {
+        for column in 0..9 {
+            averages[row][column] = cells[row][column] / counts[row][column].max(1) as f32;
+        }
+    }
+
+    let values = averages.iter().flatten();
+    let (min, max) = values.fold((f32::MAX, f32::MIN), |(min, max), &v| (min.min(v), max.max(v)));
+    if max - min < 1.0 {
+        return None;
+    }
+
+    let mut hash = 0u64;
+    for row in averages {
+        for column in 0..8 {
+            hash = (hash << 1) | (row[column] > row[column + 1]) as u64;
+        }
+    }
+    Some(hash)
+}
+
+pub fn hamming(a: u64, b: u64) -> u32 {
+    (a ^ b).count_ones()
+}
+
+fn find(parent: &mut [usize], idx: usize) -> usize {
+    let mut root = idx;
+    while parent[root] != root {
+        root = parent[root];
+    }
+    parent[idx] = root;
+    root
+}
+
+// Find exact, normalized and visual duplicates among the vault's SVGs.
+// `max_distance` is the largest perceptual hash difference (out of 64 bits) still called similar.
+pub fn scan_vault(vault_path: &str, max_distance: u32) -> Result<Vec<DuplicateGroup>, std::io::Error> {
+    let fingerprints: Vec<Fingerprint> = scan_directory_recursive(vault_path, FileFilter::Svg)?
+        .into_iter()
+        .filter_map(|item| match item {
+            FileSystemItem::SvgFile { path, .. } => fingerprint(path),
+            _ => None,
+        })
+        .collect();
+
+    let mut groups = Vec::new();
+
+    let mut by_exact: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+    let mut by_normalized: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+    for (idx, fp) in fingerprints.iter().enumerate() {
+        by_exact.entry(&fp.exact).or_default().push(idx);
+        if let Some(normalized) = &fp.normalized {
+            by_normalized.entry(normalized).or_default().push(idx);
+        }
+    }
+
+    let paths = |members: &[usize]| members.iter().map(|&idx| fingerprints[idx].path.clone()).collect();
+
+    for members in by_exact.values().filter(|members| members.len() > 1) {
+        groups.push(DuplicateGroup { kind: MatchKind::Exact, assets: paths(members), distance: 0 });
+    }
+
+    // Only worth listing when the files differ byte-wise, otherwise it repeats an exact group
+    for members in by_normalized.values().filter(|members| members.len() > 1) {
+        let distinct = members.iter().any(|&idx| fingerprints[idx].exact != fingerprints[members[0]].exact);
+        if distinct {
+            groups.push(DuplicateGroup { kind: MatchKind::Normalized, assets: paths(members), distance: 0 });
+        }
+    }
+
+    // Near-duplicates: link every pair within the distance, then take connected groups
+    let hashed: Vec<(usize, u64)> = fingerprints
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, fp)| fp.perceptual.map(|hash| (idx, hash)))
+        .collect();
+    let mut parent: Vec<usize> = (0..fingerprints.len()).collect();
+    for (i, &(a, hash_a)) in hashed.iter().enumerate() {
+        for &(b, hash_b) in &hashed[i + 1..] {
+            if hamming(hash_a, hash_b) <= max_distance {
+                let (root_a, root_b) = (find(&mut parent, a), find(&mut parent, b));
+                parent[root_b] = root_a;
+            }
+        }
+    }
+
+    let mut by_similarity: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for &(idx, _) in &hashed {
+        let root = find(&mut parent, idx);
+        by_similarity.entry(root).or_default().push(idx);
+    }
+    for members in by_similarity.values().filter(|members| members.len() > 1) {
+        // Skip groups that are entirely the same markup, those are listed above
+        let first = &fingerprints[members[0]];
+        let distinct = members.iter().any(|&idx| {
+            let fp = &fingerprints[idx];
+            fp.normalized.is_none() || fp.normalized != first.normalized
+        });
+        if !distinct {
+            continue;
+        }
+
+        let mut distance = 0;
+        for (i, &a) in members.iter().enumerate() {
+            for &b in &members[i + 1..] {
+                if let (Some(hash_a), Some(hash_b)) = (fingerprints[a].perceptual, fingerprints[b].perceptual) {
+                    distance = distance.max(hamming(hash_a, hash_b));
+                }
+            }
+        }
+        groups.push(DuplicateGroup { kind: MatchKind::Similar, assets: paths(members), distance });
+    }
+
+    Ok(groups)
+}
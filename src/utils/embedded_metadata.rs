@@ -0,0 +1,366 @@
+use crate::utils::metadata::AssetMetadata;
+use crate::utils::svg_document::{self, Element, Node};
+use std::ops::Range;
+use xmlparser::{ElementEnd, Token, Tokenizer};
+
+const RDF_NS: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#";
+const DC_NS: &str = "http://purl.org/dc/elements/1.1/";
+const CC_NS: &str = "http://creativecommons.org/ns#";
+
+// Root children that are rewritten as a whole
+const EMBEDDED_ELEMENTS: [&str; 3] = ["title", "desc", "metadata"];
+
+// Metadata carried inside the SVG: <title>, <desc> and Dublin Core fields
+// from an RDF block in <metadata>, the way Inkscape writes them
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct EmbeddedMetadata {
+    pub title: String,
+    pub desc: String,
+    pub creator: String,
+    pub rights: String,
+    pub date: String,
+    pub keywords: Vec<String>,
+}
+
+impl EmbeddedMetadata {
+    pub fn is_empty(&self) -> bool {
+        self.title.is_empty()
+            && self.desc.is_empty()
+            && self.creator.is_empty()
+            && self.rights.is_empty()
+            && self.date.is_empty()
+            && self.keywords.is_empty()
+    }
+
+    // Title and description alone fit in <title> and <desc>
+    fn has_rdf(&self) -> bool {
+        !self.creator.is_empty() || !self.rights.is_empty() || !self.date.is_empty() || !self.keywords.is_empty()
+    }
+
+    // Copy the app-level metadata over. Tags always replace the keywords,
+    // the other fields only when they are filled in.
+    pub fn mirror(&mut self, metadata: &AssetMetadata) {
+        self.keywords = metadata.tags.clone();
+        if !metadata.description.trim().is_empty() {
+            self.desc = metadata.description.trim().to_string();
+        }
+        if !metadata.author.trim().is_empty() {
+            self.creator = metadata.author.trim().to_string();
+        }
+        if !metadata.license.trim().is_empty() {
+            self.rights = metadata.license.trim().to_string();
+        }
+    }
+}
+
+// Unparseable documents have no embedded metadata
+pub fn read(source: &str) -> EmbeddedMetadata {
+    let mut embedded = EmbeddedMetadata::default();
+    let Ok(document) = svg_document::parse(source) else {
+        return embedded;
+    };
+
+    for child in document.root.child_elements() {
+        match child.local_name() {
+            "title" if embedded.title.is_empty() => embedded.title = text_content(child),
+            "desc" if embedded.desc.is_empty() => embedded.desc = text_content(child),
+            "metadata" => read_rdf(child, &mut embedded),
+            _ => {}
+        }
+    }
+
+    embedded
+}
+
+fn read_rdf(metadata: &Element, embedded: &mut EmbeddedMetadata) {
+    let Some(rdf) = find_descendant(metadata, "RDF") else {
+        return;
+    };
+
+    // cc:Work or rdf:Description
+    for work in rdf.child_elements() {
+        for field in work.child_elements() {
+            match field.local_name() {
+                "title" if embedded.title.is_empty() => embedded.title = text_content(field),
+                "description" if embedded.desc.is_empty() => embedded.desc = text_content(field),
+                "creator" if embedded.creator.is_empty() => embedded.creator = text_content(field),
+                "rights" if embedded.rights.is_empty() => embedded.rights = text_content(field),
+                "date" if embedded.date.is_empty() => embedded.date = text_content(field),
+                "subject" if embedded.keywords.is_empty() => {
+                    let mut items = Vec::new();
+                    collect_descendants(field, "li", &mut items);
+                    embedded.keywords = if items.is_empty() {
+                        split_keywords(&text_content(field))
+                    } else {
+                        items.into_iter().map(text_content).filter(|k| !k.is_empty()).collect()
+                    };
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+pub fn split_keywords(text: &str) -> Vec<String> {
+    text.split(',')
+        .map(|keyword| keyword.trim().to_string())
+        .filter(|keyword| !keyword.is_empty())
+        .collect()
+}
+
+fn find_descendant<'a>(element: &'a Element, local: &str) -> Option<&'a Element> {
+    element.child_elements().find_map(|child| {
+        if child.local_name() == local {
+            Some(child)
+        } else {
+            find_descendant(child, local)
+        }
+    })
+}
+
+fn collect_descendants<'a>(element: &'a Element, local: &str, found: &mut Vec<&'a Element>) {
+    for child in element.child_elements() {
+        if child.local_name() == local {
+            found.push(child);
+        } else {
+            collect_descendants(child, local, found);
+        }
+    }
+}
+
+// All text below an element with whitespace collapsed
+fn text_content(element: &Element) -> String {
+    fn collect(element: &Element, text: &mut String) {
+        for node in &element.children {
+            match node {
+                Node::Text(t) => text.push_str(&unescape(t)),
+                Node::CData(t) => text.push_str(t),
+                Node::Element(child) => {
+                    collect(child, text);
+                    text.push(' ');
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut text = String::new();
+    collect(element, &mut text);
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+// Replace <title>, <desc> and <metadata> directly below the root with the given
+// values, leaving the rest of the file untouched. Anything else that lived in
+// <metadata> is dropped. Empty metadata removes the elements.
+pub fn write(source: &str, embedded: &EmbeddedMetadata) -> Result<String, String> {
+    let document = svg_document::parse(source).map_err(|e| e.to_string())?;
+
+    let mut depth = 0usize;
+    let mut root_open_end: Option<usize> = None;
+    let mut root_empty: Option<Range<usize>> = None;
+    // Start of the embedded element currently being skipped, at depth 1
+    let mut current: Option<usize> = None;
+    let mut removed: Vec<Range<usize>> = Vec::new();
+
+    for token in Tokenizer::from(source) {
+        match token.map_err(|e| e.to_string())? {
+            Token::ElementStart { local, span, .. }
+                if depth == 1 && current.is_none() && EMBEDDED_ELEMENTS.contains(&local.as_str()) =>
+            {
+                current = Some(span.start());
+            }
+            Token::ElementEnd { end, span } => match end {
+                ElementEnd::Open => {
+                    if depth == 0 {
+                        root_open_end = Some(span.end());
+                    }
+                    depth += 1;
+                }
+                ElementEnd::Empty if depth == 0 => root_empty = Some(span.start()..span.end()),
+                ElementEnd::Empty => {
+                    if depth == 1 {
+                        if let Some(start) = current.take() {
+                            removed.push(start..span.end());
+                        }
+                    }
+                }
+                ElementEnd::Close(..) => {
+                    depth = depth.saturating_sub(1);
+                    if depth == 1 {
+                        if let Some(start) = current.take() {
+                            removed.push(start..span.end());
+                        }
+                    }
+                }
+            },
+            _ => {}
+        }
+    }
+
+    // Follow the indentation of the existing children, or stay on one line
+    let after_root = root_open_end.map(|end| &source[end..]).unwrap_or_default();
+    let whitespace: String = after_root.chars().take_while(|c| c.is_whitespace()).collect();
+    let (newline, indent) = match whitespace.rfind('\n') {
+        Some(pos) => ("\n", whitespace[pos + 1..].to_string()),
+        None if root_empty.is_some() => ("\n", "  ".to_string()),
+        None => ("", String::new()),
+    };
+    let unit = if newline.is_empty() {
+        String::new()
+    } else if indent.is_empty() {
+        "  ".to_string()
+    } else {
+        indent.clone()
+    };
+    let block = render_block(embedded, newline, &indent, &unit);
+
+    if let Some(empty) = root_empty {
+        if block.is_empty() {
+            return Ok(source.to_string());
+        }
+        let name = &document.root.name;
+        return Ok(format!("{}>{}{}</{}>{}", &source[..empty.start], block, newline, name, &source[empty.end..]));
+    }
+    let Some(insert_at) = root_open_end else {
+        return Err("Document has no root element".to_string());
+    };
+
+    let mut output = String::with_capacity(source.len() + block.len());
+    output.push_str(&source[..insert_at]);
+    output.push_str(&block);
+
+    let mut cursor = insert_at;
+    for range in removed {
+        let start = swallow_indentation(source, range.start).max(cursor);
+        output.push_str(&source[cursor..start]);
+        cursor = range.end;
+    }
+    output.push_str(&source[cursor..]);
+
+    Ok(output)
+}
+
+// Include the indentation and line break before an element that sits on its own line
+fn swallow_indentation(source: &str, start: usize) -> usize {
+    let line_start = source[..start].trim_end_matches([' ', '\t']).len();
+    if source[..line_start].ends_with('\n') {
+        let without_newline = line_start - 1;
+        if source[..without_newline].ends_with('\r') {
+            without_newline - 1
+        } else {
+            without_newline
+        }
+    } else {
+        start
+    }
+}
+
+fn render_block(embedded: &EmbeddedMetadata, newline: &str, indent: &str, unit: &str) -> String {
+    let mut block = String::new();
+    let mut line = |level: usize, text: &str| {
+        block.push_str(newline);
+        block.push_str(indent);
+        block.push_str(&unit.repeat(level));
+        block.push_str(text);
+    };
+
+    if !embedded.title.is_empty() {
+        line(0, &format!("<title>{}</title>", escape(&embedded.title)));
+    }
+    if !embedded.desc.is_empty() {
+        line(0, &format!("<desc>{}</desc>", escape(&embedded.desc)));
+    }
+    if !embedded.has_rdf() {
+        return block;
+    }
+
+    line(0, "<metadata>");
+    line(1, &format!(r#"<rdf:RDF xmlns:rdf="{}" xmlns:dc="{}" xmlns:cc="{}">"#, RDF_NS, DC_NS, CC_NS));
+    line(2, r#"<cc:Work rdf:about="">"#);
+
+    if !embedded.title.is_empty() {
+        line(3, &format!("<dc:title>{}</dc:title>", escape(&embedded.title)));
+    }
+    if !embedded.desc.is_empty() {
+        line(3, &format!("<dc:description>{}</dc:description>", escape(&embedded.desc)));
+    }
+    for (field, value) in [("creator", &embedded.creator), ("rights", &embedded.rights)] {
+        if !value.is_empty() {
+            line(3, &format!("<dc:{}><cc:Agent><dc:title>{}</dc:title></cc:Agent></dc:{}>", field, escape(value), field));
+        }
+    }
+    if !embedded.date.is_empty() {
+        line(3, &format!("<dc:date>{}</dc:date>", escape(&embedded.date)));
+    }
+    if !embedded.keywords.is_empty() {
+        line(3, "<dc:subject>");
+        line(4, "<rdf:Bag>");
+        for keyword in &embedded.keywords {
+            line(5, &format!("<rdf:li>{}</rdf:li>", escape(keyword)));
+        }
+        line(4, "</rdf:Bag>");
+        line(3, "</dc:subject>");
+    }
+
+    line(2, "</cc:Work>");
+    line(1, "</rdf:RDF>");
+    line(0, "</metadata>");
+    block
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn titled(title: &str) -> EmbeddedMetadata {
+        EmbeddedMetadata { title: title.to_string(), ..Default::default() }
+    }
+
+    #[test]
+    fn opens_a_self_closing_root() {
+        let source = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24"/>"#;
+        assert_eq!(
+            write(source, &titled("Add")).unwrap(),
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 24 24\">\n  <title>Add</title>\n</svg>"
+        );
+        assert_eq!(write(source, &EmbeddedMetadata::default()).unwrap(), source);
+
+        let prefixed = r#"<svg:svg xmlns:svg="http://www.w3.org/2000/svg"/>"#;
+        assert!(write(prefixed, &titled("Add")).unwrap().ends_with("</svg:svg>"));
+    }
+
+    #[test]
+    fn replaces_existing_elements_and_keeps_the_rest() {
+        let source = "<svg xmlns=\"http://www.w3.org/2000/svg\">\n    <title>Old</title>\n    <path d=\"M0 0\"/>\n</svg>";
+        assert_eq!(
+            write(source, &titled("New & improved")).unwrap(),
+            "<svg xmlns=\"http://www.w3.org/2000/svg\">\n    <title>New &amp; improved</title>\n    <path d=\"M0 0\"/>\n</svg>"
+        );
+    }
+
+    #[test]
+    fn reads_back_what_it_writes() {
+        let embedded = EmbeddedMetadata {
+            title: "Add".to_string(),
+            desc: "Plus sign".to_string(),
+            creator: "Jane".to_string(),
+            rights: "MIT".to_string(),
+            date: "2026-10-19".to_string(),
+            keywords: vec!["math".to_string(), "plus".to_string()],
+        };
+        let written = write(r#"<svg xmlns="http://www.w3.org/2000/svg"/>"#, &embedded).unwrap();
+        assert_eq!(read(&written), embedded);
+    }
+}
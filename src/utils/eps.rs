@@ -0,0 +1,39 @@
+use crate::utils::pdf::{Canvas, Dialect};
+use crate::utils::svg_format::format_number;
+use resvg::tiny_skia::Transform;
+use resvg::usvg::Tree;
+
+pub struct EpsFile {
+    pub data: String,
+    // EPS has no transparency, semi-transparent parts were drawn opaque
+    pub flattened_transparency: bool,
+}
+
+// Single page Encapsulated PostScript the size of the document, in points
+pub fn tree_to_eps(tree: &Tree, title: &str) -> EpsFile {
+    let size = tree.size();
+    let mut canvas = Canvas::new(Dialect::PostScript, size.height());
+    canvas.draw_tree(tree, Transform::identity());
+
+    let title: String = title.chars().filter(|c| c.is_ascii() && !c.is_ascii_control()).collect();
+    let mut data = String::new();
+    data.push_str("%!PS-Adobe-3.0 EPSF-3.0\n");
+    data.push_str("%%Creator: Asset Manager\n");
+    data.push_str(&format!("%%Title: {}\n", title));
+    data.push_str(&format!(
+        "%%BoundingBox: 0 0 {} {}\n",
+        size.width().ceil() as u32,
+        size.height().ceil() as u32
+    ));
+    data.push_str(&format!(
+        "%%HiResBoundingBox: 0 0 {} {}\n",
+        format_number(size.width() as f64, 3),
+        format_number(size.height() as f64, 3)
+    ));
+    data.push_str("%%LanguageLevel: 3\n%%Pages: 1\n%%EndComments\n");
+    data.push_str("%%Page: 1 1\nsave\n");
+    data.push_str(canvas.content());
+    data.push_str("restore\nshowpage\n%%EOF\n");
+
+    EpsFile { data, flattened_transparency: canvas.flattened_transparency() }
+}
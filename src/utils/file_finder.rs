@@ -1,5 +1,6 @@
 use scan_dir::ScanDir;
 use crate::models::FileSystemItem;
+use crate::utils::theme_variants;
 use std::fs;
 
 pub enum FileFilter {
@@ -102,7 +103,16 @@ pub fn scan_directory_recursive(path: &str, filter: FileFilter) -> Result<Vec<Fi
             let entry_path = entry.path();
             let name = entry.file_name().to_string_lossy().to_string();
 
+            // Hidden entries, like the app's own data folder, are not assets
+            if name.starts_with('.') {
+                continue;
+            }
+
             if entry_path.is_dir() {
+                // Generated dark variants would show up as copies of their sources
+                if theme_variants::is_variant_dir(&entry_path) {
+                    continue;
+                }
                 // Recursively scan subdirectories
                 scan_recursive(&entry_path, filter, items)?;
             } else if entry_path.is_file() {
@@ -0,0 +1,261 @@
+use crate::utils::history::{HISTORY_DIR, STORE_DIR};
+use git2::build::TreeUpdateBuilder;
+use git2::{FileMode, IndexAddOption, Repository, Signature, Status, StatusOptions};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+// Fallback identity when the repository has no user.name / user.email configured
+const DEFAULT_AUTHOR: (&str, &str) = ("Asset Manager", "asset-manager@localhost");
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum FileStatus {
+    // Staged but never committed
+    New,
+    Modified,
+    Untracked,
+    Deleted,
+}
+
+impl FileStatus {
+    pub fn badge(&self) -> &'static str {
+        match self {
+            FileStatus::New => "A",
+            FileStatus::Modified => "M",
+            FileStatus::Untracked => "U",
+            FileStatus::Deleted => "D",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FileStatus::New => "New",
+            FileStatus::Modified => "Modified",
+            FileStatus::Untracked => "Untracked",
+            FileStatus::Deleted => "Deleted",
+        }
+    }
+
+    fn from_git(status: Status) -> Option<Self> {
+        if status.intersects(Status::INDEX_DELETED | Status::WT_DELETED) {
+            Some(FileStatus::Deleted)
+        } else if status.contains(Status::INDEX_NEW) {
+            Some(FileStatus::New)
+        } else if status.contains(Status::WT_NEW) {
+            Some(FileStatus::Untracked)
+        } else if status.intersects(
+            Status::INDEX_MODIFIED | Status::WT_MODIFIED | Status::INDEX_RENAMED | Status::WT_RENAMED,
+        ) {
+            Some(FileStatus::Modified)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LogEntry {
+    pub id: String,
+    pub summary: String,
+    pub author: String,
+    // Seconds since the Unix epoch
+    pub time: i64,
+}
+
+// The repository containing the vault, if any
+pub fn open_repo(vault: &Path) -> Option<Repository> {
+    Repository::discover(vault).ok().filter(|repo| !repo.is_bare())
+}
+
+fn workdir(repo: &Repository) -> Result<PathBuf, git2::Error> {
+    repo.workdir()
+        .map(canonical)
+        .ok_or_else(|| git2::Error::from_str("Repository has no working directory"))
+}
+
+// Resolve symlinks (and /var vs /private/var on macOS) so paths compare equal,
+// also for files that no longer exist
+fn canonical(path: &Path) -> PathBuf {
+    if let Ok(resolved) = path.canonicalize() {
+        return resolved;
+    }
+    match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) => canonical(parent).join(name),
+        _ => path.to_path_buf(),
+    }
+}
+
+fn relative_path(repo: &Repository, path: &Path) -> Result<PathBuf, git2::Error> {
+    let workdir = workdir(repo)?;
+    canonical(path)
+        .strip_prefix(&workdir)
+        .map(Path::to_path_buf)
+        .map_err(|_| git2::Error::from_str(&format!("{} is outside the repository", path.display())))
+}
+
+// Status of every changed file in the repository, keyed by absolute path.
+// Files in the vault are keyed below `vault` as given, so they match the gallery's paths.
+pub fn statuses(repo: &Repository, vault: &Path) -> Result<HashMap<PathBuf, FileStatus>, git2::Error> {
+    let workdir = workdir(repo)?;
+    let canonical_vault = canonical(vault);
+    let mut options = StatusOptions::new();
+    options.include_untracked(true).recurse_untracked_dirs(true);
+
+    let mut result = HashMap::new();
+    for entry in repo.statuses(Some(&mut options))?.iter() {
+        let (Some(path), Some(status)) = (entry.path(), FileStatus::from_git(entry.status())) else {
+            continue;
+        };
+        // History snapshots are the app's own data, not an asset change.
+        // Metadata, collections and lint rules travel with the vault and can be committed.
+        let components: Vec<_> = Path::new(path).components().map(|c| c.as_os_str()).collect();
+        if components.windows(2).any(|pair| pair[0] == STORE_DIR && pair[1] == HISTORY_DIR) {
+            continue;
+        }
+        let absolute = workdir.join(path);
+        let key = match absolute.strip_prefix(&canonical_vault) {
+            Ok(relative) => vault.join(relative),
+            Err(_) => absolute,
+        };
+        result.insert(key, status);
+    }
+
+    Ok(result)
+}
+
+// Commit exactly the given files (including deletions) on HEAD. Anything else that
+// is staged stays staged and out of the commit.
+pub fn commit(repo: &Repository, paths: &[PathBuf], message: &str) -> Result<String, git2::Error> {
+    // A fresh repository has no HEAD commit yet
+    let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+    let base = match &parent {
+        Some(commit) => commit.tree()?,
+        None => repo.find_tree(repo.treebuilder(None)?.write()?)?,
+    };
+
+    let mut index = repo.index()?;
+    // Pick up whatever was staged outside the app since the index was loaded
+    index.read(false)?;
+    let mut update = TreeUpdateBuilder::new();
+    for path in paths {
+        let relative = relative_path(repo, path)?;
+        if path.exists() {
+            let mode = match base.get_path(&relative).map(|entry| entry.filemode()) {
+                Ok(mode) if mode == i32::from(FileMode::BlobExecutable) => FileMode::BlobExecutable,
+                _ => FileMode::Blob,
+            };
+            update.upsert(&relative, repo.blob_path(path)?, mode);
+            index.add_all([&relative], IndexAddOption::DEFAULT, None)?;
+        } else {
+            update.remove(&relative);
+            index.remove_path(&relative)?;
+        }
+    }
+    // Keep the index in step, so the committed files no longer show as changed
+    index.write()?;
+
+    let tree = repo.find_tree(update.create_updated(repo, &base)?)?;
+    let signature = repo
+        .signature()
+        .or_else(|_| Signature::now(DEFAULT_AUTHOR.0, DEFAULT_AUTHOR.1))?;
+    let parents: Vec<&git2::Commit> = parent.iter().collect();
+
+    let oid = repo.commit(Some("HEAD"), &signature, &signature, message, &tree, &parents)?;
+    Ok(oid.to_string())
+}
+
+// Commits that changed the file, newest first
+pub fn file_log(repo: &Repository, path: &Path, limit: usize) -> Result<Vec<LogEntry>, git2::Error> {
+    let relative = relative_path(repo, path)?;
+
+    let mut walk = repo.revwalk()?;
+    if walk.push_head().is_err() {
+        // No commits yet
+        return Ok(Vec::new());
+    }
+
+    let blob_id = |tree: &git2::Tree| tree.get_path(&relative).ok().map(|entry| entry.id());
+
+    let mut entries = Vec::new();
+    for oid in walk {
+        let commit = repo.find_commit(oid?)?;
+        let current = blob_id(&commit.tree()?);
+        let previous = match commit.parent(0) {
+            Ok(parent) => blob_id(&parent.tree()?),
+            Err(_) => None,
+        };
+
+        if current != previous {
+            entries.push(LogEntry {
+                id: commit.id().to_string()[..7].to_string(),
+                summary: commit.summary().unwrap_or_default().to_string(),
+                author: commit.author().name().unwrap_or_default().to_string(),
+                time: commit.time().seconds(),
+            });
+            if entries.len() >= limit {
+                break;
+            }
+        }
+    }
+
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn repository(test: &str) -> (PathBuf, Repository) {
+        let dir = std::env::temp_dir().join(format!("git-{}-{}", test, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let repo = Repository::init(&dir).unwrap();
+        (dir, repo)
+    }
+
+    fn committed(repo: &Repository) -> Vec<String> {
+        let tree = repo.head().unwrap().peel_to_tree().unwrap();
+        tree.iter().map(|entry| entry.name().unwrap().to_string()).collect()
+    }
+
+    #[test]
+    fn commits_only_the_selected_files() {
+        let (dir, repo) = repository("selected");
+        fs::write(dir.join("a.svg"), "<svg/>").unwrap();
+        fs::write(dir.join("b.svg"), "<svg/>").unwrap();
+
+        commit(&repo, &[dir.join("a.svg")], "Add a").unwrap();
+        assert_eq!(committed(&repo), ["a.svg"]);
+
+        // Staged outside the app, stays staged and out of the next commit
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("b.svg")).unwrap();
+        index.write().unwrap();
+        fs::write(dir.join("a.svg"), "<svg width=\"2\"/>").unwrap();
+        commit(&repo, &[dir.join("a.svg")], "Change a").unwrap();
+        assert_eq!(committed(&repo), ["a.svg"]);
+        assert_eq!(statuses(&repo, &dir).unwrap().get(&dir.join("b.svg")), Some(&FileStatus::New));
+        assert_eq!(statuses(&repo, &dir).unwrap().get(&dir.join("a.svg")), None);
+
+        fs::remove_file(dir.join("a.svg")).unwrap();
+        commit(&repo, &[dir.join("a.svg")], "Remove a").unwrap();
+        assert!(committed(&repo).is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn hides_only_history_snapshots() {
+        let (dir, repo) = repository("history");
+        let store = dir.join(STORE_DIR);
+        fs::create_dir_all(store.join(HISTORY_DIR)).unwrap();
+        fs::write(store.join(HISTORY_DIR).join("index.json"), "{}").unwrap();
+        fs::write(store.join("metadata.json"), "{}").unwrap();
+
+        let found = statuses(&repo, &dir).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found.get(&store.join("metadata.json")), Some(&FileStatus::Untracked));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
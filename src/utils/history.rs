@@ -0,0 +1,275 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Hidden folder inside the vault for app data, skipped when scanning
+pub const STORE_DIR: &str = ".asset_manager";
+
+pub(crate) const HISTORY_DIR: &str = "history";
+const OBJECTS_DIR: &str = "objects";
+const INDEX_FILE: &str = "index.json";
+const DELETED: &str = "Deleted";
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Snapshot {
+    // SHA-256 of the content, also the name of the stored object
+    pub hash: String,
+    // Seconds since the Unix epoch
+    pub timestamp: u64,
+    pub reason: String,
+}
+
+// Asset path relative to the vault and its last snapshot
+pub type DeletedAsset = (String, Snapshot);
+
+// Asset path relative to the vault -> snapshots, oldest first
+#[derive(Serialize, Deserialize, Default)]
+struct HistoryIndex {
+    assets: BTreeMap<String, Vec<Snapshot>>,
+}
+
+fn history_dir(vault: &Path) -> PathBuf {
+    vault.join(STORE_DIR).join(HISTORY_DIR)
+}
+
+pub fn object_path(vault: &Path, hash: &str) -> PathBuf {
+    history_dir(vault).join(OBJECTS_DIR).join(format!("{}.svg", hash))
+}
+
+pub fn content_hash(content: &str) -> String {
+    Sha256::digest(content.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+// Vault-relative path with forward slashes, used as a key by the vault sidecar files
+pub fn asset_key(vault: &Path, asset: &Path) -> String {
+    asset
+        .strip_prefix(vault)
+        .unwrap_or(asset)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+fn load_index(vault: &Path) -> HistoryIndex {
+    fs::read_to_string(history_dir(vault).join(INDEX_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_index(vault: &Path, index: &HistoryIndex) -> Result<(), Box<dyn std::error::Error>> {
+    let dir = history_dir(vault);
+    fs::create_dir_all(&dir)?;
+    fs::write(dir.join(INDEX_FILE), serde_json::to_string_pretty(index)?)?;
+    Ok(())
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn store_object(vault: &Path, content: &str) -> std::io::Result<String> {
+    let hash = content_hash(content);
+    let path = object_path(vault, &hash);
+    if !path.exists() {
+        fs::create_dir_all(path.parent().unwrap_or(vault))?;
+        fs::write(&path, content)?;
+    }
+    Ok(hash)
+}
+
+// Record an asset being overwritten. The content it had before is kept as
+// "Original" the first time, so the very first version can always be restored.
+pub fn record_change(
+    vault: &Path,
+    asset: &Path,
+    before: Option<&str>,
+    after: &str,
+    reason: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut index = load_index(vault);
+    let snapshots = index.assets.entry(asset_key(vault, asset)).or_default();
+
+    if snapshots.is_empty() {
+        if let Some(before) = before {
+            snapshots.push(Snapshot {
+                hash: store_object(vault, before)?,
+                timestamp: now(),
+                reason: "Original".to_string(),
+            });
+        }
+    }
+
+    let hash = store_object(vault, after)?;
+    if snapshots.last().map(|s| s.hash == hash).unwrap_or(false) {
+        return Ok(());
+    }
+    snapshots.push(Snapshot {
+        hash,
+        timestamp: now(),
+        reason: reason.to_string(),
+    });
+
+    save_index(vault, &index)
+}
+
+// Keep the content of an SVG that is about to be deleted, so it can be restored.
+// Other files (fonts) have no history.
+pub fn record_deletion(vault: &Path, asset: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    if !asset.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("svg")) {
+        return Ok(());
+    }
+    let content = fs::read_to_string(asset)?;
+    let mut index = load_index(vault);
+    index.assets.entry(asset_key(vault, asset)).or_default().push(Snapshot {
+        hash: store_object(vault, &content)?,
+        timestamp: now(),
+        reason: DELETED.to_string(),
+    });
+    save_index(vault, &index)
+}
+
+// Assets whose last snapshot is a deletion and that haven't come back since, newest first
+pub fn deleted_assets(vault: &Path) -> Vec<DeletedAsset> {
+    let mut deleted: Vec<DeletedAsset> = load_index(vault)
+        .assets
+        .into_iter()
+        .filter_map(|(key, mut snapshots)| {
+            let last = snapshots.pop()?;
+            (last.reason == DELETED && !vault.join(&key).exists()).then_some((key, last))
+        })
+        .collect();
+    deleted.sort_by_key(|(_, snapshot)| std::cmp::Reverse(snapshot.timestamp));
+    deleted
+}
+
+// Write the last version of a deleted asset back to where it was
+pub fn restore_deleted(vault: &Path, key: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let mut index = load_index(vault);
+    let snapshots = index.assets.get_mut(key).ok_or("No history for this asset")?;
+    let last = snapshots.last().cloned().ok_or("No history for this asset")?;
+
+    let path = vault.join(key);
+    if path.exists() {
+        return Err(format!("{} already exists", key).into());
+    }
+    fs::create_dir_all(path.parent().unwrap_or(vault))?;
+    fs::write(&path, read_snapshot(vault, &last.hash)?)?;
+
+    snapshots.push(Snapshot {
+        hash: last.hash,
+        timestamp: now(),
+        reason: "Restored after deletion".to_string(),
+    });
+    save_index(vault, &index)?;
+    Ok(path)
+}
+
+// Move the history of a renamed file, or of every file below a renamed folder
+pub fn rename(vault: &Path, old_path: &Path, new_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let mut index = load_index(vault);
+    let old_key = asset_key(vault, old_path);
+    let new_key = asset_key(vault, new_path);
+    let old_name = old_path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+
+    let moved: Vec<String> = index
+        .assets
+        .keys()
+        .filter(|key| **key == old_key || key.starts_with(&format!("{}/", old_key)))
+        .cloned()
+        .collect();
+    if moved.is_empty() {
+        return Ok(());
+    }
+
+    for key in moved {
+        let Some(mut snapshots) = index.assets.remove(&key) else {
+            continue;
+        };
+        if let Some(last) = snapshots.last().cloned() {
+            snapshots.push(Snapshot {
+                hash: last.hash,
+                timestamp: now(),
+                reason: format!("Renamed from {}", old_name),
+            });
+        }
+        let renamed = format!("{}{}", new_key, &key[old_key.len()..]);
+        index.assets.entry(renamed).or_default().extend(snapshots);
+    }
+
+    save_index(vault, &index)
+}
+
+// Snapshots of one asset, newest first
+pub fn snapshots(vault: &Path, asset: &Path) -> Vec<Snapshot> {
+    let mut snapshots = load_index(vault)
+        .assets
+        .remove(&asset_key(vault, asset))
+        .unwrap_or_default();
+    snapshots.reverse();
+    snapshots
+}
+
+pub fn read_snapshot(vault: &Path, hash: &str) -> std::io::Result<String> {
+    fs::read_to_string(object_path(vault, hash))
+}
+
+// "2026-10-19 14:03 UTC", without pulling in a date crate
+pub fn format_timestamp(timestamp: u64) -> String {
+    let days = (timestamp / 86_400) as i64;
+    let seconds = timestamp % 86_400;
+
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        (seconds % 3600) / 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restores_deleted_assets() {
+        let vault = std::env::temp_dir().join(format!("history-test-{}", std::process::id()));
+        let asset = vault.join("icons").join("add.svg");
+        fs::create_dir_all(asset.parent().unwrap()).unwrap();
+        fs::write(&asset, "<svg/>").unwrap();
+
+        record_deletion(&vault, &asset).unwrap();
+        fs::remove_file(&asset).unwrap();
+        let deleted = deleted_assets(&vault);
+        assert_eq!(deleted.len(), 1);
+        assert_eq!(deleted[0].0, "icons/add.svg");
+
+        assert_eq!(restore_deleted(&vault, "icons/add.svg").unwrap(), asset);
+        assert_eq!(fs::read_to_string(&asset).unwrap(), "<svg/>");
+        assert!(deleted_assets(&vault).is_empty());
+        assert!(restore_deleted(&vault, "icons/add.svg").is_err());
+
+        fs::remove_dir_all(&vault).unwrap();
+    }
+}
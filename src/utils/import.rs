@@ -0,0 +1,349 @@
+use crate::utils::archive::ArchiveEntry;
+use crate::utils::sanitize;
+use crate::utils::svg_format::{self, FormatAction, FormatOptions};
+use crate::utils::svg_validator;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const FONT_EXTENSIONS: &[&str] = &["ttf", "otf", "woff", "woff2"];
+
+// TrueType, OpenType, WOFF and WOFF2 signatures
+const FONT_SIGNATURES: &[&[u8; 4]] = &[b"\x00\x01\x00\x00", b"true", b"OTTO", b"wOFF", b"wOF2"];
+
+// How files are normalized on their way into the vault
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(default)]
+pub struct ImportOptions {
+    // "Arrow Left.svg" -> "arrow-left.svg"
+    pub kebab_case: bool,
+    // Put in front of every name that doesn't start with it yet, e.g. "ic-"
+    pub prefix: String,
+    // Formatter run over imported SVGs
+    pub optimize: Option<FormatAction>,
+    // Drop comments, <metadata> and editor data
+    pub strip_metadata: bool,
+    // Remove scripts, event handlers and external references
+    pub sanitize: bool,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        Self {
+            kebab_case: true,
+            prefix: String::new(),
+            optimize: None,
+            strip_metadata: false,
+            sanitize: true,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum AssetKind {
+    Svg,
+    Font,
+}
+
+pub fn asset_kind(path: &Path) -> Option<AssetKind> {
+    let extension = path.extension()?.to_string_lossy().to_lowercase();
+    if extension == "svg" {
+        Some(AssetKind::Svg)
+    } else if FONT_EXTENSIONS.contains(&extension.as_str()) {
+        Some(AssetKind::Font)
+    } else {
+        None
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct ImportReport {
+    // Source file and the copy in the vault
+    pub imported: Vec<(PathBuf, PathBuf)>,
+    // Source file and why it was left out
+    pub rejected: Vec<(PathBuf, String)>,
+    // Source file and the active content removed from it
+    pub sanitized: Vec<(PathBuf, Vec<String>)>,
+}
+
+impl ImportReport {
+    pub fn merge(&mut self, other: ImportReport) {
+        self.imported.extend(other.imported);
+        self.rejected.extend(other.rejected);
+        self.sanitized.extend(other.sanitized);
+    }
+
+    pub fn summary(&self) -> String {
+        let mut summary = format!("{} imported", self.imported.len());
+        if !self.rejected.is_empty() {
+            summary.push_str(&format!(", {} rejected", self.rejected.len()));
+        }
+        if !self.sanitized.is_empty() {
+            summary.push_str(&format!(", {} sanitized", self.sanitized.len()));
+        }
+        summary
+    }
+}
+
+// Files are taken as they are, folders contribute their SVGs and fonts.
+// Hidden files and folders are skipped.
+pub fn collect_sources(paths: &[PathBuf]) -> Vec<PathBuf> {
+    fn visit(dir: &Path, files: &mut Vec<PathBuf>) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        let mut paths: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+        paths.sort();
+
+        for path in paths {
+            if path.file_name().is_some_and(|name| name.to_string_lossy().starts_with('.')) {
+                continue;
+            }
+            if path.is_dir() {
+                visit(&path, files);
+            } else if asset_kind(&path).is_some() {
+                files.push(path);
+            }
+        }
+    }
+
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            visit(path, &mut files);
+        } else {
+            files.push(path.clone());
+        }
+    }
+    files
+}
+
+// Lowercase words joined by dashes, splitting camelCase as well: "myIcon_v2" -> "my-icon-v2"
+pub fn to_kebab_case(name: &str) -> String {
+    let mut out = String::new();
+    let mut previous: Option<char> = None;
+
+    for c in name.chars() {
+        if c.is_alphanumeric() {
+            let word_start = previous.is_some_and(|p| p.is_lowercase() || p.is_numeric()) && c.is_uppercase();
+            if word_start && !out.ends_with('-') {
+                out.push('-');
+            }
+            out.extend(c.to_lowercase());
+        } else if !out.is_empty() && !out.ends_with('-') {
+            out.push('-');
+        }
+        previous = Some(c);
+    }
+
+    let out = out.trim_end_matches('-').to_string();
+    if out.is_empty() { "asset".to_string() } else { out }
+}
+
+// File name an asset gets in the vault, before conflicts are resolved
+pub fn target_name(source: &Path, options: &ImportOptions) -> String {
+    let stem = source.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let extension = source.extension().map(|e| e.to_string_lossy().to_lowercase()).unwrap_or_default();
+
+    let mut stem = if options.kebab_case { to_kebab_case(&stem) } else { stem };
+    let prefix = options.prefix.trim();
+    if !prefix.is_empty() && !stem.starts_with(prefix) {
+        stem = format!("{}{}", prefix, stem);
+    }
+
+    if extension.is_empty() { stem } else { format!("{}.{}", stem, extension) }
+}
+
+// "name.svg", then "name-2.svg", "name-3.svg"... whichever is free
+fn unique_path(dir: &Path, name: &str) -> PathBuf {
+    let candidate = dir.join(name);
+    if !candidate.exists() {
+        return candidate;
+    }
+
+    let (stem, extension) = match name.rsplit_once('.') {
+        Some((stem, extension)) => (stem, format!(".{}", extension)),
+        None => (name, String::new()),
+    };
+    (2..)
+        .map(|n| dir.join(format!("{}-{}{}", stem, n, extension)))
+        .find(|candidate| !candidate.exists())
+        .unwrap_or(candidate)
+}
+
+// Reason a file can't be imported, if any
+pub fn check_asset(kind: AssetKind, bytes: &[u8]) -> Result<(), String> {
+    match kind {
+        AssetKind::Svg => {
+            let content = std::str::from_utf8(bytes).map_err(|_| "Not valid UTF-8".to_string())?;
+            match svg_validator::validate(content)
+                .into_iter()
+                .find(|d| d.severity == svg_validator::Severity::Error)
+            {
+                Some(error) => Err(error.to_string()),
+                None => Ok(()),
+            }
+        }
+        AssetKind::Font => {
+            if FONT_SIGNATURES.iter().any(|signature| bytes.starts_with(*signature)) {
+                Ok(())
+            } else {
+                Err("Not a TrueType, OpenType or WOFF font".to_string())
+            }
+        }
+    }
+}
+
+// Validate, then sanitize and normalize SVGs as configured. Fonts are copied as they are.
+// Also returns the active content that was removed.
+fn prepare(
+    kind: AssetKind,
+    bytes: Vec<u8>,
+    options: &ImportOptions,
+    format_options: &FormatOptions,
+) -> Result<(Vec<u8>, Vec<String>), String> {
+    check_asset(kind, &bytes)?;
+    if kind == AssetKind::Font {
+        return Ok((bytes, Vec::new()));
+    }
+
+    let mut content = String::from_utf8(bytes).map_err(|_| "Not valid UTF-8".to_string())?;
+    let mut removed = Vec::new();
+    if options.sanitize {
+        (content, removed) = sanitize::sanitize(&content)?;
+    }
+    if options.strip_metadata {
+        content = svg_format::strip_metadata(&content, format_options).map_err(|e| e.to_string())?;
+    }
+    if let Some(action) = options.optimize {
+        content = svg_format::apply(action, &content, format_options).map_err(|e| e.to_string())?;
+    }
+
+    Ok((content.into_bytes(), removed))
+}
+
+// Copy the sources into `destination`, normalizing SVGs on the way.
+// Files that can't be read, don't validate or aren't assets are reported instead.
+pub fn import_files(
+    sources: &[PathBuf],
+    destination: &Path,
+    options: &ImportOptions,
+    format_options: &FormatOptions,
+) -> std::io::Result<ImportReport> {
+    fs::create_dir_all(destination)?;
+
+    let mut report = ImportReport::default();
+    for source in sources {
+        let Some(kind) = asset_kind(source) else {
+            report.rejected.push((source.clone(), "Unsupported file type".to_string()));
+            continue;
+        };
+
+        let prepared = fs::read(source)
+            .map_err(|e| e.to_string())
+            .and_then(|bytes| prepare(kind, bytes, options, format_options));
+        let (bytes, removed) = match prepared {
+            Ok(prepared) => prepared,
+            Err(reason) => {
+                report.rejected.push((source.clone(), reason));
+                continue;
+            }
+        };
+
+        let target = unique_path(destination, &target_name(source, options));
+        match fs::write(&target, bytes) {
+            Ok(_) => {
+                if !removed.is_empty() {
+                    report.sanitized.push((source.clone(), removed));
+                }
+                report.imported.push((source.clone(), target));
+            }
+            Err(e) => report.rejected.push((source.clone(), e.to_string())),
+        }
+    }
+
+    Ok(report)
+}
+
+// Same as import_files for entries read from an archive. With `keep_structure`
+// the folders inside the archive are recreated below `destination`.
+pub fn import_entries(
+    archive: &Path,
+    entries: &[&ArchiveEntry],
+    destination: &Path,
+    keep_structure: bool,
+    options: &ImportOptions,
+    format_options: &FormatOptions,
+) -> std::io::Result<ImportReport> {
+    fs::create_dir_all(destination)?;
+
+    let mut report = ImportReport::default();
+    for entry in entries {
+        let source = archive.join(&entry.path);
+        let (bytes, removed) = match prepare(entry.kind, entry.data.to_vec(), options, format_options) {
+            Ok(prepared) => prepared,
+            Err(reason) => {
+                report.rejected.push((source, reason));
+                continue;
+            }
+        };
+
+        let folder = match entry.path.parent() {
+            Some(parent) if keep_structure => destination.join(parent),
+            _ => destination.to_path_buf(),
+        };
+        let written = fs::create_dir_all(&folder).and_then(|_| {
+            let target = unique_path(&folder, &target_name(&entry.path, options));
+            fs::write(&target, bytes).map(|_| target)
+        });
+        match written {
+            Ok(target) => {
+                if !removed.is_empty() {
+                    report.sanitized.push((source.clone(), removed));
+                }
+                report.imported.push((source, target));
+            }
+            Err(e) => report.rejected.push((source, e.to_string())),
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn kebab_cases_names() {
+        assert_eq!(to_kebab_case("Arrow Left"), "arrow-left");
+        assert_eq!(to_kebab_case("arrowLeft"), "arrow-left");
+        assert_eq!(to_kebab_case("arrow_left  (2)"), "arrow-left-2");
+        assert_eq!(to_kebab_case("icon2Big"), "icon2-big");
+        assert_eq!(to_kebab_case("--"), "asset");
+    }
+
+    #[test]
+    fn target_names_get_the_prefix_once() {
+        let options = ImportOptions { prefix: "ic-".to_string(), ..Default::default() };
+        assert_eq!(target_name(Path::new("in/Arrow Left.SVG"), &options), "ic-arrow-left.svg");
+        assert_eq!(target_name(Path::new("ic-add.svg"), &options), "ic-add.svg");
+
+        let keep = ImportOptions { kebab_case: false, ..Default::default() };
+        assert_eq!(target_name(Path::new("Arrow Left.svg"), &keep), "Arrow Left.svg");
+    }
+
+    #[test]
+    fn unique_paths_count_up() {
+        let dir = std::env::temp_dir().join(format!("import-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        assert_eq!(unique_path(&dir, "add.svg"), dir.join("add.svg"));
+
+        fs::write(dir.join("add.svg"), "").unwrap();
+        fs::write(dir.join("add-2.svg"), "").unwrap();
+        assert_eq!(unique_path(&dir, "add.svg"), dir.join("add-3.svg"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
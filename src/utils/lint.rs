@@ -0,0 +1,470 @@
+use crate::utils::history::STORE_DIR;
+use crate::utils::sanitize::{self, Finding, Threat};
+use crate::utils::svg_document::{self, Element, Node};
+use crate::utils::svg_validator::Severity;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::LazyLock;
+use svgtypes::{Color, Paint, ViewBox};
+
+const RULES_FILE: &str = "lint.json";
+
+// Properties that take a color, as attributes or inside style=""
+const COLOR_PROPERTIES: &[&str] = &["fill", "stroke", "stop-color", "color", "flood-color", "lighting-color"];
+
+const RASTER_EXTENSIONS: &[&str] = &[".png", ".jpg", ".jpeg", ".gif", ".webp", ".bmp"];
+
+// Hex colors inside <style>
+static COLOR_PATTERN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"#(?:[0-9a-fA-F]{6}|[0-9a-fA-F]{3})\b").unwrap());
+
+// Which checks run and their limits. Kept in the vault so CI lints with the same rules as the app.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(default)]
+pub struct LintRules {
+    pub require_view_box: bool,
+    pub no_fixed_size: bool,
+    pub square: bool,
+    // Allowed viewBox sizes, empty allows any
+    pub grid_sizes: Vec<u32>,
+    // 0 allows any number of paths
+    pub max_paths: usize,
+    pub no_raster_images: bool,
+    pub no_scripts: bool,
+    pub no_external_references: bool,
+    // Allowed colors, empty allows any
+    pub palette: Vec<String>,
+    // Regex the file name has to match, empty allows any
+    pub name_pattern: String,
+}
+
+impl Default for LintRules {
+    fn default() -> Self {
+        Self {
+            require_view_box: true,
+            no_fixed_size: false,
+            square: true,
+            grid_sizes: vec![16, 20, 24],
+            max_paths: 0,
+            no_raster_images: true,
+            no_scripts: true,
+            no_external_references: true,
+            palette: Vec::new(),
+            name_pattern: r"^[a-z0-9]+(-[a-z0-9]+)*\.svg$".to_string(),
+        }
+    }
+}
+
+impl LintRules {
+    fn file_path(vault: &Path) -> PathBuf {
+        vault.join(STORE_DIR).join(RULES_FILE)
+    }
+
+    pub fn load(vault: &Path) -> Self {
+        fs::read_to_string(Self::file_path(vault))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, vault: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Self::file_path(vault);
+        fs::create_dir_all(path.parent().unwrap_or(vault))?;
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Rule {
+    // The file couldn't be read or parsed at all
+    Parse,
+    ViewBox,
+    FixedSize,
+    Square,
+    GridSize,
+    PathCount,
+    RasterImage,
+    Script,
+    ExternalReference,
+    Palette,
+    FileName,
+}
+
+impl Rule {
+    // Stable name for CLI output and scripts
+    pub fn id(&self) -> &'static str {
+        match self {
+            Rule::Parse => "parse",
+            Rule::ViewBox => "viewbox",
+            Rule::FixedSize => "fixed-size",
+            Rule::Square => "square",
+            Rule::GridSize => "grid-size",
+            Rule::PathCount => "max-paths",
+            Rule::RasterImage => "raster-image",
+            Rule::Script => "script",
+            Rule::ExternalReference => "external-reference",
+            Rule::Palette => "palette",
+            Rule::FileName => "file-name",
+        }
+    }
+
+    // Security and portability problems are errors, style is a warning
+    fn severity(&self) -> Severity {
+        match self {
+            Rule::Parse | Rule::RasterImage | Rule::Script | Rule::ExternalReference => Severity::Error,
+            _ => Severity::Warning,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Problem {
+    pub rule: Rule,
+    pub severity: Severity,
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl std::fmt::Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Ln {}, Col {}: {} [{}]", self.line, self.column, self.message, self.rule.id())
+    }
+}
+
+pub fn error_count(problems: &[Problem]) -> usize {
+    problems.iter().filter(|p| p.severity == Severity::Error).count()
+}
+
+// The rules with the name pattern and palette parsed once, for linting many files
+pub struct Linter<'a> {
+    rules: &'a LintRules,
+    // None when any name is allowed
+    name_pattern: Option<Result<Regex, regex::Error>>,
+    palette: Vec<Color>,
+}
+
+impl<'a> Linter<'a> {
+    pub fn new(rules: &'a LintRules) -> Self {
+        let pattern = rules.name_pattern.trim();
+        Self {
+            rules,
+            name_pattern: (!pattern.is_empty()).then(|| Regex::new(pattern)),
+            palette: rules.palette.iter().filter_map(|color| Color::from_str(color.trim()).ok()).collect(),
+        }
+    }
+
+    // Check one file, reading it from disk
+    pub fn lint_file(&self, path: &Path) -> Vec<Problem> {
+        match fs::read_to_string(path) {
+            Ok(source) => {
+                let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                self.lint(&name, &source)
+            }
+            Err(e) => {
+                vec![Problem { rule: Rule::Parse, severity: Severity::Error, message: e.to_string(), line: 1, column: 1 }]
+            }
+        }
+    }
+
+    pub fn lint(&self, name: &str, source: &str) -> Vec<Problem> {
+        let rules = self.rules;
+        let mut problems = Vec::new();
+        let mut report = |rule: Rule, pos: usize, message: String| {
+            let (line, column) = svg_document::line_col(source, pos);
+            problems.push(Problem { rule, severity: rule.severity(), message, line, column });
+        };
+
+        match &self.name_pattern {
+            Some(Ok(pattern)) if !pattern.is_match(name) => {
+                report(Rule::FileName, 0, format!("File name doesn't match {}", rules.name_pattern.trim()))
+            }
+            Some(Err(_)) => report(Rule::FileName, 0, format!("Invalid name pattern {}", rules.name_pattern.trim())),
+            _ => {}
+        }
+
+        let document = match svg_document::parse(source) {
+            Ok(document) => document,
+            Err(e) => {
+                let (line, column) = (e.line, e.column);
+                problems.push(Problem { rule: Rule::Parse, severity: Severity::Error, message: e.message, line, column });
+                return problems;
+            }
+        };
+        let root = &document.root;
+
+        let view_box = root.attr("viewBox").and_then(|value| ViewBox::from_str(value).ok());
+        let fixed = |name: &str| root.attr(name).filter(|value| !value.trim().ends_with('%'));
+        if rules.require_view_box && view_box.is_none() {
+            report(Rule::ViewBox, root.pos, "Missing viewBox".to_string());
+        }
+        if rules.no_fixed_size && (fixed("width").is_some() || fixed("height").is_some()) {
+            report(Rule::FixedSize, root.pos, "Fixed width or height on the root, size it with CSS instead".to_string());
+        }
+
+        let size = view_box.map(|v| (v.w, v.h)).or_else(|| {
+            let length = |name: &str| fixed(name)?.trim().trim_end_matches("px").parse::<f64>().ok();
+            Some((length("width")?, length("height")?))
+        });
+        if let Some((width, height)) = size {
+            let dimensions = format!("{} × {}", width, height);
+            if rules.square && (width - height).abs() > f64::EPSILON {
+                report(Rule::Square, root.pos, format!("Not square ({})", dimensions));
+            }
+            let on_grid = |side: f64| rules.grid_sizes.iter().any(|&grid| (side - grid as f64).abs() < 1e-6);
+            if !rules.grid_sizes.is_empty() && (!on_grid(width) || !on_grid(height)) {
+                let allowed: Vec<String> = rules.grid_sizes.iter().map(|size| size.to_string()).collect();
+                report(Rule::GridSize, root.pos, format!("{} is not on the {} grid", dimensions, allowed.join("/")));
+            }
+        }
+
+        let mut paths = 0;
+        let palette = &self.palette;
+        let mut stack = vec![root];
+
+        while let Some(element) = stack.pop() {
+            let local = element.local_name();
+            if local == "path" {
+                paths += 1;
+            }
+
+            for attr in &element.attributes {
+                let name = svg_document::local_name(&attr.name);
+                let value = attr.value.trim();
+                let is_href = name == "href";
+
+                if rules.no_raster_images && is_href && matches!(local, "image" | "feImage") {
+                    let lower = value.to_ascii_lowercase();
+                    let embedded = lower.starts_with("data:image/") && !lower.starts_with("data:image/svg+xml");
+                    if embedded || RASTER_EXTENSIONS.iter().any(|ext| lower.ends_with(ext)) {
+                        let kind = if embedded { "Embedded" } else { "Linked" };
+                        report(Rule::RasterImage, attr.pos, format!("{} raster image", kind));
+                    }
+                }
+
+                if !palette.is_empty() {
+                    let declarations: Vec<(&str, &str)> = if name == "style" {
+                        value.split(';').filter_map(|declaration| declaration.split_once(':')).collect()
+                    } else {
+                        vec![(name, value)]
+                    };
+                    for (property, value) in declarations {
+                        if !COLOR_PROPERTIES.contains(&property.trim()) {
+                            continue;
+                        }
+                        if let Some(color) = paint_color(value.trim()) {
+                            if !in_palette(palette, &color) {
+                                report(Rule::Palette, attr.pos, format!("{} is not in the palette", value.trim()));
+                            }
+                        }
+                    }
+                }
+            }
+
+            // Stylesheets can set colors too
+            if local == "style" {
+                let css = text_content(element);
+                if !palette.is_empty() {
+                    for found in COLOR_PATTERN.find_iter(&css) {
+                        if Color::from_str(found.as_str()).is_ok_and(|color| !in_palette(palette, &color)) {
+                            report(Rule::Palette, element.pos, format!("{} is not in the palette", found.as_str()));
+                        }
+                    }
+                }
+            }
+
+            // Foreign content isn't rendered as SVG
+            if local != "foreignObject" && local != "metadata" {
+                stack.extend(element.child_elements().collect::<Vec<_>>().into_iter().rev());
+            }
+        }
+
+        // Same detector as the sanitizer and the import badges, so they all agree.
+        // It also looks inside <foreignObject>, which the loop above skips.
+        if rules.no_scripts || rules.no_external_references {
+            let findings = sanitize::scan(source).unwrap_or_else(|e| {
+                // Files the sanitizer refuses to expand are reported as unsafe scripts
+                vec![Finding { threat: Threat::Script, message: e, pos: 0 }]
+            });
+            for finding in findings {
+                match finding.threat {
+                    Threat::Script if rules.no_scripts => report(Rule::Script, finding.pos, finding.message),
+                    Threat::ExternalReference if rules.no_external_references => {
+                        report(Rule::ExternalReference, finding.pos, finding.message)
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if rules.max_paths > 0 && paths > rules.max_paths {
+            report(Rule::PathCount, root.pos, format!("{} paths, at most {} allowed", paths, rules.max_paths));
+        }
+
+        problems.sort_by_key(|p| (p.line, p.column));
+        problems
+    }
+}
+
+// Check one file, reading it from disk
+pub fn lint_file(path: &Path, rules: &LintRules) -> Vec<Problem> {
+    Linter::new(rules).lint_file(path)
+}
+
+// Every asset that has problems, in the order given
+pub fn lint_assets(assets: &[PathBuf], rules: &LintRules) -> Vec<(PathBuf, Vec<Problem>)> {
+    let linter = Linter::new(rules);
+    assets
+        .iter()
+        .map(|path| (path.clone(), linter.lint_file(path)))
+        .filter(|(_, problems)| !problems.is_empty())
+        .collect()
+}
+
+fn paint_color(value: &str) -> Option<Color> {
+    match Paint::from_str(value).ok()? {
+        Paint::Color(color) => Some(color),
+        _ => None,
+    }
+}
+
+fn in_palette(palette: &[Color], color: &Color) -> bool {
+    palette.iter().any(|allowed| allowed.red == color.red && allowed.green == color.green && allowed.blue == color.blue)
+}
+
+fn text_content(element: &Element) -> String {
+    element
+        .children
+        .iter()
+        .filter_map(|node| match node {
+            Node::Text(text) | Node::CData(text) => Some(text.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Every check off, so each test turns on the one it looks at
+    fn no_rules() -> LintRules {
+        LintRules {
+            require_view_box: false,
+            no_fixed_size: false,
+            square: false,
+            grid_sizes: Vec::new(),
+            max_paths: 0,
+            no_raster_images: false,
+            no_scripts: false,
+            no_external_references: false,
+            palette: Vec::new(),
+            name_pattern: String::new(),
+        }
+    }
+
+    fn messages(rules: &LintRules, name: &str, source: &str) -> Vec<String> {
+        Linter::new(rules)
+            .lint(name, source)
+            .iter()
+            .map(|problem| format!("{}: {}", problem.rule.id(), problem.message))
+            .collect()
+    }
+
+    fn svg(attributes: &str, content: &str) -> String {
+        format!(r#"<svg xmlns="http://www.w3.org/2000/svg" {}>{}</svg>"#, attributes, content)
+    }
+
+    #[test]
+    fn requires_view_box() {
+        let rules = LintRules { require_view_box: true, ..no_rules() };
+        assert_eq!(messages(&rules, "a.svg", &svg(r#"width="24" height="24""#, "")), ["viewbox: Missing viewBox"]);
+        assert!(messages(&rules, "a.svg", &svg(r#"viewBox="0 0 24 24""#, "")).is_empty());
+    }
+
+    #[test]
+    fn flags_fixed_size() {
+        let rules = LintRules { no_fixed_size: true, ..no_rules() };
+        assert_eq!(
+            messages(&rules, "a.svg", &svg(r#"viewBox="0 0 24 24" width="24""#, "")),
+            ["fixed-size: Fixed width or height on the root, size it with CSS instead"]
+        );
+        assert!(messages(&rules, "a.svg", &svg(r#"viewBox="0 0 24 24" width="100%" height="100%""#, "")).is_empty());
+    }
+
+    #[test]
+    fn requires_square() {
+        let rules = LintRules { square: true, ..no_rules() };
+        assert_eq!(messages(&rules, "a.svg", &svg(r#"viewBox="0 0 24 16""#, "")), ["square: Not square (24 × 16)"]);
+        // Without a viewBox the size comes from width and height
+        assert_eq!(messages(&rules, "a.svg", &svg(r#"width="24px" height="16""#, "")), ["square: Not square (24 × 16)"]);
+        assert!(messages(&rules, "a.svg", &svg(r#"viewBox="0 0 24 24""#, "")).is_empty());
+    }
+
+    #[test]
+    fn checks_grid_sizes() {
+        let rules = LintRules { grid_sizes: vec![16, 24], ..no_rules() };
+        assert_eq!(
+            messages(&rules, "a.svg", &svg(r#"viewBox="0 0 24 20""#, "")),
+            ["grid-size: 24 × 20 is not on the 16/24 grid"]
+        );
+        assert!(messages(&rules, "a.svg", &svg(r#"viewBox="0 0 16 24""#, "")).is_empty());
+    }
+
+    #[test]
+    fn counts_paths() {
+        let rules = LintRules { max_paths: 2, ..no_rules() };
+        let paths = r#"<path d="M0 0"/><g><path d="M0 0"/><path d="M0 0"/></g>"#;
+        assert_eq!(messages(&rules, "a.svg", &svg("", paths)), ["max-paths: 3 paths, at most 2 allowed"]);
+        assert!(messages(&rules, "a.svg", &svg("", r#"<path d="M0 0"/><path d="M0 0"/>"#)).is_empty());
+    }
+
+    #[test]
+    fn flags_raster_images() {
+        let rules = LintRules { no_raster_images: true, ..no_rules() };
+        let images = r#"<image href="photo.PNG"/><image href="data:image/jpeg;base64,AA"/><image href="data:image/svg+xml;base64,AA"/><image href="b.svg"/>"#;
+        let problems = Linter::new(&rules).lint("a.svg", &svg("", images));
+        let found: Vec<&str> = problems.iter().map(|problem| problem.message.as_str()).collect();
+        assert_eq!(found, ["Linked raster image", "Embedded raster image"]);
+        assert!(problems.iter().all(|problem| problem.severity == Severity::Error));
+    }
+
+    #[test]
+    fn checks_the_palette() {
+        let rules = LintRules { palette: vec!["#000".to_string(), "red".to_string()], ..no_rules() };
+        let content = r##"<path fill="#ff0000" stroke="#00f" d="M0 0"/><path style="fill: black; stop-color: #0f0; width: #123" d="M0 0"/><path fill="url(#g)" d="M0 0"/><style>.a { fill: #FF0000; stroke: #abcdef }</style>"##;
+        assert_eq!(
+            messages(&rules, "a.svg", &svg("", content)),
+            [
+                "palette: #00f is not in the palette",
+                "palette: #0f0 is not in the palette",
+                "palette: #abcdef is not in the palette",
+            ]
+        );
+    }
+
+    #[test]
+    fn checks_file_names() {
+        let rules = LintRules { name_pattern: LintRules::default().name_pattern, ..no_rules() };
+        let source = svg("", "");
+        assert!(messages(&rules, "arrow-left.svg", &source).is_empty());
+        assert_eq!(
+            messages(&rules, "Arrow_Left.svg", &source),
+            [r"file-name: File name doesn't match ^[a-z0-9]+(-[a-z0-9]+)*\.svg$"]
+        );
+        let rules = LintRules { name_pattern: "(".to_string(), ..no_rules() };
+        assert_eq!(messages(&rules, "a.svg", &source), ["file-name: Invalid name pattern ("]);
+    }
+
+    #[test]
+    fn agrees_with_the_sanitizer() {
+        let source = r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24"><foreignObject><div onclick="x()"/></foreignObject><a xlink:href="java&#x09;script:x()"/><rect style="fill: url(https://example.com/a.svg#p)"/></svg>"##;
+        let problems = Linter::new(&LintRules::default()).lint("icon.svg", source);
+        let rules: Vec<Rule> = problems.iter().map(|problem| problem.rule).collect();
+        assert_eq!(rules, [Rule::Script, Rule::Script, Rule::Script, Rule::ExternalReference]);
+        assert_eq!(rules.len(), sanitize::scan(source).unwrap().len());
+    }
+}
@@ -0,0 +1,182 @@
+use crate::utils::history::STORE_DIR;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const METADATA_FILE: &str = "metadata.json";
+
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, Debug)]
+pub struct MetadataField {
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, Debug)]
+pub struct AssetMetadata {
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub author: String,
+    #[serde(default)]
+    pub license: String,
+    #[serde(default)]
+    pub fields: Vec<MetadataField>,
+}
+
+impl AssetMetadata {
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+            && self.description.trim().is_empty()
+            && self.author.trim().is_empty()
+            && self.license.trim().is_empty()
+            && self.fields.iter().all(|f| f.key.trim().is_empty() && f.value.trim().is_empty())
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+
+    // Tags are trimmed and kept unique, ignoring case
+    pub fn add_tag(&mut self, tag: &str) -> bool {
+        let tag = tag.trim();
+        if tag.is_empty() || self.has_tag(tag) {
+            return false;
+        }
+        self.tags.push(tag.to_string());
+        true
+    }
+
+    // `query` is lowercase. "#tag" only matches tags, anything else matches any text.
+    pub fn matches(&self, query: &str) -> bool {
+        if let Some(tag) = query.strip_prefix('#') {
+            return self.tags.iter().any(|t| t.to_lowercase().starts_with(tag));
+        }
+
+        self.tags.iter().any(|t| t.to_lowercase().contains(query))
+            || self.description.to_lowercase().contains(query)
+            || self.author.to_lowercase().contains(query)
+            || self.license.to_lowercase().contains(query)
+            || self.fields.iter().any(|f| {
+                f.key.to_lowercase().contains(query) || f.value.to_lowercase().contains(query)
+            })
+    }
+}
+
+// Sidecar database of asset metadata, stored inside the vault so it travels with it
+#[derive(Serialize, Deserialize, Default)]
+pub struct MetadataStore {
+    #[serde(skip)]
+    vault: PathBuf,
+    // Asset path relative to the vault -> metadata
+    assets: BTreeMap<String, AssetMetadata>,
+}
+
+impl MetadataStore {
+    fn file_path(vault: &Path) -> PathBuf {
+        vault.join(STORE_DIR).join(METADATA_FILE)
+    }
+
+    pub fn load(vault: &Path) -> Self {
+        let mut store: Self = fs::read_to_string(Self::file_path(vault))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        store.vault = vault.to_path_buf();
+        store
+    }
+
+    pub fn save(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        self.assets.retain(|_, metadata| !metadata.is_empty());
+
+        // Don't create the sidecar for a vault that never had any metadata
+        let path = Self::file_path(&self.vault);
+        if self.assets.is_empty() && !path.exists() {
+            return Ok(());
+        }
+
+        fs::create_dir_all(path.parent().unwrap_or(&self.vault))?;
+        fs::write(path, serde_json::to_string_pretty(&self)?)?;
+        Ok(())
+    }
+
+    fn key(&self, path: &Path) -> String {
+        path.strip_prefix(&self.vault)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/")
+    }
+
+    pub fn get(&self, path: &Path) -> Option<&AssetMetadata> {
+        self.assets.get(&self.key(path))
+    }
+
+    // Metadata for editing, created empty if the asset has none yet
+    pub fn entry(&mut self, path: &Path) -> &mut AssetMetadata {
+        let key = self.key(path);
+        self.assets.entry(key).or_default()
+    }
+
+    pub fn matches(&self, path: &Path, query: &str) -> bool {
+        self.get(path).map(|m| m.matches(query)).unwrap_or(false)
+    }
+
+    // Move the metadata of a renamed file, or of everything below a renamed folder
+    pub fn rename(&mut self, old_path: &Path, new_path: &Path) {
+        let old_key = self.key(old_path);
+        let new_key = self.key(new_path);
+
+        let moved: Vec<String> = self
+            .assets
+            .keys()
+            .filter(|key| **key == old_key || key.starts_with(&format!("{}/", old_key)))
+            .cloned()
+            .collect();
+
+        for key in moved {
+            if let Some(metadata) = self.assets.remove(&key) {
+                self.assets.insert(format!("{}{}", new_key, &key[old_key.len()..]), metadata);
+            }
+        }
+    }
+
+    pub fn remove(&mut self, path: &Path) {
+        let key = self.key(path);
+        self.assets
+            .retain(|k, _| *k != key && !k.starts_with(&format!("{}/", key)));
+    }
+
+    // Fold the metadata of a duplicate into the asset that is kept: tags and
+    // custom fields are combined, text fields only fill in blanks
+    pub fn merge(&mut self, from: &Path, into: &Path) {
+        let Some(source) = self.get(from).cloned() else {
+            return;
+        };
+        let target = self.entry(into);
+
+        for tag in &source.tags {
+            target.add_tag(tag);
+        }
+        for (value, merged) in [
+            (&source.description, &mut target.description),
+            (&source.author, &mut target.author),
+            (&source.license, &mut target.license),
+        ] {
+            if merged.trim().is_empty() {
+                *merged = value.clone();
+            }
+        }
+        for field in source.fields {
+            if !target.fields.iter().any(|f| f.key == field.key) {
+                target.fields.push(field);
+            }
+        }
+    }
+
+    pub fn all_tags(&self) -> Vec<String> {
+        let tags: BTreeSet<&String> = self.assets.values().flat_map(|m| m.tags.iter()).collect();
+        tags.into_iter().cloned().collect()
+    }
+}
@@ -0,0 +1,264 @@
+use crate::utils::import::to_kebab_case;
+use crate::utils::svg_document;
+use crate::utils::svg_render;
+use crate::utils::{pdf, vector_drawable};
+use resvg::usvg::{BlendMode, Group, Node, Tree};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Catalog created inside the output folder unless it already is one
+const CATALOG_NAME: &str = "Icons.xcassets";
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum MobileTarget {
+    Android,
+    Ios,
+}
+
+impl MobileTarget {
+    pub fn label(&self) -> &'static str {
+        match self {
+            MobileTarget::Android => "Android VectorDrawable",
+            MobileTarget::Ios => "iOS asset catalog",
+        }
+    }
+}
+
+// What goes into each .imageset
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum AppleFormat {
+    Pdf,
+    Svg,
+}
+
+impl AppleFormat {
+    pub fn label(&self) -> &'static str {
+        match self {
+            AppleFormat::Pdf => "PDF",
+            AppleFormat::Svg => "SVG (iOS 13+)",
+        }
+    }
+
+    fn extension(&self) -> &'static str {
+        match self {
+            AppleFormat::Pdf => "pdf",
+            AppleFormat::Svg => "svg",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(default)]
+pub struct MobileExportOptions {
+    pub target: MobileTarget,
+    pub apple_format: AppleFormat,
+    // Render as template images, tinted with the tint color
+    pub template_rendering: bool,
+    // Folder for the drawables, or for iOS the .xcassets itself or the folder to create one in
+    pub output_dir: String,
+}
+
+impl Default for MobileExportOptions {
+    fn default() -> Self {
+        Self {
+            target: MobileTarget::Android,
+            apple_format: AppleFormat::Pdf,
+            template_rendering: false,
+            output_dir: String::new(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct MobileExportReport {
+    pub exported: usize,
+    // Asset name with what couldn't be carried over
+    pub issues: Vec<(String, Vec<String>)>,
+    pub errors: Vec<String>,
+    pub output: PathBuf,
+}
+
+impl MobileExportReport {
+    pub fn summary(&self) -> String {
+        let mut summary = format!("{} exported", self.exported);
+        if !self.issues.is_empty() {
+            summary.push_str(&format!(", {} with unsupported features", self.issues.len()));
+        }
+        if !self.errors.is_empty() {
+            summary.push_str(&format!(", {} failed", self.errors.len()));
+        }
+        summary
+    }
+}
+
+fn walk_groups(group: &Group, found: &mut Vec<String>) {
+    for child in group.children() {
+        match child {
+            Node::Group(child) => {
+                if child.blend_mode() != BlendMode::Normal {
+                    push_once(found, "Blend modes are ignored");
+                }
+                walk_groups(child, found);
+            }
+            Node::Image(_) => push_once(found, "Embedded images are dropped"),
+            _ => {}
+        }
+    }
+}
+
+fn push_once(list: &mut Vec<String>, item: &str) {
+    if !list.iter().any(|existing| existing == item) {
+        list.push(item.to_string());
+    }
+}
+
+// SVG features neither VectorDrawable nor the PDF writer can draw
+pub fn dropped_features(source: &str, tree: &Tree) -> Vec<String> {
+    let mut found = Vec::new();
+    if !tree.filters().is_empty() {
+        push_once(&mut found, "Filters are dropped");
+    }
+    if !tree.masks().is_empty() {
+        push_once(&mut found, "Masks are dropped");
+    }
+    if !tree.patterns().is_empty() {
+        push_once(&mut found, "Pattern fills are dropped");
+    }
+    walk_groups(tree.root(), &mut found);
+
+    // Text never makes it into the tree, so look at the markup
+    if let Ok(document) = svg_document::parse(source) {
+        let mut stack = vec![&document.root];
+        while let Some(element) = stack.pop() {
+            if element.local_name() == "text" {
+                push_once(&mut found, "Text is dropped, convert it to outlines first");
+                break;
+            }
+            stack.extend(element.child_elements());
+        }
+    }
+    found
+}
+
+// Resource names may only hold lowercase letters, digits and underscores
+pub fn android_name(path: &Path) -> String {
+    let stem = path.file_stem().map(|s| s.to_string_lossy().to_lowercase()).unwrap_or_default();
+    let words: Vec<&str> = stem.split(|c: char| !c.is_ascii_alphanumeric()).filter(|w| !w.is_empty()).collect();
+    let name = words.join("_");
+    if name.starts_with("ic_") { name } else { format!("ic_{}", name) }
+}
+
+fn unique_name(base: String, taken: &mut Vec<String>, separator: &str) -> String {
+    let mut name = base.clone();
+    let mut counter = 2;
+    while taken.contains(&name) {
+        name = format!("{}{}{}", base, separator, counter);
+        counter += 1;
+    }
+    taken.push(name.clone());
+    name
+}
+
+fn write_json(path: &Path, value: &serde_json::Value) -> Result<(), String> {
+    let text = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+    fs::write(path, text).map_err(|e| e.to_string())
+}
+
+pub fn export(assets: &[PathBuf], options: &MobileExportOptions) -> Result<MobileExportReport, String> {
+    if options.output_dir.trim().is_empty() {
+        return Err("No output folder".to_string());
+    }
+    let output = PathBuf::from(options.output_dir.trim());
+
+    let output = match options.target {
+        MobileTarget::Android => output,
+        MobileTarget::Ios if output.extension().is_some_and(|e| e == "xcassets") => output,
+        MobileTarget::Ios => output.join(CATALOG_NAME),
+    };
+    fs::create_dir_all(&output).map_err(|e| e.to_string())?;
+    if options.target == MobileTarget::Ios && !output.join("Contents.json").exists() {
+        write_json(&output.join("Contents.json"), &json!({ "info": { "author": "xcode", "version": 1 } }))?;
+    }
+
+    let mut report = MobileExportReport { output: output.clone(), ..Default::default() };
+    let mut taken = Vec::new();
+
+    for path in assets {
+        let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        let stem = path.file_stem().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        let result = fs::read_to_string(path).map_err(|e| e.to_string()).and_then(|source| {
+            let tree = svg_render::parse_tree(&source)?;
+            let mut issues = Vec::new();
+
+            match options.target {
+                MobileTarget::Android => {
+                    let name = unique_name(android_name(path), &mut taken, "_");
+                    let drawable = vector_drawable::convert(&tree);
+                    issues = dropped_features(&source, &tree);
+                    for issue in &drawable.issues {
+                        push_once(&mut issues, issue);
+                    }
+                    fs::write(output.join(format!("{}.xml", name)), drawable.xml).map_err(|e| e.to_string())?;
+                }
+                MobileTarget::Ios => {
+                    let name = unique_name(to_kebab_case(&stem), &mut taken, "-");
+                    let image_set = output.join(format!("{}.imageset", name));
+                    fs::create_dir_all(&image_set).map_err(|e| e.to_string())?;
+
+                    let file = format!("{}.{}", name, options.apple_format.extension());
+                    match options.apple_format {
+                        AppleFormat::Pdf => {
+                            issues = pdf::unsupported_features(&source, &tree);
+                            fs::write(image_set.join(&file), pdf::tree_to_pdf(&tree)).map_err(|e| e.to_string())?;
+                        }
+                        AppleFormat::Svg => fs::write(image_set.join(&file), &source).map_err(|e| e.to_string())?,
+                    }
+
+                    let mut properties = json!({ "preserves-vector-representation": true });
+                    if options.template_rendering {
+                        properties["template-rendering-intent"] = json!("template");
+                    }
+                    write_json(
+                        &image_set.join("Contents.json"),
+                        &json!({
+                            "images": [{ "filename": file, "idiom": "universal" }],
+                            "info": { "author": "xcode", "version": 1 },
+                            "properties": properties,
+                        }),
+                    )?;
+                }
+            }
+            Ok(issues)
+        });
+
+        match result {
+            Ok(issues) => {
+                report.exported += 1;
+                if !issues.is_empty() {
+                    report.issues.push((file_name, issues));
+                }
+            }
+            Err(e) => report.errors.push(format!("{}: {}", file_name, e)),
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_android_resources() {
+        assert_eq!(android_name(Path::new("Arrow Left.svg")), "ic_arrow_left");
+        assert_eq!(android_name(Path::new("icons/24-clock.v2.svg")), "ic_24_clock_v2");
+        assert_eq!(android_name(Path::new("ic_add.svg")), "ic_add");
+
+        let mut taken = Vec::new();
+        assert_eq!(unique_name(android_name(Path::new("add.svg")), &mut taken, "_"), "ic_add");
+        assert_eq!(unique_name(android_name(Path::new("Add.svg")), &mut taken, "_"), "ic_add_2");
+    }
+}
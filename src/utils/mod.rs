@@ -2,4 +2,32 @@ pub mod file_finder;
 pub mod config;
 pub mod file_actions;
 pub mod svg_parser;
-pub mod font_loader;
\ No newline at end of file
+pub mod font_loader;
+pub mod theme_variants;
+pub mod contrast;
+pub mod svg_document;
+pub mod svg_validator;
+pub mod svg_render;
+pub mod svg_format;
+pub mod svg_diff;
+pub mod history;
+pub mod git;
+pub mod metadata;
+pub mod collections;
+pub mod recent;
+pub mod embedded_metadata;
+pub mod duplicates;
+pub mod import;
+pub mod archive;
+pub mod bundle;
+pub mod snippets;
+pub mod pdf;
+pub mod vector_drawable;
+pub mod mobile_export;
+pub mod eps;
+pub mod print_export;
+pub mod catalog;
+pub mod site;
+pub mod server;
+pub mod lint;
+pub mod sanitize;
@@ -0,0 +1,659 @@
+use crate::utils::mobile_export::dropped_features;
+use crate::utils::svg_format::format_number;
+use crate::utils::vector_drawable::GROUP_OPACITY;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use resvg::tiny_skia::{Path, PathBuilder, PathSegment, Rect, Transform};
+use resvg::usvg::{self, ClipPath, FillRule, Group, LineCap, LineJoin, Node, Paint, Tree};
+use std::io::Write;
+
+// Object numbers of the catalog and the page tree, written last
+const CATALOG: usize = 1;
+const PAGES: usize = 2;
+
+fn num(value: f32) -> String {
+    format_number(value as f64, 3)
+}
+
+// PDF matrices are [a b c d e f] with x' = a*x + c*y + e and y' = b*x + d*y + f
+fn matrix(ts: Transform) -> String {
+    [ts.sx, ts.ky, ts.kx, ts.sy, ts.tx, ts.ty].map(num).join(" ")
+}
+
+fn rgb(color: usvg::Color) -> String {
+    [color.red, color.green, color.blue].map(|c| num(c as f32 / 255.0)).join(" ")
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Dialect {
+    Pdf,
+    // Level 3, for EPS
+    PostScript,
+}
+
+// Operator names, which is most of what differs between the two
+struct Operators {
+    move_to: &'static str,
+    line_to: &'static str,
+    curve_to: &'static str,
+    close: &'static str,
+    save: &'static str,
+    restore: &'static str,
+    fill: &'static str,
+    even_odd_fill: &'static str,
+    stroke: &'static str,
+    clip: &'static str,
+    even_odd_clip: &'static str,
+    line_width: &'static str,
+    line_cap: &'static str,
+    line_join: &'static str,
+    miter_limit: &'static str,
+    dash: &'static str,
+}
+
+const PDF_OPERATORS: Operators = Operators {
+    move_to: "m",
+    line_to: "l",
+    curve_to: "c",
+    close: "h",
+    save: "q",
+    restore: "Q",
+    fill: "f",
+    even_odd_fill: "f*",
+    stroke: "S",
+    clip: "W n",
+    even_odd_clip: "W* n",
+    line_width: "w",
+    line_cap: "J",
+    line_join: "j",
+    miter_limit: "M",
+    dash: "d",
+};
+
+const POSTSCRIPT_OPERATORS: Operators = Operators {
+    move_to: "moveto",
+    line_to: "lineto",
+    curve_to: "curveto",
+    close: "closepath",
+    save: "gsave",
+    restore: "grestore",
+    fill: "fill",
+    even_odd_fill: "eofill",
+    stroke: "stroke",
+    clip: "clip newpath",
+    even_odd_clip: "eoclip newpath",
+    line_width: "setlinewidth",
+    line_cap: "setlinecap",
+    line_join: "setlinejoin",
+    miter_limit: "setmiterlimit",
+    dash: "setdash",
+};
+
+impl Dialect {
+    fn operators(&self) -> &'static Operators {
+        match self {
+            Dialect::Pdf => &PDF_OPERATORS,
+            Dialect::PostScript => &POSTSCRIPT_OPERATORS,
+        }
+    }
+
+    fn concat(&self, ts: Transform) -> String {
+        match self {
+            Dialect::Pdf => format!("{} cm\n", matrix(ts)),
+            Dialect::PostScript => format!("[{}] concat\n", matrix(ts)),
+        }
+    }
+}
+
+// Path operators for `path`. Quadratic segments are raised to cubics since neither format has them.
+fn path_ops(out: &mut String, path: &Path, dialect: Dialect) {
+    let ops = dialect.operators();
+    let mut last = (0.0, 0.0);
+    for segment in path.segments() {
+        match segment {
+            PathSegment::MoveTo(p) => {
+                out.push_str(&format!("{} {} {}\n", num(p.x), num(p.y), ops.move_to));
+                last = (p.x, p.y);
+            }
+            PathSegment::LineTo(p) => {
+                out.push_str(&format!("{} {} {}\n", num(p.x), num(p.y), ops.line_to));
+                last = (p.x, p.y);
+            }
+            PathSegment::QuadTo(c, p) => {
+                let c1 = (last.0 + 2.0 / 3.0 * (c.x - last.0), last.1 + 2.0 / 3.0 * (c.y - last.1));
+                let c2 = (p.x + 2.0 / 3.0 * (c.x - p.x), p.y + 2.0 / 3.0 * (c.y - p.y));
+                out.push_str(&format!(
+                    "{} {} {} {} {} {} {}\n",
+                    num(c1.0),
+                    num(c1.1),
+                    num(c2.0),
+                    num(c2.1),
+                    num(p.x),
+                    num(p.y),
+                    ops.curve_to
+                ));
+                last = (p.x, p.y);
+            }
+            PathSegment::CubicTo(c1, c2, p) => {
+                out.push_str(&format!(
+                    "{} {} {} {} {} {} {}\n",
+                    num(c1.x),
+                    num(c1.y),
+                    num(c2.x),
+                    num(c2.y),
+                    num(p.x),
+                    num(p.y),
+                    ops.curve_to
+                ));
+                last = (p.x, p.y);
+            }
+            PathSegment::Close => {
+                out.push_str(ops.close);
+                out.push('\n');
+            }
+        }
+    }
+}
+
+// A gradient as a shading dictionary, the same syntax in PDF and PostScript
+struct Shading {
+    // Gradient space to the space the shading is painted in
+    matrix: Transform,
+    // ShadingType 2 (axial) or 3 (radial) with its Coords
+    kind: u8,
+    coords: Vec<f32>,
+    stops: Vec<(f32, usvg::Color)>,
+}
+
+impl Shading {
+    fn from_paint(paint: &Paint, matrix: Transform) -> Option<Self> {
+        let (kind, coords, base) = match paint {
+            Paint::LinearGradient(gradient) => (2, vec![gradient.x1(), gradient.y1(), gradient.x2(), gradient.y2()], &***gradient),
+            Paint::RadialGradient(gradient) => (
+                3,
+                vec![gradient.fx(), gradient.fy(), 0.0, gradient.cx(), gradient.cy(), gradient.r().get()],
+                &***gradient,
+            ),
+            _ => return None,
+        };
+        let stops = base.stops().iter().map(|stop| (stop.offset().get(), stop.color())).collect();
+        Some(Shading { matrix: matrix.pre_concat(base.transform()), kind, coords, stops })
+    }
+
+    // One exponential function per pair of stops, stitched together
+    fn function(&self) -> String {
+        let mut stops = self.stops.clone();
+        if stops.is_empty() {
+            stops.push((0.0, usvg::Color::black()));
+        }
+        if stops.len() == 1 {
+            stops.push((1.0, stops[0].1));
+        }
+
+        let pieces: Vec<String> = stops
+            .windows(2)
+            .map(|pair| format!("<< /FunctionType 2 /Domain [0 1] /C0 [{}] /C1 [{}] /N 1 >>", rgb(pair[0].1), rgb(pair[1].1)))
+            .collect();
+        let bounds: Vec<String> = stops[1..stops.len() - 1].iter().map(|(offset, _)| num(*offset)).collect();
+        let encode = vec!["0 1"; pieces.len()].join(" ");
+        format!(
+            "<< /FunctionType 3 /Domain [{} {}] /Functions [{}] /Bounds [{}] /Encode [{}] >>",
+            num(stops[0].0),
+            num(stops[stops.len() - 1].0),
+            pieces.join(" "),
+            bounds.join(" "),
+            encode
+        )
+    }
+
+    fn dictionary(&self) -> String {
+        let coords: Vec<String> = self.coords.iter().map(|c| num(*c)).collect();
+        format!(
+            "<< /ShadingType {} /ColorSpace /DeviceRGB /Coords [{}] /Function {} /Extend [true true] >>",
+            self.kind,
+            coords.join(" "),
+            self.function()
+        )
+    }
+
+    // PDF shading pattern, `matrix` mapping to the default page space
+    fn pattern(&self) -> String {
+        format!("<< /Type /Pattern /PatternType 2 /Matrix [{}] /Shading {} >>", matrix(self.matrix), self.dictionary())
+    }
+}
+
+// A page of vector drawing, with the resources a PDF page refers to
+pub struct Canvas {
+    dialect: Dialect,
+    height: f32,
+    content: String,
+    // Fill and stroke opacity of each /GSn
+    states: Vec<(String, String)>,
+    shadings: Vec<Shading>,
+    // PostScript has no transparency, set when something was drawn opaque
+    flattened_transparency: bool,
+}
+
+impl Canvas {
+    // Coordinates on the canvas run top-down like in SVG
+    pub fn new(dialect: Dialect, height: f32) -> Self {
+        let mut canvas = Canvas {
+            dialect,
+            height,
+            content: String::new(),
+            states: Vec::new(),
+            shadings: Vec::new(),
+            flattened_transparency: false,
+        };
+        canvas.content.push_str(&dialect.concat(canvas.page_transform()));
+        canvas
+    }
+
+    // From canvas coordinates to the default page space
+    fn page_transform(&self) -> Transform {
+        Transform::from_row(1.0, 0.0, 0.0, -1.0, 0.0, self.height)
+    }
+
+    pub fn flattened_transparency(&self) -> bool {
+        self.flattened_transparency
+    }
+
+    pub(crate) fn content(&self) -> &str {
+        &self.content
+    }
+
+    fn op(&mut self, op: &str) {
+        self.content.push_str(op);
+        self.content.push('\n');
+    }
+
+    fn opacity(&mut self, fill: f32, stroke: f32) {
+        if self.dialect == Dialect::PostScript {
+            self.flattened_transparency |= fill < 1.0 || stroke < 1.0;
+            return;
+        }
+
+        let key = (num(fill), num(stroke));
+        let index = match self.states.iter().position(|state| *state == key) {
+            Some(index) => index,
+            None => {
+                self.states.push(key);
+                self.states.len() - 1
+            }
+        };
+        self.op(&format!("/GS{} gs", index));
+    }
+
+    fn stroke_style(&mut self, stroke: &usvg::Stroke) {
+        let ops = self.dialect.operators();
+        let cap = match stroke.linecap() {
+            LineCap::Butt => 0,
+            LineCap::Round => 1,
+            LineCap::Square => 2,
+        };
+        let join = match stroke.linejoin() {
+            LineJoin::Miter | LineJoin::MiterClip => 0,
+            LineJoin::Round => 1,
+            LineJoin::Bevel => 2,
+        };
+        self.op(&format!("{} {}", num(stroke.width().get()), ops.line_width));
+        self.op(&format!("{} {} {} {} {} {}", cap, ops.line_cap, join, ops.line_join, num(stroke.miterlimit().get()), ops.miter_limit));
+        if let Some(dashes) = stroke.dasharray() {
+            let dashes: Vec<String> = dashes.iter().map(|d| num(*d)).collect();
+            self.op(&format!("[{}] {} {}", dashes.join(" "), num(stroke.dashoffset()), ops.dash));
+        }
+    }
+
+    // Draw the whole document with `ts` mapping its size onto the canvas
+    pub fn draw_tree(&mut self, tree: &Tree, ts: Transform) {
+        self.draw_group(tree.root(), ts, 1.0);
+    }
+
+    fn draw_group(&mut self, group: &Group, ts: Transform, opacity: f32) {
+        let ops = self.dialect.operators();
+        for child in group.children() {
+            match child {
+                Node::Group(child) => {
+                    self.op(ops.save);
+                    if let Some(clip) = child.clip_path() {
+                        self.clip(clip, ts.pre_concat(child.abs_transform()));
+                    }
+                    self.draw_group(child, ts, opacity * child.opacity().get());
+                    self.op(ops.restore);
+                }
+                Node::Path(path) => self.draw_path(path, ts, opacity),
+                // resvg is built without fonts, so text never reaches the tree. Both are
+                // listed by unsupported_features, so exports can warn about them.
+                Node::Text(_) | Node::Image(_) => {}
+            }
+        }
+    }
+
+    fn draw_path(&mut self, path: &usvg::Path, ts: Transform, opacity: f32) {
+        if !path.is_visible() {
+            return;
+        }
+        let ops = self.dialect.operators();
+        let ts = ts.pre_concat(path.abs_transform());
+
+        // Patterns aren't supported, such paints are left out
+        let fill = path.fill().filter(|fill| !matches!(fill.paint(), Paint::Pattern(_)));
+        let stroke = path.stroke().filter(|stroke| !matches!(stroke.paint(), Paint::Pattern(_)));
+        if fill.is_none() && stroke.is_none() {
+            return;
+        }
+
+        let mut data = String::new();
+        path_ops(&mut data, path.data(), self.dialect);
+
+        self.op(ops.save);
+        self.opacity(
+            fill.map(|fill| fill.opacity().get()).unwrap_or(1.0) * opacity,
+            stroke.map(|stroke| stroke.opacity().get()).unwrap_or(1.0) * opacity,
+        );
+        self.content.push_str(&self.dialect.concat(ts));
+
+        let stroke_first = path.paint_order() == usvg::PaintOrder::StrokeAndFill;
+        if stroke_first {
+            if let Some(stroke) = stroke {
+                self.stroke_path(stroke, &data, ts);
+            }
+        }
+        if let Some(fill) = fill {
+            self.fill_path(fill, &data, ts);
+        }
+        if !stroke_first {
+            if let Some(stroke) = stroke {
+                self.stroke_path(stroke, &data, ts);
+            }
+        }
+        self.op(ops.restore);
+    }
+
+    fn fill_path(&mut self, fill: &usvg::Fill, data: &str, ts: Transform) {
+        let ops = self.dialect.operators();
+        let even_odd = fill.rule() == FillRule::EvenOdd;
+        match (fill.paint(), self.dialect) {
+            (Paint::Color(color), Dialect::Pdf) => self.op(&format!("{} rg", rgb(*color))),
+            (Paint::Color(color), Dialect::PostScript) => self.op(&format!("{} setrgbcolor", rgb(*color))),
+            (gradient, Dialect::Pdf) => {
+                let Some(name) = self.pattern(gradient, ts) else { return };
+                self.op(&format!("/Pattern cs {} scn", name));
+            }
+            (gradient, Dialect::PostScript) => {
+                // Clip to the shape and paint the shading over it
+                let clip = if even_odd { ops.even_odd_clip } else { ops.clip };
+                self.shade(gradient, &format!("{}{}", data, clip));
+                return;
+            }
+        }
+        self.content.push_str(data);
+        self.op(if even_odd { ops.even_odd_fill } else { ops.fill });
+    }
+
+    fn stroke_path(&mut self, stroke: &usvg::Stroke, data: &str, ts: Transform) {
+        let ops = self.dialect.operators();
+        self.stroke_style(stroke);
+        match (stroke.paint(), self.dialect) {
+            (Paint::Color(color), Dialect::Pdf) => self.op(&format!("{} RG", rgb(*color))),
+            (Paint::Color(color), Dialect::PostScript) => self.op(&format!("{} setrgbcolor", rgb(*color))),
+            (gradient, Dialect::Pdf) => {
+                let Some(name) = self.pattern(gradient, ts) else { return };
+                self.op(&format!("/Pattern CS {} SCN", name));
+            }
+            (gradient, Dialect::PostScript) => {
+                self.shade(gradient, &format!("{}strokepath {}", data, ops.clip));
+                return;
+            }
+        }
+        self.content.push_str(data);
+        self.op(ops.stroke);
+    }
+
+    // PDF pattern for a gradient painted under `ts`, returns its resource name
+    fn pattern(&mut self, gradient: &Paint, ts: Transform) -> Option<String> {
+        let shading = Shading::from_paint(gradient, self.page_transform().pre_concat(ts))?;
+        self.shadings.push(shading);
+        Some(format!("/P{}", self.shadings.len() - 1))
+    }
+
+    // PostScript shfill inside `clip`, in the current coordinate system
+    fn shade(&mut self, gradient: &Paint, clip: &str) {
+        let ops = self.dialect.operators();
+        let Some(shading) = Shading::from_paint(gradient, Transform::identity()) else {
+            return;
+        };
+        self.op(ops.save);
+        self.content.push_str(clip);
+        self.content.push('\n');
+        self.content.push_str(&self.dialect.concat(shading.matrix));
+        self.op(&format!("{} shfill", shading.dictionary()));
+        self.op(ops.restore);
+    }
+
+    // Intersect the clip with the union of the clip path's children
+    fn clip(&mut self, clip: &ClipPath, ts: Transform) {
+        let ops = self.dialect.operators();
+        let mut paths = Vec::new();
+        collect_clip_paths(clip.root(), ts.pre_concat(clip.transform()), &mut paths);
+
+        if paths.is_empty() {
+            // An empty clip path hides everything
+            self.op(&format!("0 0 {} {} {}", ops.move_to, ops.close, ops.clip));
+        } else {
+            for (path, _) in &paths {
+                path_ops(&mut self.content, path, self.dialect);
+            }
+            let even_odd = paths.len() == 1 && paths[0].1 == FillRule::EvenOdd;
+            self.op(if even_odd { ops.even_odd_clip } else { ops.clip });
+        }
+
+        if let Some(inner) = clip.clip_path() {
+            self.clip(inner, ts);
+        }
+    }
+
+    // Filled square with a light gray outline, for color swatches
+    pub fn swatch(&mut self, x: f32, y: f32, size: f32, color: usvg::Color) {
+        let ops = self.dialect.operators();
+        let Some(rect) = Rect::from_xywh(x, y, size, size) else {
+            return;
+        };
+        let mut data = String::new();
+        path_ops(&mut data, &PathBuilder::from_rect(rect), self.dialect);
+
+        self.op(ops.save);
+        self.opacity(1.0, 1.0);
+        match self.dialect {
+            Dialect::Pdf => self.op(&format!("{} rg 0.8 0.8 0.8 RG", rgb(color))),
+            Dialect::PostScript => self.op(&format!("{} setrgbcolor", rgb(color))),
+        }
+        self.content.push_str(&data);
+        self.op(ops.fill);
+        if self.dialect == Dialect::PostScript {
+            self.op("0.8 setgray");
+        }
+        self.op(&format!("0.5 {}", ops.line_width));
+        self.content.push_str(&data);
+        self.op(ops.stroke);
+        self.op(ops.restore);
+    }
+
+    // Gray Helvetica, `x` and `y` being the start of the baseline. Characters
+    // outside Latin-1 (ASCII in PostScript) are replaced with "?".
+    pub fn text(&mut self, x: f32, y: f32, size: f32, text: &str) {
+        let latin1 = self.dialect == Dialect::Pdf;
+        let escaped: String = text
+            .chars()
+            .map(|c| match c {
+                '(' | ')' | '\\' => format!("\\{}", c),
+                c if c.is_ascii() && !c.is_ascii_control() => c.to_string(),
+                c if latin1 && (0xA0..=0xFF).contains(&(c as u32)) => format!("\\{:03o}", c as u32),
+                _ => "?".to_string(),
+            })
+            .collect();
+
+        // The canvas is flipped, so flip the glyphs back upright
+        match self.dialect {
+            Dialect::Pdf => self.op(&format!(
+                "BT /F1 {} Tf 1 0 0 -1 {} {} Tm 0.35 0.35 0.35 rg ({}) Tj ET",
+                num(size),
+                num(x),
+                num(y),
+                escaped
+            )),
+            Dialect::PostScript => self.op(&format!(
+                "gsave /Helvetica findfont {} scalefont setfont 0.35 setgray {} {} moveto 1 -1 scale ({}) show grestore",
+                num(size),
+                num(x),
+                num(y),
+                escaped
+            )),
+        }
+    }
+}
+
+// Clip path children, moved into the space of the clipped group
+fn collect_clip_paths(group: &Group, ts: Transform, paths: &mut Vec<(Path, FillRule)>) {
+    for child in group.children() {
+        match child {
+            Node::Path(path) => {
+                let rule = path.fill().map(|fill| fill.rule()).unwrap_or(FillRule::NonZero);
+                if let Some(data) = path.data().clone().transform(ts) {
+                    paths.push((data, rule));
+                }
+            }
+            Node::Group(child) => collect_clip_paths(child, ts.pre_concat(child.transform()), paths),
+            _ => {}
+        }
+    }
+}
+
+// A PDF file built page by page
+pub struct PdfDocument {
+    objects: Vec<String>,
+    streams: Vec<(usize, Vec<u8>)>,
+    pages: Vec<usize>,
+}
+
+impl Default for PdfDocument {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PdfDocument {
+    pub fn new() -> Self {
+        // Catalog and page tree are filled in by finish()
+        PdfDocument { objects: vec![String::new(), String::new()], streams: Vec::new(), pages: Vec::new() }
+    }
+
+    fn add_object(&mut self, body: String) -> usize {
+        self.objects.push(body);
+        self.objects.len()
+    }
+
+    // Compressed stream; `dictionary` entries, if any, end with a space and get /Length and /Filter added
+    fn add_stream(&mut self, dictionary: &str, data: &[u8]) -> usize {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        let compressed = encoder.write_all(data).and_then(|_| encoder.finish()).unwrap_or_default();
+        let id = self.add_object(format!("<< {}/Length {} /Filter /FlateDecode >>", dictionary, compressed.len()));
+        self.streams.push((id, compressed));
+        id
+    }
+
+    pub fn add_page(&mut self, width: f32, height: f32, canvas: Canvas) {
+        let content = self.add_stream("", canvas.content.as_bytes());
+
+        let states: Vec<String> = canvas
+            .states
+            .iter()
+            .enumerate()
+            .map(|(index, (fill, stroke))| format!("/GS{} << /Type /ExtGState /ca {} /CA {} >>", index, fill, stroke))
+            .collect();
+        let patterns: Vec<String> = canvas
+            .shadings
+            .iter()
+            .enumerate()
+            .map(|(index, shading)| format!("/P{} {} 0 R", index, self.add_object(shading.pattern())))
+            .collect();
+
+        let page = self.add_object(format!(
+            "<< /Type /Page /Parent {} 0 R /MediaBox [0 0 {} {}] /Contents {} 0 R /Resources << /ExtGState << {} >> /Pattern << {} >> /Font << /F1 << /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >> >> >> >>",
+            PAGES,
+            num(width),
+            num(height),
+            content,
+            states.join(" "),
+            patterns.join(" ")
+        ));
+        self.pages.push(page);
+    }
+
+    pub fn finish(mut self) -> Vec<u8> {
+        self.objects[CATALOG - 1] = format!("<< /Type /Catalog /Pages {} 0 R >>", PAGES);
+        let kids: Vec<String> = self.pages.iter().map(|page| format!("{} 0 R", page)).collect();
+        self.objects[PAGES - 1] = format!("<< /Type /Pages /Kids [{}] /Count {} >>", kids.join(" "), self.pages.len());
+
+        let mut out: Vec<u8> = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+        let mut offsets = Vec::with_capacity(self.objects.len());
+        for (index, body) in self.objects.iter().enumerate() {
+            let id = index + 1;
+            offsets.push(out.len());
+            out.extend_from_slice(format!("{} 0 obj\n{}\n", id, body).as_bytes());
+            if let Some((_, data)) = self.streams.iter().find(|(stream, _)| *stream == id) {
+                out.extend_from_slice(b"stream\n");
+                out.extend_from_slice(data);
+                out.extend_from_slice(b"\nendstream\n");
+            }
+            out.extend_from_slice(b"endobj\n");
+        }
+
+        let xref = out.len();
+        out.extend_from_slice(format!("xref\n0 {}\n0000000000 65535 f \n", self.objects.len() + 1).as_bytes());
+        for offset in offsets {
+            out.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+        }
+        out.extend_from_slice(
+            format!("trailer\n<< /Size {} /Root {} 0 R >>\nstartxref\n{}\n%%EOF\n", self.objects.len() + 1, CATALOG, xref).as_bytes(),
+        );
+        out
+    }
+}
+
+// What the PDF and EPS writers leave out or draw differently, for export reports
+pub fn unsupported_features(source: &str, tree: &Tree) -> Vec<String> {
+    let mut found = dropped_features(source, tree);
+    if fades_groups(tree.root()) {
+        found.push(GROUP_OPACITY.to_string());
+    }
+    let mut stops = tree
+        .linear_gradients()
+        .iter()
+        .map(|gradient| &***gradient)
+        .chain(tree.radial_gradients().iter().map(|gradient| &***gradient))
+        .flat_map(|gradient| gradient.stops());
+    if stops.any(|stop| stop.opacity().get() < 1.0) {
+        found.push("Gradient transparency is drawn opaque".to_string());
+    }
+    found
+}
+
+// Whether a semi-transparent group holds more than one shape, which draw_group fades one by one
+fn fades_groups(group: &Group) -> bool {
+    group.children().iter().any(|child| match child {
+        Node::Group(child) => (child.opacity().get() < 1.0 && child.children().len() > 1) || fades_groups(child),
+        _ => false,
+    })
+}
+
+// Single page PDF the size of the document, in points
+pub fn tree_to_pdf(tree: &Tree) -> Vec<u8> {
+    let size = tree.size();
+    let mut canvas = Canvas::new(Dialect::Pdf, size.height());
+    canvas.draw_tree(tree, Transform::identity());
+
+    let mut document = PdfDocument::new();
+    document.add_page(size.width(), size.height(), canvas);
+    document.finish()
+}
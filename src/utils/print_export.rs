@@ -0,0 +1,313 @@
+use crate::utils::eps;
+use crate::utils::pdf::{self, Canvas, Dialect, PdfDocument};
+use crate::utils::svg_render;
+use resvg::tiny_skia::Transform;
+use resvg::usvg::Tree;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Space around the page content, in points
+const MARGIN: f32 = 36.0;
+const LABEL_SIZE: f32 = 7.0;
+const TITLE_SIZE: f32 = 11.0;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum PrintFormat {
+    Pdf,
+    Eps,
+}
+
+impl PrintFormat {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PrintFormat::Pdf => "PDF",
+            PrintFormat::Eps => "EPS",
+        }
+    }
+
+    fn extension(&self) -> &'static str {
+        match self {
+            PrintFormat::Pdf => "pdf",
+            PrintFormat::Eps => "eps",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum PdfLayout {
+    // One file per asset, the only choice for EPS
+    Separate,
+    OnePerPage,
+    ContactSheet,
+}
+
+impl PdfLayout {
+    pub const ALL: [PdfLayout; 3] = [PdfLayout::Separate, PdfLayout::OnePerPage, PdfLayout::ContactSheet];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PdfLayout::Separate => "One file per asset",
+            PdfLayout::OnePerPage => "One page per asset",
+            PdfLayout::ContactSheet => "Contact sheet",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum PageSize {
+    // The size of the asset, no margins or labels
+    Fit,
+    A4,
+    Letter,
+}
+
+impl PageSize {
+    pub const ALL: [PageSize; 3] = [PageSize::Fit, PageSize::A4, PageSize::Letter];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PageSize::Fit => "Fit to asset",
+            PageSize::A4 => "A4",
+            PageSize::Letter => "US Letter",
+        }
+    }
+
+    // Width and height in points, contact sheets use A4 for Fit
+    pub(crate) fn dimensions(&self) -> Option<(f32, f32)> {
+        match self {
+            PageSize::Fit => None,
+            PageSize::A4 => Some((595.0, 842.0)),
+            PageSize::Letter => Some((612.0, 792.0)),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(default)]
+pub struct PrintOptions {
+    pub format: PrintFormat,
+    pub layout: PdfLayout,
+    pub page_size: PageSize,
+    // Icons per row on a contact sheet
+    pub columns: usize,
+    // File names under the icons
+    pub labels: bool,
+}
+
+impl Default for PrintOptions {
+    fn default() -> Self {
+        Self {
+            format: PrintFormat::Pdf,
+            layout: PdfLayout::Separate,
+            page_size: PageSize::Fit,
+            columns: 6,
+            labels: true,
+        }
+    }
+}
+
+impl PrintOptions {
+    // Whether everything goes into one file rather than a folder
+    pub fn single_file(&self) -> bool {
+        self.format == PrintFormat::Pdf && self.layout != PdfLayout::Separate
+    }
+}
+
+#[derive(Default)]
+pub struct PrintReport {
+    pub exported: usize,
+    pub pages: usize,
+    // File name with what couldn't be carried over
+    pub issues: Vec<(String, Vec<String>)>,
+    pub errors: Vec<String>,
+}
+
+impl PrintReport {
+    pub fn summary(&self) -> String {
+        let mut summary = format!("{} assets", self.exported);
+        if self.pages > 0 {
+            summary.push_str(&format!(" on {} pages", self.pages));
+        }
+        if !self.issues.is_empty() {
+            summary.push_str(&format!(", {} with unsupported features", self.issues.len()));
+        }
+        if !self.errors.is_empty() {
+            summary.push_str(&format!(", {} failed", self.errors.len()));
+        }
+        summary
+    }
+}
+
+struct Asset {
+    name: String,
+    tree: Tree,
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default()
+}
+
+// Parse every asset, noting what the vector writers leave out
+fn load(assets: &[PathBuf], report: &mut PrintReport) -> Vec<Asset> {
+    let mut loaded = Vec::new();
+    for path in assets {
+        let name = file_name(path);
+        let tree = fs::read_to_string(path).map_err(|e| e.to_string()).and_then(|source| {
+            let tree = svg_render::parse_tree(&source)?;
+            let dropped = pdf::unsupported_features(&source, &tree);
+            if !dropped.is_empty() {
+                report.issues.push((name.clone(), dropped));
+            }
+            Ok(tree)
+        });
+        match tree {
+            Ok(tree) => loaded.push(Asset { name, tree }),
+            Err(e) => report.errors.push(format!("{}: {}", name, e)),
+        }
+    }
+    loaded
+}
+
+// Scale and center the tree inside a box
+fn fit(tree: &Tree, x: f32, y: f32, width: f32, height: f32) -> Transform {
+    let size = tree.size();
+    let scale = (width / size.width()).min(height / size.height());
+    let dx = x + (width - size.width() * scale) / 2.0;
+    let dy = y + (height - size.height() * scale) / 2.0;
+    Transform::from_scale(scale, scale).post_translate(dx, dy)
+}
+
+// Cut a label down to what fits in `width`, Helvetica being about half as wide as high
+pub(crate) fn truncate(text: &str, width: f32, size: f32) -> String {
+    let max = (width / (size * 0.5)).floor() as usize;
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let kept: String = text.chars().take(max.saturating_sub(3)).collect();
+    format!("{}...", kept)
+}
+
+// One PDF or EPS file per asset, named after it, in `output_dir`
+pub fn export_separate(assets: &[PathBuf], output_dir: &Path, options: &PrintOptions) -> Result<PrintReport, String> {
+    fs::create_dir_all(output_dir).map_err(|e| e.to_string())?;
+    let mut report = PrintReport::default();
+
+    for asset in load(assets, &mut report) {
+        let stem = Path::new(&asset.name).file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+        let output = output_dir.join(format!("{}.{}", stem, options.format.extension()));
+
+        let data = match options.format {
+            PrintFormat::Pdf => pdf::tree_to_pdf(&asset.tree),
+            PrintFormat::Eps => {
+                let file = eps::tree_to_eps(&asset.tree, &stem);
+                if file.flattened_transparency {
+                    let issue = "Transparency is drawn opaque in EPS".to_string();
+                    match report.issues.iter_mut().find(|(name, _)| *name == asset.name) {
+                        Some((_, issues)) => issues.push(issue),
+                        None => report.issues.push((asset.name.clone(), vec![issue])),
+                    }
+                }
+                file.data.into_bytes()
+            }
+        };
+
+        match fs::write(&output, data) {
+            Ok(_) => report.exported += 1,
+            Err(e) => report.errors.push(format!("{}: {}", asset.name, e)),
+        }
+    }
+
+    Ok(report)
+}
+
+// All assets in one PDF, each on its own page or laid out on contact sheets
+pub fn export_document(assets: &[PathBuf], output: &Path, options: &PrintOptions, title: &str) -> Result<PrintReport, String> {
+    let mut report = PrintReport::default();
+    let loaded = load(assets, &mut report);
+    if loaded.is_empty() {
+        return Err("Nothing to export".to_string());
+    }
+
+    let mut document = PdfDocument::new();
+    match options.layout {
+        PdfLayout::ContactSheet => report.pages = contact_sheets(&mut document, &loaded, options, title),
+        _ => {
+            for asset in &loaded {
+                one_page(&mut document, asset, options);
+            }
+            report.pages = loaded.len();
+        }
+    }
+    report.exported = loaded.len();
+
+    fs::write(output, document.finish()).map_err(|e| e.to_string())?;
+    Ok(report)
+}
+
+fn one_page(document: &mut PdfDocument, asset: &Asset, options: &PrintOptions) {
+    let Some((width, height)) = options.page_size.dimensions() else {
+        let size = asset.tree.size();
+        let mut canvas = Canvas::new(Dialect::Pdf, size.height());
+        canvas.draw_tree(&asset.tree, Transform::identity());
+        document.add_page(size.width(), size.height(), canvas);
+        return;
+    };
+
+    let label_space = if options.labels { TITLE_SIZE * 3.0 } else { 0.0 };
+    let mut canvas = Canvas::new(Dialect::Pdf, height);
+    let ts = fit(&asset.tree, MARGIN, MARGIN, width - MARGIN * 2.0, height - MARGIN * 2.0 - label_space);
+    canvas.draw_tree(&asset.tree, ts);
+    if options.labels {
+        let label = truncate(&asset.name, width - MARGIN * 2.0, TITLE_SIZE);
+        canvas.text(MARGIN, height - MARGIN, TITLE_SIZE, &label);
+    }
+    document.add_page(width, height, canvas);
+}
+
+// Grid of icons with their names, returns the number of pages
+fn contact_sheets(document: &mut PdfDocument, assets: &[Asset], options: &PrintOptions, title: &str) -> usize {
+    let (width, height) = options.page_size.dimensions().unwrap_or((595.0, 842.0));
+    let columns = options.columns.max(1);
+    let header = TITLE_SIZE * 2.5;
+
+    let cell_width = (width - MARGIN * 2.0) / columns as f32;
+    let padding = (cell_width * 0.12).max(2.0);
+    let label_space = if options.labels { LABEL_SIZE * 2.0 } else { 0.0 };
+    let cell_height = cell_width + label_space;
+    let rows = (((height - MARGIN * 2.0 - header) / cell_height).floor() as usize).max(1);
+
+    let pages: Vec<&[Asset]> = assets.chunks(columns * rows).collect();
+    for (page, chunk) in pages.iter().enumerate() {
+        let mut canvas = Canvas::new(Dialect::Pdf, height);
+        let heading = format!("{} - page {} of {}", title, page + 1, pages.len());
+        canvas.text(MARGIN, MARGIN + TITLE_SIZE, TITLE_SIZE, &heading);
+
+        for (index, asset) in chunk.iter().enumerate() {
+            let x = MARGIN + (index % columns) as f32 * cell_width;
+            let y = MARGIN + header + (index / columns) as f32 * cell_height;
+            let icon = cell_width - padding * 2.0;
+            canvas.draw_tree(&asset.tree, fit(&asset.tree, x + padding, y + padding, icon, icon));
+            if options.labels {
+                let label = truncate(&asset.name, cell_width - 4.0, LABEL_SIZE);
+                canvas.text(x + 2.0, y + cell_width + LABEL_SIZE, LABEL_SIZE, &label);
+            }
+        }
+        document.add_page(width, height, canvas);
+    }
+    pages.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncates_long_labels() {
+        // 10 characters of 5 points fit in 50 points
+        assert_eq!(truncate("arrow-left", 50.0, 10.0), "arrow-left");
+        assert_eq!(truncate("arrow-left.svg", 50.0, 10.0), "arrow-l...");
+        assert_eq!(truncate("äöüäöüäöüä", 50.0, 10.0), "äöüäöüäöüä");
+        assert_eq!(truncate("arrow", 10.0, 10.0), "...");
+    }
+}
@@ -0,0 +1,94 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Oldest entries are dropped beyond this
+pub const RECENT_LIMIT: usize = 100;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum RecentAction {
+    Opened,
+    Edited,
+    Copied,
+    Exported,
+}
+
+impl RecentAction {
+    pub fn label(&self) -> &'static str {
+        match self {
+            RecentAction::Opened => "Opened",
+            RecentAction::Edited => "Edited",
+            RecentAction::Copied => "Copied",
+            RecentAction::Exported => "Exported",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RecentEntry {
+    pub path: PathBuf,
+    pub action: RecentAction,
+    // Seconds since the Unix epoch
+    pub timestamp: u64,
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+// Newest first, one entry per asset
+pub fn push(recent: &mut Vec<RecentEntry>, path: &Path, action: RecentAction) {
+    recent.retain(|entry| entry.path != path);
+    recent.insert(
+        0,
+        RecentEntry {
+            path: path.to_path_buf(),
+            action,
+            timestamp: now(),
+        },
+    );
+    recent.truncate(RECENT_LIMIT);
+}
+
+// New location of `path` after `old_path` was renamed, also for files below a renamed folder
+pub fn renamed(path: &Path, old_path: &Path, new_path: &Path) -> Option<PathBuf> {
+    path.strip_prefix(old_path).ok().map(|rest| {
+        if rest.as_os_str().is_empty() {
+            new_path.to_path_buf()
+        } else {
+            new_path.join(rest)
+        }
+    })
+}
+
+pub fn rename(favorites: &mut [PathBuf], recent: &mut [RecentEntry], old_path: &Path, new_path: &Path) {
+    for path in favorites.iter_mut().chain(recent.iter_mut().map(|entry| &mut entry.path)) {
+        if let Some(moved) = renamed(path, old_path, new_path) {
+            *path = moved;
+        }
+    }
+}
+
+pub fn remove(favorites: &mut Vec<PathBuf>, recent: &mut Vec<RecentEntry>, path: &Path) {
+    favorites.retain(|favorite| !favorite.starts_with(path));
+    recent.retain(|entry| !entry.path.starts_with(path));
+}
+
+// "just now", "5 min ago", "3 h ago", "2 d ago", then the date
+pub fn format_age(timestamp: u64) -> String {
+    let age = now().saturating_sub(timestamp);
+    match age {
+        0..=59 => "just now".to_string(),
+        60..=3_599 => format!("{} min ago", age / 60),
+        3_600..=86_399 => format!("{} h ago", age / 3_600),
+        86_400..=604_799 => format!("{} d ago", age / 86_400),
+        _ => crate::utils::history::format_timestamp(timestamp)
+            .split(' ')
+            .next()
+            .unwrap_or_default()
+            .to_string(),
+    }
+}
@@ -0,0 +1,455 @@
+use crate::utils::svg_document;
+use std::collections::HashMap;
+use std::fs;
+use std::ops::Range;
+use std::path::PathBuf;
+use xmlparser::{ElementEnd, EntityDefinition, Token, Tokenizer};
+
+// Elements that run code or embed HTML, removed with everything inside them
+const ACTIVE_ELEMENTS: [&str; 2] = ["script", "foreignObject"];
+// Animations that can swap in a link or an event handler after sanitizing
+const ANIMATION_ELEMENTS: [&str; 2] = ["set", "animate"];
+// Bytes that entity references may expand to in one file, well above what a real icon
+// needs and far below what a "billion laughs" file asks for
+const ENTITY_BUDGET: usize = 64 * 1024;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Threat {
+    // Code that runs when the file is opened or clicked
+    Script,
+    // Loads something from outside the file
+    ExternalReference,
+}
+
+#[derive(Clone, Debug)]
+pub struct Finding {
+    pub threat: Threat,
+    pub message: String,
+    // Byte offset of the element or attribute
+    pub pos: usize,
+}
+
+#[derive(Default, Debug)]
+pub struct SanitizeReport {
+    // Rewritten files, with the content before and what was removed
+    pub cleaned: Vec<(PathBuf, String, Vec<String>)>,
+    pub errors: Vec<String>,
+}
+
+impl SanitizeReport {
+    pub fn summary(&self) -> String {
+        let removed: usize = self.cleaned.iter().map(|(_, _, findings)| findings.len()).sum();
+        let mut summary = format!("{} items removed from {} files", removed, self.cleaned.len());
+        if !self.errors.is_empty() {
+            summary.push_str(&format!(", {} failed", self.errors.len()));
+        }
+        summary
+    }
+}
+
+// What sanitize() would remove, in document order. Empty for a clean file.
+pub fn scan(source: &str) -> Result<Vec<Finding>, String> {
+    active_content(source).map(|(_, findings)| findings)
+}
+
+// The messages of scan(), for badges and reports
+pub fn find_active_content(source: &str) -> Result<Vec<String>, String> {
+    scan(source).map(|findings| findings.into_iter().map(|finding| finding.message).collect())
+}
+
+// Remove scripts, <foreignObject>, event handlers, javascript: links and references
+// to other files. The rest of the file is kept byte for byte.
+pub fn sanitize(source: &str) -> Result<(String, Vec<String>), String> {
+    svg_document::parse(source).map_err(|e| e.to_string())?;
+    let (ranges, findings) = active_content(source)?;
+
+    let mut sanitized = source.to_string();
+    for range in ranges.iter().rev() {
+        sanitized.replace_range(range.clone(), "");
+    }
+    Ok((sanitized, findings.into_iter().map(|finding| finding.message).collect()))
+}
+
+// Sanitize the files in place, skipping the ones that are already clean
+pub fn sanitize_files(paths: &[PathBuf]) -> SanitizeReport {
+    let mut report = SanitizeReport::default();
+    for path in paths {
+        let result = fs::read_to_string(path).map_err(|e| e.to_string()).and_then(|before| {
+            let (after, findings) = sanitize(&before)?;
+            if !findings.is_empty() {
+                fs::write(path, &after).map_err(|e| e.to_string())?;
+            }
+            Ok((before, findings))
+        });
+        match result {
+            Ok((_, findings)) if findings.is_empty() => {}
+            Ok((before, findings)) => report.cleaned.push((path.clone(), before, findings)),
+            Err(e) => report.errors.push(format!("{}: {}", path.display(), e)),
+        }
+    }
+    report
+}
+
+// An element whose end hasn't been seen yet
+struct Open {
+    local: String,
+    // Where the tag starts, for reporting
+    pos: usize,
+    // Start of the removal range, including the whitespace in front
+    start: usize,
+    // Why the whole element goes, if it does
+    remove: Option<(Threat, String)>,
+    // Text of a <style> element
+    css: String,
+}
+
+// Byte ranges to remove, sorted and never overlapping, and what they hold
+fn active_content(source: &str) -> Result<(Vec<Range<usize>>, Vec<Finding>), String> {
+    let mut entities: HashMap<String, String> = HashMap::new();
+    let mut stack: Vec<Open> = Vec::new();
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    let mut findings: Vec<Finding> = Vec::new();
+    let mut budget = ENTITY_BUDGET;
+
+    for token in Tokenizer::from(source) {
+        match token.map_err(|e| e.to_string())? {
+            // Internal entities can spell out "javascript:" in attribute values.
+            // Nested entities are refused, they only serve to blow up the expansion.
+            Token::EntityDeclaration { name, definition: EntityDefinition::EntityValue(value), .. } => {
+                if refers_to_entity(value.as_str()) {
+                    return Err(format!("Entity '{}' refers to another entity", name.as_str()));
+                }
+                let value = decode(value.as_str(), &entities, &mut budget)?;
+                entities.insert(name.as_str().to_string(), value);
+            }
+            Token::ElementStart { local, span, .. } => {
+                let local = local.as_str();
+                let remove = ACTIVE_ELEMENTS.contains(&local).then(|| (Threat::Script, format!("<{}> element", local)));
+                stack.push(Open {
+                    local: local.to_string(),
+                    pos: span.start(),
+                    start: with_leading_space(source, span.start()),
+                    remove,
+                    css: String::new(),
+                });
+            }
+            Token::Attribute { prefix, local, value, span } => {
+                let Some(element) = stack.last_mut() else {
+                    continue;
+                };
+                let name = local.as_str();
+                let attribute = if prefix.is_empty() { name.to_string() } else { format!("{}:{}", prefix.as_str(), name) };
+                let raw = decode(value.as_str(), &entities, &mut budget)?;
+                let value = normalize(&raw);
+
+                if ANIMATION_ELEMENTS.contains(&element.local.as_str()) && name == "attributeName" {
+                    let target = local_name(&value);
+                    if target == "href" || is_event_handler(target) {
+                        element.remove = Some((Threat::Script, format!("<{}> of {}", element.local, raw.trim())));
+                    }
+                }
+
+                let finding = if is_event_handler(name) {
+                    Some((Threat::Script, format!("Event handler '{}'", attribute)))
+                } else if is_script_url(&value) {
+                    Some((Threat::Script, format!("javascript: link in '{}'", attribute)))
+                } else if name == "href" && is_external(&value) {
+                    Some((Threat::ExternalReference, format!("External {} {}", attribute, raw.trim())))
+                } else {
+                    let css = if name == "style" { unescape_css(&raw) } else { raw };
+                    external_url(&css).map(|target| (Threat::ExternalReference, format!("External reference {}", target)))
+                };
+
+                if let Some((threat, message)) = finding {
+                    ranges.push(with_leading_space(source, span.start())..span.end());
+                    findings.push(Finding { threat, message, pos: span.start() });
+                }
+            }
+            Token::Text { text } | Token::Cdata { text, .. } => {
+                if let Some(element) = stack.last_mut().filter(|element| element.local == "style") {
+                    element.css.push_str(text.as_str());
+                }
+            }
+            Token::ElementEnd { end, span } => {
+                if let ElementEnd::Open = end {
+                    continue;
+                }
+                let Some(mut element) = stack.pop() else {
+                    continue;
+                };
+                if element.local == "style" && element.remove.is_none() {
+                    element.remove = unsafe_stylesheet(&decode(&element.css, &entities, &mut budget)?);
+                }
+                if let Some((threat, message)) = element.remove {
+                    ranges.push(element.start..span.end());
+                    findings.push(Finding { threat, message, pos: element.pos });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    findings.sort_by_key(|finding| finding.pos);
+    Ok((merge(ranges), findings))
+}
+
+// Sort the ranges and fold the ones inside removed elements into them
+fn merge(mut ranges: Vec<Range<usize>>) -> Vec<Range<usize>> {
+    ranges.sort_by_key(|range| range.start);
+    let mut merged: Vec<Range<usize>> = Vec::new();
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start < last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+fn is_event_handler(name: &str) -> bool {
+    name.len() > 2 && name.to_ascii_lowercase().starts_with("on")
+}
+
+fn is_script_url(value: &str) -> bool {
+    value.contains("javascript:") || value.contains("vbscript:")
+}
+
+// Anything but a fragment of this file or an embedded image
+fn is_external(value: &str) -> bool {
+    !value.is_empty() && !value.starts_with('#') && !value.starts_with("data:image/")
+}
+
+fn local_name(name: &str) -> &str {
+    name.rsplit_once(':').map(|(_, local)| local).unwrap_or(name)
+}
+
+// Lowercase without the whitespace and control characters browsers skip in URLs,
+// so "Java&#x09;Script:" reads as "javascript:"
+fn normalize(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| !c.is_whitespace() && !c.is_control())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+// Replace character and entity references. Unknown entities are kept as they are.
+fn decode(text: &str, entities: &HashMap<String, String>, budget: &mut usize) -> Result<String, String> {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(end) = rest.find(';') else {
+            break;
+        };
+        let name = &rest[1..end];
+        let character = match name.strip_prefix('#') {
+            Some(hex) if hex.starts_with(['x', 'X']) => u32::from_str_radix(&hex[1..], 16).ok().and_then(char::from_u32),
+            Some(decimal) => decimal.parse().ok().and_then(char::from_u32),
+            None => None,
+        };
+        match (character, name) {
+            (Some(c), _) => out.push(c),
+            (None, "lt") => out.push('<'),
+            (None, "gt") => out.push('>'),
+            (None, "amp") => out.push('&'),
+            (None, "quot") => out.push('"'),
+            (None, "apos") => out.push('\''),
+            (None, _) => match entities.get(name) {
+                Some(value) => {
+                    *budget = budget
+                        .checked_sub(value.len())
+                        .ok_or_else(|| format!("Entities expand to more than {} KB", ENTITY_BUDGET / 1024))?;
+                    out.push_str(value);
+                }
+                None => out.push_str(&rest[..=end]),
+            },
+        }
+        rest = &rest[end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+// Whether the text uses a named entity other than the five predefined ones
+fn refers_to_entity(text: &str) -> bool {
+    text.split('&').skip(1).any(|reference| {
+        let name = reference.split(';').next().unwrap_or_default();
+        reference.contains(';') && !name.starts_with('#') && !matches!(name, "lt" | "gt" | "amp" | "quot" | "apos")
+    })
+}
+
+// Resolve CSS escapes ("\70" and "\p" both being "p") and drop comments
+fn unescape_css(css: &str) -> String {
+    let mut out = String::with_capacity(css.len());
+    let mut chars = css.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                let mut hex = String::new();
+                while hex.len() < 6 && chars.peek().is_some_and(|c| c.is_ascii_hexdigit()) {
+                    hex.extend(chars.next());
+                }
+                if hex.is_empty() {
+                    out.extend(chars.next());
+                } else {
+                    out.extend(u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32));
+                    // One whitespace ends the escape
+                    chars.next_if(|c| c.is_whitespace());
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = ' ';
+                for c in chars.by_ref() {
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    previous = c;
+                }
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+// Why a stylesheet has to go, if it pulls in other files or runs code
+fn unsafe_stylesheet(css: &str) -> Option<(Threat, String)> {
+    let css = unescape_css(css);
+    let lower = css.to_ascii_lowercase();
+    if lower.contains("@import") {
+        Some((Threat::ExternalReference, "<style> with @import".to_string()))
+    } else if is_script_url(&normalize(&css)) || lower.contains("expression(") {
+        Some((Threat::Script, "<style> with script".to_string()))
+    } else {
+        external_url(&css).map(|target| (Threat::ExternalReference, format!("<style> with external reference {}", target)))
+    }
+}
+
+// The first url(...) that doesn't point inside the document
+fn external_url(value: &str) -> Option<String> {
+    let mut rest = value;
+    while let Some(start) = rest.find("url(") {
+        rest = &rest[start + 4..];
+        let end = rest.find(')')?;
+        let target = rest[..end].trim().trim_matches(['"', '\'']);
+        if !target.starts_with('#') && !target.starts_with("data:") {
+            return Some(target.to_string());
+        }
+        rest = &rest[end..];
+    }
+    None
+}
+
+// Take the whitespace in front along, so no blank lines or double spaces stay behind
+fn with_leading_space(source: &str, start: usize) -> usize {
+    start - (source[..start].len() - source[..start].trim_end().len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SVG: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink">"##;
+
+    fn clean(body: &str) -> (String, Vec<String>) {
+        let (sanitized, findings) = sanitize(&format!("{}{}</svg>", SVG, body)).unwrap();
+        (sanitized[SVG.len()..sanitized.len() - "</svg>".len()].to_string(), findings)
+    }
+
+    #[test]
+    fn removes_scripts_and_foreign_objects() {
+        let (body, findings) = clean(r##"<script>alert(1)</script><foreignObject><div onclick="x()"/></foreignObject><rect/>"##);
+        assert_eq!(body, "<rect/>");
+        assert!(findings.contains(&"<script> element".to_string()));
+        assert!(findings.contains(&"<foreignObject> element".to_string()));
+    }
+
+    #[test]
+    fn removes_event_handlers() {
+        let (body, findings) = clean(r##"<rect width="2" onclick="x()" onLoad='y()'/>"##);
+        assert_eq!(body, r##"<rect width="2"/>"##);
+        assert_eq!(findings.len(), 2);
+    }
+
+    #[test]
+    fn removes_javascript_and_external_links() {
+        let (body, _) = clean(r##"<a xlink:href="javascript:alert(1)"><use href="icons.svg#x"/><use href="#ok"/></a>"##);
+        assert_eq!(body, r##"<a><use/><use href="#ok"/></a>"##);
+        let (body, _) = clean(r##"<image href="data:image/png;base64,AAAA"/><rect fill="url(https://x.test/p)"/>"##);
+        assert_eq!(body, r##"<image href="data:image/png;base64,AAAA"/><rect/>"##);
+    }
+
+    #[test]
+    fn removes_encoded_javascript() {
+        let (body, _) = clean(r##"<a href="&#106;avascript:alert(1)"/><a href="java&#x09;script:x"/><a href=" JavaScript:x"/>"##);
+        assert_eq!(body, "<a/><a/><a/>");
+    }
+
+    #[test]
+    fn removes_javascript_from_declared_entities() {
+        let source = r##"<!DOCTYPE svg [<!ENTITY js "&#106;avascript:alert(1)">]><svg xmlns="http://www.w3.org/2000/svg"><a href="&js;"/></svg>"##;
+        let (sanitized, findings) = sanitize(source).unwrap();
+        assert!(sanitized.ends_with("<a/></svg>"), "{}", sanitized);
+        assert_eq!(findings.len(), 1);
+    }
+
+    #[test]
+    fn refuses_nested_entities() {
+        let mut doctype = String::from(r#"<!ENTITY l0 "lol">"#);
+        for level in 1..10 {
+            doctype.push_str(&format!(r#"<!ENTITY l{} "{}">"#, level, format!("&l{};", level - 1).repeat(10)));
+        }
+        let source = format!(r#"<!DOCTYPE svg [{}]><svg xmlns="http://www.w3.org/2000/svg"><a href="&l9;"/></svg>"#, doctype);
+        let started = std::time::Instant::now();
+        assert!(scan(&source).is_err());
+        assert!(sanitize(&source).is_err());
+        assert!(started.elapsed() < std::time::Duration::from_secs(1));
+    }
+
+    #[test]
+    fn caps_entity_expansion() {
+        let entity = "x".repeat(1024);
+        let references = "&big;".repeat(100);
+        let source = format!(
+            r#"<!DOCTYPE svg [<!ENTITY big "{}">]><svg xmlns="http://www.w3.org/2000/svg"><text class="{}"/></svg>"#,
+            entity, references
+        );
+        assert!(scan(&source).is_err());
+    }
+
+    #[test]
+    fn removes_animations_of_links_and_handlers() {
+        let (body, findings) = clean(
+            r##"<a><set attributeName="href" to="&#106;avascript:alert(1)"/><animate attributeName="xlink:href" values="#a;https://x.test"/><animate attributeName="onclick" to="x()"></animate><animate attributeName="opacity" values="0;1"/></a>"##,
+        );
+        assert_eq!(body, r##"<a><animate attributeName="opacity" values="0;1"/></a>"##);
+        assert!(findings.iter().any(|finding| finding.starts_with("<set>")));
+    }
+
+    #[test]
+    fn removes_unsafe_stylesheets() {
+        let (body, _) = clean(r##"<style>@import url(http://x.test/a.css);</style><rect/>"##);
+        assert_eq!(body, "<rect/>");
+        let (body, _) = clean(r##"<style><![CDATA[.a { fill: url(https://x.test/p) }]]></style>"##);
+        assert_eq!(body, "");
+        let (body, _) = clean(r##"<style>@\69 mport "a.css";</style><style>.a{fill:url(#g)}</style>"##);
+        assert_eq!(body, "<style>.a{fill:url(#g)}</style>");
+    }
+
+    #[test]
+    fn removes_external_references_from_style_attributes() {
+        let (body, _) = clean(r##"<rect style="fill: u\72l(https://x.test/p)"/>"##);
+        assert_eq!(body, "<rect/>");
+    }
+
+    #[test]
+    fn keeps_clean_files_byte_for_byte() {
+        let source = format!("{}\n  <defs><linearGradient id=\"g\"/></defs>\n  <rect fill=\"url(#g)\" href=\"#g\"/>\n</svg>", SVG);
+        let (sanitized, findings) = sanitize(&source).unwrap();
+        assert_eq!(sanitized, source);
+        assert!(findings.is_empty());
+    }
+}
@@ -0,0 +1,361 @@
+use crate::utils::bundle::{self, color_hex};
+use crate::utils::history::{asset_key, content_hash, STORE_DIR};
+use crate::utils::metadata::MetadataStore;
+use crate::utils::sanitize;
+use crate::utils::site::href;
+use crate::utils::snippets;
+use crate::utils::svg_document;
+use crate::utils::svg_parser::parse_svg_info;
+use crate::utils::svg_render::{self, MAX_RASTER_SIZE};
+use crate::utils::theme_variants::{apply_mapping, ColorMapping};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use svgtypes::Color;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+// Only reachable from this machine
+const HOST: &str = "127.0.0.1";
+// Listing results when no ?limit is given
+const DEFAULT_LIMIT: usize = 200;
+const SVG_TYPE: &str = "image/svg+xml";
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(default)]
+pub struct ServerOptions {
+    pub port: u16,
+    // Start serving when the app opens
+    pub autostart: bool,
+}
+
+impl Default for ServerOptions {
+    fn default() -> Self {
+        Self { port: 7878, autostart: false }
+    }
+}
+
+// A running server, stopped when dropped
+pub struct ServerHandle {
+    pub address: String,
+    server: Arc<Server>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl ServerHandle {
+    // Block until the server stops, for headless use
+    pub fn wait(mut self) {
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for ServerHandle {
+    fn drop(&mut self) {
+        self.server.unblock();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+// Bind to localhost and answer requests on a background thread
+pub fn start(vault: &Path, port: u16) -> Result<ServerHandle, String> {
+    let vault = std::path::absolute(vault).map_err(|e| e.to_string())?;
+    if !vault.is_dir() {
+        return Err(format!("Vault not found: {}", vault.display()));
+    }
+
+    let address = format!("{}:{}", HOST, port);
+    let server = Arc::new(Server::http(&address).map_err(|e| format!("Can't listen on {}: {}", address, e))?);
+    let worker = Arc::clone(&server);
+    let thread = std::thread::spawn(move || {
+        for request in worker.incoming_requests() {
+            handle(&vault, request);
+        }
+    });
+
+    Ok(ServerHandle { address: format!("http://{}", address), server, thread: Some(thread) })
+}
+
+struct Reply {
+    status: u16,
+    content_type: &'static str,
+    body: Vec<u8>,
+    etag: Option<String>,
+}
+
+impl Reply {
+    fn error(status: u16, message: &str) -> Self {
+        let body = serde_json::to_vec(&json!({ "error": message })).unwrap_or_default();
+        Reply { status, content_type: "application/json", body, etag: None }
+    }
+}
+
+fn handle(vault: &Path, request: Request) {
+    let reply = if matches!(request.method(), Method::Get | Method::Head) {
+        route(vault, request.url())
+    } else {
+        Reply::error(405, "Only GET and HEAD are supported")
+    };
+
+    let cached = reply.etag.as_ref().is_some_and(|etag| {
+        request
+            .headers()
+            .iter()
+            .filter(|header| header.field.equiv("If-None-Match"))
+            .any(|header| header.value.as_str().split(',').any(|tag| tag.trim() == etag || tag.trim() == "*"))
+    });
+
+    let mut headers = vec![
+        header("Access-Control-Allow-Origin", "*"),
+        // Always revalidate, so edits in the vault show up on the next request
+        header("Cache-Control", "no-cache"),
+    ];
+    if let Some(etag) = &reply.etag {
+        headers.push(header("ETag", etag));
+    }
+    // Icons are readable from any origin, so opened directly they mustn't run or load anything
+    if reply.content_type == SVG_TYPE {
+        headers.push(header("Content-Security-Policy", "default-src 'none'; style-src 'unsafe-inline'"));
+    }
+
+    let response = if cached {
+        Response::from_data(Vec::new()).with_status_code(304)
+    } else {
+        headers.push(header("Content-Type", reply.content_type));
+        Response::from_data(reply.body).with_status_code(reply.status)
+    };
+    let response = headers.into_iter().flatten().fold(response, |response, header| response.with_header(header));
+    let _ = request.respond(response);
+}
+
+fn header(name: &str, value: &str) -> Option<Header> {
+    Header::from_bytes(name.as_bytes(), value.as_bytes()).ok()
+}
+
+fn route(vault: &Path, url: &str) -> Reply {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let path = percent_decode(path);
+    let query = parse_query(query);
+
+    if path == "/" || path == "/api/icons" {
+        return list(vault, &query);
+    }
+    let Some(asset) = path.strip_prefix("/icons/") else {
+        return Reply::error(404, "Not found, try /icons/{path}.svg or /api/icons");
+    };
+    let (stem, png) = match (asset.strip_suffix(".svg"), asset.strip_suffix(".png")) {
+        (Some(stem), _) => (stem, false),
+        (_, Some(stem)) => (stem, true),
+        _ => return Reply::error(404, "Icons end in .svg or .png"),
+    };
+    let Some(file) = resolve(vault, &format!("{}.svg", stem)) else {
+        return Reply::error(404, &format!("No icon at {}.svg", stem));
+    };
+
+    match icon(&file, png, &query) {
+        Ok(reply) => reply,
+        Err(e) => Reply::error(400, &e),
+    }
+}
+
+// A vault file from a URL path, refusing anything that would leave the vault
+fn resolve(vault: &Path, relative: &str) -> Option<PathBuf> {
+    let relative = Path::new(relative);
+    let safe = relative.components().all(|component| match component {
+        Component::Normal(name) => !name.to_string_lossy().starts_with('.'),
+        _ => false,
+    });
+    let file = vault.join(relative);
+    (safe && file.is_file()).then_some(file)
+}
+
+fn param<'a>(query: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    query.iter().rev().find(|(key, _)| key == name).map(|(_, value)| value.as_str())
+}
+
+fn icon(file: &Path, png: bool, query: &[(String, String)]) -> Result<Reply, String> {
+    let mut source = fs::read_to_string(file).map_err(|e| e.to_string())?;
+
+    let color = match param(query, "color") {
+        Some(value) => Some(parse_color(value)?),
+        None => None,
+    };
+    let size = match param(query, "size") {
+        Some(value) => match value.parse::<f64>() {
+            Ok(size) if size >= 1.0 && size <= MAX_RASTER_SIZE as f64 => Some(size),
+            _ => return Err(format!("size must be between 1 and {}", MAX_RASTER_SIZE)),
+        },
+        None => None,
+    };
+
+    // Same file, same options, same bytes
+    let variant = format!("{}|{}|{:?}|{:?}", png, file.display(), color.as_ref().map(color_hex), size);
+    let etag = format!("\"{}\"", &content_hash(&format!("{}\n{}", variant, source))[..32]);
+
+    if let Some(color) = &color {
+        source = recolor(&source, file, color);
+    }
+
+    if png {
+        let tree = svg_render::parse_tree(&source)?;
+        let longest = tree.size().width().max(tree.size().height());
+        let scale = size.map(|size| size as f32 / longest).unwrap_or(1.0);
+        let body = svg_render::rasterize_tree(&tree, scale)?.encode_png().map_err(|e| e.to_string())?;
+        return Ok(Reply { status: 200, content_type: "image/png", body, etag: Some(etag) });
+    }
+
+    // Vault files aren't trusted, scripts and external references are left out
+    source = sanitize::sanitize(&source)?.0;
+    if let Some(size) = size {
+        source = snippets::resized(&source, size)?;
+    }
+    Ok(Reply { status: 200, content_type: SVG_TYPE, body: source.into_bytes(), etag: Some(etag) })
+}
+
+// "ff0000", "#f00" or "red"
+fn parse_color(value: &str) -> Result<Color, String> {
+    Color::from_str(value)
+        .or_else(|_| Color::from_str(&format!("#{}", value)))
+        .map_err(|_| format!("Not a color: {}", value))
+}
+
+// Paint every color of the icon, and currentColor, with `color`.
+// Icons without any color get it as the root fill, which they would otherwise draw in black.
+fn recolor(source: &str, file: &Path, color: &Color) -> String {
+    let hex = color_hex(color);
+    let colors = parse_svg_info(file).map(|info| info.colors_used).unwrap_or_default();
+    let mapping: Vec<ColorMapping> = colors
+        .iter()
+        .map(|used| ColorMapping { light: color_hex(used), dark: hex.clone() })
+        .collect();
+
+    let has_current_color = source.contains("currentColor");
+    let mut recolored = apply_mapping(source, &mapping).replace("currentColor", &hex);
+
+    if colors.is_empty() && !has_current_color {
+        if let Ok(document) = svg_document::parse(&recolored) {
+            let at = document.root.pos + 1 + document.root.name.len();
+            recolored.insert_str(at, &format!(" fill=\"{}\"", hex));
+        }
+    }
+    recolored
+}
+
+// JSON listing, filtered by ?q= the way the gallery search is
+fn list(vault: &Path, query: &[(String, String)]) -> Reply {
+    let search = param(query, "q").unwrap_or("").trim().to_lowercase();
+    let limit = param(query, "limit").and_then(|limit| limit.parse().ok()).unwrap_or(DEFAULT_LIMIT);
+    let metadata = MetadataStore::load(vault);
+
+    let mut matches: Vec<(String, PathBuf)> = bundle::collect_assets(&[vault.to_path_buf()])
+        .into_iter()
+        .map(|path| (asset_key(vault, &path), path))
+        .filter(|(key, _)| !key.split('/').any(|part| part == STORE_DIR))
+        .filter(|(key, path)| search.is_empty() || key.to_lowercase().contains(&search) || metadata.matches(path, &search))
+        .collect();
+    matches.sort_by_key(|(key, _)| key.to_lowercase());
+
+    let icons: Vec<_> = matches
+        .iter()
+        .take(limit)
+        .map(|(key, path)| {
+            let stem = key.strip_suffix(".svg").unwrap_or(key);
+            let meta = metadata.get(path);
+            json!({
+                "name": Path::new(key).file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default(),
+                "path": key,
+                "svg": format!("/icons/{}.svg", href(stem)),
+                "png": format!("/icons/{}.png", href(stem)),
+                "tags": meta.map(|m| m.tags.clone()).unwrap_or_default(),
+                "description": meta.map(|m| m.description.clone()).unwrap_or_default(),
+            })
+        })
+        .collect();
+
+    let body = json!({ "total": matches.len(), "icons": icons });
+    let body = serde_json::to_vec_pretty(&body).unwrap_or_default();
+    let etag = format!("\"{}\"", &content_hash(&String::from_utf8_lossy(&body))[..32]);
+    Reply { status: 200, content_type: "application/json", body, etag: Some(etag) }
+}
+
+fn parse_query(query: &str) -> Vec<(String, String)> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(key), percent_decode(&value.replace('+', " ")))
+        })
+        .collect()
+}
+
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes.get(i + 1..i + 3).and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vault(test: &str) -> PathBuf {
+        let vault = std::env::temp_dir().join(format!("server-{}-{}", test, std::process::id()));
+        fs::create_dir_all(vault.join("icons")).unwrap();
+        fs::create_dir_all(vault.join(STORE_DIR)).unwrap();
+        fs::write(vault.join("icons").join("add.svg"), r#"<svg xmlns="http://www.w3.org/2000/svg" onload="x()"/>"#).unwrap();
+        fs::write(vault.join(STORE_DIR).join("secret.svg"), "<svg/>").unwrap();
+        vault
+    }
+
+    #[test]
+    fn resolves_only_visible_vault_files() {
+        let vault = vault("resolve");
+        assert_eq!(resolve(&vault, "icons/add.svg"), Some(vault.join("icons").join("add.svg")));
+        assert_eq!(resolve(&vault, "icons/../icons/add.svg"), None);
+        assert_eq!(resolve(&vault, "../add.svg"), None);
+        assert_eq!(resolve(&vault, &vault.join("icons").join("add.svg").to_string_lossy()), None);
+        assert_eq!(resolve(&vault, &format!("{}/secret.svg", STORE_DIR)), None);
+        assert_eq!(resolve(&vault, "./icons/add.svg"), None);
+        fs::remove_dir_all(&vault).unwrap();
+    }
+
+    #[test]
+    fn refuses_encoded_traversal() {
+        let vault = vault("encoded");
+        assert_eq!(route(&vault, "/icons/%2e%2e/icons/add.svg").status, 404);
+        assert_eq!(route(&vault, "/icons/icons%2f..%2f..%2fadd.svg").status, 404);
+        assert_eq!(route(&vault, "/icons/%2easset_manager/secret.svg").status, 404);
+        fs::remove_dir_all(&vault).unwrap();
+    }
+
+    #[test]
+    fn serves_sanitized_icons() {
+        let vault = vault("sanitized");
+        let reply = route(&vault, "/icons/icons/add.svg");
+        assert_eq!(reply.status, 200);
+        assert_eq!(reply.content_type, SVG_TYPE);
+        assert!(!String::from_utf8(reply.body).unwrap().contains("onload"));
+        fs::remove_dir_all(&vault).unwrap();
+    }
+}
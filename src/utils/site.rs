@@ -0,0 +1,358 @@
+use crate::utils::bundle::color_hex;
+use crate::utils::catalog::escape_html;
+use crate::utils::history::asset_key;
+use crate::utils::metadata::MetadataStore;
+use crate::utils::snippets::{self, SnippetKind};
+use crate::utils::svg_format::format_number;
+use crate::utils::svg_parser::parse_svg_info;
+use crate::utils::svg_render;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const STYLE_FILE: &str = "style.css";
+
+const STYLE: &str = r#"body { font-family: system-ui, sans-serif; margin: 0; color: #222; background: #fafafa; }
+header { position: sticky; top: 0; background: #fff; border-bottom: 1px solid #e3e3e3; padding: 1rem 2rem; display: flex; gap: 1rem; align-items: center; }
+header h1 { font-size: 1.2rem; margin: 0; }
+header a { color: inherit; text-decoration: none; }
+#search { flex: 1; max-width: 24rem; padding: .4rem .6rem; font-size: 1rem; border: 1px solid #ccc; border-radius: 4px; }
+main { padding: 1.5rem 2rem; }
+.count { color: #777; font-size: .85rem; }
+.grid { display: grid; grid-template-columns: repeat(auto-fill, minmax(7rem, 1fr)); gap: .75rem; }
+.tile { display: block; background: #fff; border: 1px solid #e3e3e3; border-radius: 6px; padding: .75rem .5rem; text-align: center; color: inherit; text-decoration: none; }
+.tile:hover { border-color: #4a90d9; }
+.tile img { width: 48px; height: 48px; object-fit: contain; }
+.tile span { display: block; margin-top: .4rem; font-size: .75rem; overflow-wrap: anywhere; }
+.tile[hidden] { display: none; }
+.preview { display: flex; align-items: center; justify-content: center; width: 16rem; height: 16rem; border: 1px solid #e3e3e3; border-radius: 6px;
+  background: conic-gradient(#eee 25%, #fff 0 50%, #eee 0 75%, #fff 0) 0 0 / 16px 16px; }
+.preview img { max-width: 80%; max-height: 80%; }
+.detail { display: flex; gap: 2rem; flex-wrap: wrap; }
+.info { flex: 1; min-width: 18rem; }
+.info dt { font-weight: 600; font-size: .8rem; color: #777; margin-top: .75rem; }
+.info dd { margin: .2rem 0 0; }
+.swatch { display: inline-block; width: 1rem; height: 1rem; margin-right: 4px; vertical-align: middle; border: 1px solid #ccc; border-radius: 2px; }
+.tag { display: inline-block; background: #eef3fa; border-radius: 3px; padding: 0 .4rem; margin-right: 4px; font-size: .8rem; }
+.downloads a { margin-right: 1rem; }
+.snippet { margin-top: 1.5rem; }
+.snippet h3 { display: flex; justify-content: space-between; font-size: .9rem; margin: 0 0 .4rem; }
+.snippet textarea { width: 100%; height: 8rem; font-family: ui-monospace, monospace; font-size: .8rem; box-sizing: border-box; }
+"#;
+
+// Filters the tiles by name, folder, tags and description, "#tag" only looks at tags like the app does
+const SEARCH_SCRIPT: &str = r#"<script>
+const search = document.getElementById('search');
+const tiles = Array.from(document.querySelectorAll('.tile'));
+const count = document.getElementById('count');
+search.addEventListener('input', () => {
+  const query = search.value.trim().toLowerCase();
+  let shown = 0;
+  for (const tile of tiles) {
+    const match = query.startsWith('#')
+      ? tile.dataset.tags.split(' ').some(tag => tag.startsWith(query.slice(1)))
+      : tile.dataset.search.includes(query);
+    tile.hidden = !match;
+    if (match) shown++;
+  }
+  count.textContent = shown + ' of ' + tiles.length + ' icons';
+});
+</script>
+"#;
+
+// The clipboard API can be missing on file:// pages, selecting the text still works there
+const COPY_SCRIPT: &str = r#"<script>
+for (const button of document.querySelectorAll('button[data-copy]')) {
+  button.addEventListener('click', () => {
+    const text = document.getElementById(button.dataset.copy);
+    const done = () => { button.textContent = 'Copied'; setTimeout(() => button.textContent = 'Copy', 1500); };
+    if (navigator.clipboard && window.isSecureContext) {
+      navigator.clipboard.writeText(text.value).then(done);
+    } else {
+      text.select();
+      document.execCommand('copy');
+      done();
+    }
+  });
+}
+</script>
+"#;
+
+#[derive(Default)]
+pub struct SiteReport {
+    pub icons: usize,
+    pub files: usize,
+    pub errors: Vec<String>,
+}
+
+impl SiteReport {
+    pub fn summary(&self) -> String {
+        let mut summary = format!("{} icons, {} files", self.icons, self.files);
+        if !self.errors.is_empty() {
+            summary.push_str(&format!(", {} failed", self.errors.len()));
+        }
+        summary
+    }
+}
+
+struct Icon {
+    path: PathBuf,
+    // Path relative to the vault with forward slashes
+    key: String,
+    name: String,
+    source: String,
+}
+
+impl Icon {
+    fn page(&self) -> String {
+        format!("icons/{}", Path::new(&self.key).with_extension("html").to_string_lossy().replace('\\', "/"))
+    }
+
+    fn png(&self, size: u32) -> String {
+        let stem = Path::new(&self.key).with_extension("").to_string_lossy().replace('\\', "/");
+        format!("assets/{}-{}.png", stem, size)
+    }
+
+    // Relative way back to the site root from the detail page
+    fn root(&self) -> String {
+        "../".repeat(self.key.matches('/').count() + 1)
+    }
+}
+
+// Percent-encode each segment so names with spaces or '#' still resolve from file://
+pub(crate) fn href(path: &str) -> String {
+    let mut encoded = String::new();
+    for byte in path.bytes() {
+        match byte {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => encoded.push(byte as char),
+            byte => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+fn write(output_dir: &Path, relative: &str, bytes: &[u8], report: &mut SiteReport) {
+    let path = output_dir.join(relative);
+    let result = match path.parent() {
+        Some(parent) => fs::create_dir_all(parent).and_then(|_| fs::write(&path, bytes)),
+        None => fs::write(&path, bytes),
+    };
+    match result {
+        Ok(_) => report.files += 1,
+        Err(e) => report.errors.push(format!("{}: {}", relative, e)),
+    }
+}
+
+// `page_title` goes in the tab, `title` links back to the grid
+fn header(page_title: &str, title: &str, root: &str, search: bool) -> String {
+    let mut html = String::from("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+    html.push_str(&format!("<title>{}</title>\n", escape_html(page_title)));
+    html.push_str(&format!("<link rel=\"stylesheet\" href=\"{}{}\">\n</head>\n<body>\n<header>\n", root, STYLE_FILE));
+    html.push_str(&format!("<h1><a href=\"{}index.html\">{}</a></h1>\n", root, escape_html(title)));
+    if search {
+        html.push_str("<input id=\"search\" type=\"search\" placeholder=\"Search names, folders or #tags\" autofocus>\n");
+    }
+    html.push_str("</header>\n<main>\n");
+    html
+}
+
+// Write index.html, one page per icon under icons/ and the files under assets/ into `output_dir`.
+// Everything is linked relatively so the site also works when opened from disk.
+pub fn generate(
+    vault: &Path,
+    assets: &[PathBuf],
+    output_dir: &Path,
+    title: &str,
+    png_sizes: &[u32],
+    metadata: &MetadataStore,
+) -> Result<SiteReport, String> {
+    fs::create_dir_all(output_dir).map_err(|e| e.to_string())?;
+    let mut report = SiteReport::default();
+
+    let mut icons = Vec::new();
+    for path in assets {
+        let key = if path.starts_with(vault) {
+            asset_key(vault, path)
+        } else {
+            path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default()
+        };
+        match fs::read_to_string(path) {
+            Ok(source) => icons.push(Icon {
+                path: path.clone(),
+                name: Path::new(&key).file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default(),
+                key,
+                source,
+            }),
+            Err(e) => report.errors.push(format!("{}: {}", key, e)),
+        }
+    }
+    icons.sort_by_key(|icon| icon.key.to_lowercase());
+
+    write(output_dir, STYLE_FILE, STYLE.as_bytes(), &mut report);
+
+    let mut index = header(title, title, "", true);
+    index.push_str(&format!("<p id=\"count\" class=\"count\">{} icons</p>\n<div class=\"grid\">\n", icons.len()));
+    for icon in &icons {
+        let tags = metadata.get(&icon.path).map(|m| m.tags.join(" ").to_lowercase()).unwrap_or_default();
+        let mut search = format!("{} {}", icon.key, tags);
+        if let Some(meta) = metadata.get(&icon.path) {
+            search.push(' ');
+            search.push_str(&meta.description);
+        }
+        index.push_str(&format!(
+            "<a class=\"tile\" href=\"{}\" title=\"{}\" data-search=\"{}\" data-tags=\"{}\"><img src=\"{}\" alt=\"\" loading=\"lazy\"><span>{}</span></a>\n",
+            href(&icon.page()),
+            escape_html(&icon.key),
+            escape_html(&search.to_lowercase()),
+            escape_html(&tags),
+            href(&format!("assets/{}", icon.key)),
+            escape_html(&icon.name),
+        ));
+
+        write(output_dir, &format!("assets/{}", icon.key), icon.source.as_bytes(), &mut report);
+        if !png_sizes.is_empty() {
+            let tree = svg_render::parse_tree(&icon.source);
+            for size in png_sizes {
+                let png = tree.as_ref().map_err(|e| e.clone()).and_then(|tree| {
+                    let scale = *size as f32 / tree.size().width().max(tree.size().height());
+                    svg_render::rasterize_tree(tree, scale)?.encode_png().map_err(|e| e.to_string())
+                });
+                match png {
+                    Ok(png) => write(output_dir, &icon.png(*size), &png, &mut report),
+                    Err(e) => report.errors.push(format!("{}: {}", icon.png(*size), e)),
+                }
+            }
+        }
+
+        let page = detail_page(icon, title, png_sizes, metadata, &mut report);
+        write(output_dir, &icon.page(), page.as_bytes(), &mut report);
+        report.icons += 1;
+    }
+    index.push_str("</div>\n</main>\n");
+    index.push_str(SEARCH_SCRIPT);
+    index.push_str("</body>\n</html>\n");
+    write(output_dir, "index.html", index.as_bytes(), &mut report);
+
+    Ok(report)
+}
+
+fn detail_page(icon: &Icon, title: &str, png_sizes: &[u32], metadata: &MetadataStore, report: &mut SiteReport) -> String {
+    let root = icon.root();
+    let svg = format!("{}assets/{}", root, href(&icon.key));
+    let mut html = header(&format!("{} - {}", icon.name, title), title, &root, false);
+
+    html.push_str("<div class=\"detail\">\n");
+    html.push_str(&format!("<div class=\"preview\"><img src=\"{}\" alt=\"{}\"></div>\n", svg, escape_html(&icon.name)));
+    html.push_str(&format!("<div class=\"info\">\n<h2>{}</h2>\n<dl>\n", escape_html(&icon.name)));
+    html.push_str(&format!("<dt>Path</dt><dd>{}</dd>\n", escape_html(&icon.key)));
+
+    if let Ok(tree) = svg_render::parse_tree(&icon.source) {
+        let size = tree.size();
+        html.push_str(&format!(
+            "<dt>Size</dt><dd>{} × {}</dd>\n",
+            format_number(size.width() as f64, 2),
+            format_number(size.height() as f64, 2)
+        ));
+    }
+    if let Ok(info) = parse_svg_info(&icon.path) {
+        if !info.colors_used.is_empty() {
+            html.push_str("<dt>Colors</dt><dd>");
+            for color in &info.colors_used {
+                let hex = color_hex(color);
+                html.push_str(&format!("<span class=\"swatch\" style=\"background: {hex}\" title=\"{hex}\"></span>"));
+            }
+            html.push_str("</dd>\n");
+        }
+    }
+    if let Some(meta) = metadata.get(&icon.path) {
+        if !meta.tags.is_empty() {
+            html.push_str("<dt>Tags</dt><dd>");
+            for tag in &meta.tags {
+                html.push_str(&format!("<span class=\"tag\">{}</span>", escape_html(tag)));
+            }
+            html.push_str("</dd>\n");
+        }
+        for (label, value) in [("Description", &meta.description), ("Author", &meta.author), ("License", &meta.license)] {
+            if !value.trim().is_empty() {
+                html.push_str(&format!("<dt>{}</dt><dd>{}</dd>\n", label, escape_html(value)));
+            }
+        }
+    }
+
+    html.push_str("<dt>Download</dt><dd class=\"downloads\">");
+    html.push_str(&format!("<a href=\"{}\" download>SVG</a>", svg));
+    for size in png_sizes {
+        html.push_str(&format!("<a href=\"{}{}\" download>PNG {}px</a>", root, href(&icon.png(*size)), size));
+    }
+    html.push_str("</dd>\n</dl>\n</div>\n</div>\n");
+
+    let snippets = [
+        ("SVG", Ok(icon.source.clone())),
+        ("JSX", snippets::generate(SnippetKind::React, &icon.source, &icon.path)),
+        ("Data URI", snippets::data_uri(&icon.source)),
+    ];
+    for (index, (label, snippet)) in snippets.into_iter().enumerate() {
+        match snippet {
+            Ok(snippet) => {
+                html.push_str(&format!(
+                    "<div class=\"snippet\">\n<h3>{}<button data-copy=\"snippet-{}\">Copy</button></h3>\n<textarea id=\"snippet-{}\" readonly>{}</textarea>\n</div>\n",
+                    label,
+                    index,
+                    index,
+                    escape_html(&snippet)
+                ));
+            }
+            Err(e) => report.errors.push(format!("{} {}: {}", icon.key, label, e)),
+        }
+    }
+
+    html.push_str("</main>\n");
+    html.push_str(COPY_SCRIPT);
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn icon(key: &str) -> Icon {
+        Icon { path: PathBuf::from(key), key: key.to_string(), name: String::new(), source: String::new() }
+    }
+
+    #[test]
+    fn encodes_hrefs() {
+        assert_eq!(href("icons/arrow-left_2.html"), "icons/arrow-left_2.html");
+        assert_eq!(href("assets/a b#1?.svg"), "assets/a%20b%231%3F.svg");
+        assert_eq!(href("assets/ü.svg"), "assets/%C3%BC.svg");
+    }
+
+    #[test]
+    fn links_back_to_the_root_from_any_depth() {
+        assert_eq!(icon("add.svg").root(), "../");
+        assert_eq!(icon("ui/arrows/up.svg").root(), "../../../");
+        assert_eq!(icon("ui/arrows/up.svg").page(), "icons/ui/arrows/up.html");
+        assert_eq!(icon("ui/arrows/up.svg").png(32), "assets/ui/arrows/up-32.png");
+
+        let vault = std::env::temp_dir().join(format!("site-root-{}", std::process::id()));
+        let output = vault.join("site");
+        let _ = fs::remove_dir_all(&vault);
+        fs::create_dir_all(vault.join("ui").join("arrows")).unwrap();
+        let up = vault.join("ui").join("arrows").join("up arrow.svg");
+        fs::write(&up, r#"<svg xmlns="http://www.w3.org/2000/svg" width="8" height="8"/>"#).unwrap();
+
+        let report = generate(&vault, &[up], &output, "Icons", &[16], &MetadataStore::load(&vault)).unwrap();
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        let page = fs::read_to_string(output.join("icons/ui/arrows/up arrow.html")).unwrap();
+        assert!(page.contains("href=\"../../../style.css\""));
+        assert!(page.contains("href=\"../../../index.html\""));
+        assert!(page.contains("../../../assets/ui/arrows/up%20arrow.svg"));
+        assert!(page.contains("href=\"../../../assets/ui/arrows/up%20arrow-16.png\""));
+        let page_dir = output.join("icons/ui/arrows");
+        assert!(page_dir.join("../../../style.css").exists());
+        assert!(page_dir.join("../../../assets/ui/arrows/up arrow.svg").exists());
+        assert!(page_dir.join("../../../assets/ui/arrows/up arrow-16.png").exists());
+
+        fs::remove_dir_all(&vault).unwrap();
+    }
+}
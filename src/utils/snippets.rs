@@ -0,0 +1,468 @@
+use crate::utils::svg_document::{self, Attribute, Element, Node};
+use crate::utils::svg_format::{self, FormatOptions, QuoteStyle};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use svgtypes::ViewBox;
+
+// Used when the document doesn't say how big it is
+const DEFAULT_SIZE: f64 = 24.0;
+
+// Elements whose text is written inline
+const TEXT_ELEMENTS: &[&str] = &["text", "tspan", "textPath", "title", "desc", "style"];
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum SnippetKind {
+    InlineSvg,
+    DataUri,
+    ImgTag,
+    CssBackground,
+    CssMask,
+    React,
+    Vue,
+    Svelte,
+}
+
+impl SnippetKind {
+    pub const ALL: [SnippetKind; 8] = [
+        SnippetKind::InlineSvg,
+        SnippetKind::DataUri,
+        SnippetKind::ImgTag,
+        SnippetKind::CssBackground,
+        SnippetKind::CssMask,
+        SnippetKind::React,
+        SnippetKind::Vue,
+        SnippetKind::Svelte,
+    ];
+
+    pub const COMPONENTS: [SnippetKind; 3] = [SnippetKind::React, SnippetKind::Vue, SnippetKind::Svelte];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SnippetKind::InlineSvg => "Inline <svg>",
+            SnippetKind::DataUri => "Data URI",
+            SnippetKind::ImgTag => "<img> with data URI",
+            SnippetKind::CssBackground => "CSS background-image",
+            SnippetKind::CssMask => "CSS mask-image",
+            SnippetKind::React => "React component (TSX)",
+            SnippetKind::Vue => "Vue component (SFC)",
+            SnippetKind::Svelte => "Svelte component",
+        }
+    }
+
+    // File extension for components, None for the other snippets
+    pub fn extension(&self) -> Option<&'static str> {
+        match self {
+            SnippetKind::React => Some("tsx"),
+            SnippetKind::Vue => Some("vue"),
+            SnippetKind::Svelte => Some("svelte"),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Syntax {
+    Html,
+    Jsx,
+    Svelte,
+}
+
+// Component name from a file name: "arrow-left.svg" -> "ArrowLeft", "24-clock" -> "Icon24Clock"
+pub fn component_name(path: &Path) -> String {
+    let stem = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let mut name = String::new();
+    for word in stem.split(|c: char| !c.is_ascii_alphanumeric()).filter(|w| !w.is_empty()) {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            name.extend(first.to_uppercase());
+            name.push_str(chars.as_str());
+        }
+    }
+
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert_str(0, "Icon");
+    }
+    name
+}
+
+// CSS class name from a file name: "Arrow Left.svg" -> "icon-arrow-left"
+fn class_name(path: &Path) -> String {
+    let stem = path.file_stem().map(|s| s.to_string_lossy().to_lowercase()).unwrap_or_default();
+    let words: Vec<&str> = stem.split(|c: char| !c.is_ascii_alphanumeric()).filter(|w| !w.is_empty()).collect();
+    format!("icon-{}", words.join("-"))
+}
+
+// The root without editor data, its size, and width/height moved into a viewBox
+struct Prepared {
+    root: Element,
+    width: f64,
+    height: f64,
+}
+
+fn parse_length(value: Option<&str>) -> Option<f64> {
+    value?.trim().trim_end_matches("px").parse().ok()
+}
+
+fn prepare(source: &str) -> Result<Prepared, String> {
+    let cleaned = svg_format::strip_metadata(source, &FormatOptions::default()).map_err(|e| e.to_string())?;
+    let mut root = svg_document::parse(&cleaned).map_err(|e| e.to_string())?.root;
+    if root.local_name() != "svg" {
+        return Err(format!("Root element is <{}>, not <svg>", root.name));
+    }
+
+    let view_box = root.attr("viewBox").and_then(|v| ViewBox::from_str(v).ok());
+    let width = parse_length(root.attr("width")).or(view_box.map(|v| v.w)).unwrap_or(DEFAULT_SIZE);
+    let height = parse_length(root.attr("height")).or(view_box.map(|v| v.h)).unwrap_or(DEFAULT_SIZE);
+
+    root.attributes.retain(|attr| attr.name != "width" && attr.name != "height");
+    if view_box.is_none() {
+        root.attributes.push(Attribute {
+            name: "viewBox".to_string(),
+            value: format!("0 0 {} {}", svg_format::format_number(width, 3), svg_format::format_number(height, 3)),
+            pos: 0,
+        });
+    }
+
+    Ok(Prepared { root, width, height })
+}
+
+// "stroke-width" -> "strokeWidth", "xlink:href" -> "xlinkHref"
+fn camel_case(name: &str) -> String {
+    let mut out = String::new();
+    let mut upper = false;
+    for c in name.chars() {
+        if c == '-' || c == ':' {
+            upper = true;
+        } else if upper {
+            out.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn jsx_attribute_name(name: &str) -> String {
+    match name {
+        "class" => "className".to_string(),
+        _ if name.starts_with("data-") || name.starts_with("aria-") => name.to_string(),
+        _ => camel_case(name),
+    }
+}
+
+// style="fill: red; stroke-width: 2" -> style={{ fill: "red", strokeWidth: "2" }}
+fn jsx_style(value: &str) -> String {
+    let declarations: Vec<String> = value
+        .split(';')
+        .filter_map(|declaration| declaration.split_once(':'))
+        .map(|(property, value)| {
+            let property = property.trim();
+            let key = if property.starts_with("--") {
+                format!("{:?}", property)
+            } else {
+                camel_case(property)
+            };
+            format!("{}: {:?}", key, value.trim())
+        })
+        .collect();
+    format!("{{{{ {} }}}}", declarations.join(", "))
+}
+
+fn escape_braces(text: &str) -> String {
+    text.replace('{', "&#123;").replace('}', "&#125;")
+}
+
+fn write_attributes(out: &mut String, element: &Element, syntax: Syntax) {
+    for attr in &element.attributes {
+        out.push(' ');
+        let value = attr.value.replace('"', "&quot;");
+        match syntax {
+            Syntax::Jsx if attr.name == "style" => {
+                out.push_str("style=");
+                out.push_str(&jsx_style(&attr.value));
+            }
+            Syntax::Jsx => out.push_str(&format!("{}=\"{}\"", jsx_attribute_name(&attr.name), value)),
+            Syntax::Svelte => out.push_str(&format!("{}=\"{}\"", attr.name, escape_braces(&value))),
+            Syntax::Html => out.push_str(&format!("{}=\"{}\"", attr.name, value)),
+        }
+    }
+}
+
+fn write_inline(out: &mut String, node: &Node, syntax: Syntax) {
+    match node {
+        Node::Element(element) => {
+            out.push('<');
+            out.push_str(&element.name);
+            write_attributes(out, element, syntax);
+            if element.children.is_empty() {
+                out.push_str(" />");
+                return;
+            }
+            out.push('>');
+            for child in &element.children {
+                write_inline(out, child, syntax);
+            }
+            out.push_str(&format!("</{}>", element.name));
+        }
+        Node::Text(text) | Node::CData(text) => match syntax {
+            Syntax::Jsx if text.contains(['{', '}']) || matches!(node, Node::CData(_)) => {
+                out.push_str(&format!("{{{:?}}}", text));
+            }
+            Syntax::Svelte => out.push_str(&escape_braces(text)),
+            _ if matches!(node, Node::CData(_)) => out.push_str(&format!("<![CDATA[{}]]>", text)),
+            _ => out.push_str(text),
+        },
+        _ => {}
+    }
+}
+
+// One element per line, two spaces per level. `extra` goes into the root's start tag.
+fn write_markup(out: &mut String, element: &Element, depth: usize, syntax: Syntax, extra: &str) {
+    let indent = "  ".repeat(depth);
+    out.push_str(&indent);
+    out.push('<');
+    out.push_str(&element.name);
+    write_attributes(out, element, syntax);
+    out.push_str(extra);
+
+    let has_text = element.children.iter().any(|node| match node {
+        Node::Text(text) => !text.trim().is_empty(),
+        Node::CData(_) => true,
+        _ => false,
+    });
+    let children: Vec<&Element> = element.child_elements().collect();
+
+    if has_text || TEXT_ELEMENTS.contains(&element.local_name()) {
+        out.push('>');
+        for child in &element.children {
+            write_inline(out, child, syntax);
+        }
+        out.push_str(&format!("</{}>", element.name));
+    } else if children.is_empty() {
+        out.push_str(" />");
+    } else {
+        out.push_str(">\n");
+        for child in children {
+            write_markup(out, child, depth + 1, syntax, "");
+            out.push('\n');
+        }
+        out.push_str(&format!("{}</{}>", indent, element.name));
+    }
+}
+
+// The SVG scaled so its longest side is `size`, metadata stripped
+pub(crate) fn resized(source: &str, size: f64) -> Result<String, String> {
+    let prepared = prepare(source)?;
+    let scale = size / prepared.width.max(prepared.height);
+    let extra = format!(
+        " width=\"{}\" height=\"{}\"",
+        svg_format::format_number(prepared.width * scale, 3),
+        svg_format::format_number(prepared.height * scale, 3)
+    );
+    let mut out = String::new();
+    write_markup(&mut out, &prepared.root, 0, Syntax::Html, &extra);
+    out.push('\n');
+    Ok(out)
+}
+
+// Percent-encode what data URIs can't carry as-is. Attributes use single quotes,
+// so the result fits in a double-quoted HTML attribute or CSS url("…").
+pub fn data_uri(source: &str) -> Result<String, String> {
+    let options = FormatOptions {
+        quote_style: QuoteStyle::Single,
+        ..FormatOptions::default()
+    };
+    let cleaned = svg_format::strip_metadata(source, &options).map_err(|e| e.to_string())?;
+    let minified = svg_format::minify(&cleaned, &options).map_err(|e| e.to_string())?;
+
+    let mut encoded = String::from("data:image/svg+xml,");
+    for byte in minified.bytes() {
+        match byte {
+            b'%' | b'#' | b'<' | b'>' | b'"' | b'{' | b'}' | b'|' | b'\\' | b'^' | b'`' => {
+                encoded.push_str(&format!("%{:02X}", byte))
+            }
+            b'\n' | b'\r' | b'\t' => encoded.push(' '),
+            byte if byte.is_ascii() => encoded.push(byte as char),
+            byte => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    Ok(encoded)
+}
+
+pub fn generate(kind: SnippetKind, source: &str, path: &Path) -> Result<String, String> {
+    let prepared = prepare(source)?;
+    let (width, height) = (svg_format::format_number(prepared.width, 3), svg_format::format_number(prepared.height, 3));
+    let size = svg_format::format_number(prepared.width.max(prepared.height), 3);
+    let name = component_name(path);
+    let mut out = String::new();
+
+    match kind {
+        SnippetKind::InlineSvg => {
+            let extra = format!(" width=\"{}\" height=\"{}\"", width, height);
+            write_markup(&mut out, &prepared.root, 0, Syntax::Html, &extra);
+            out.push('\n');
+        }
+        SnippetKind::DataUri => out = data_uri(source)?,
+        SnippetKind::ImgTag => {
+            let alt = path.file_stem().map(|s| s.to_string_lossy().replace('"', "&quot;")).unwrap_or_default();
+            out = format!("<img src=\"{}\" width=\"{}\" height=\"{}\" alt=\"{}\">", data_uri(source)?, width, height, alt);
+        }
+        SnippetKind::CssBackground => {
+            out = format!(
+                ".{} {{\n  width: {}px;\n  height: {}px;\n  background-image: url(\"{}\");\n  background-repeat: no-repeat;\n  background-size: contain;\n}}\n",
+                class_name(path),
+                width,
+                height,
+                data_uri(source)?
+            );
+        }
+        SnippetKind::CssMask => {
+            // Takes the text color, like an icon font
+            let uri = data_uri(source)?;
+            out = format!(
+                ".{} {{\n  width: {}px;\n  height: {}px;\n  background-color: currentColor;\n  -webkit-mask: url(\"{}\") no-repeat center / contain;\n  mask: url(\"{}\") no-repeat center / contain;\n}}\n",
+                class_name(path),
+                width,
+                height,
+                uri,
+                uri
+            );
+        }
+        SnippetKind::React => {
+            out.push_str("import type { SVGProps } from \"react\";\n\n");
+            out.push_str(&format!(
+                "export function {}({{ size = {}, ...props }}: SVGProps<SVGSVGElement> & {{ size?: number | string }}) {{\n",
+                name, size
+            ));
+            out.push_str("  return (\n");
+            write_markup(&mut out, &prepared.root, 2, Syntax::Jsx, " width={size} height={size} {...props}");
+            out.push_str("\n  );\n}\n\n");
+            out.push_str(&format!("export default {};\n", name));
+        }
+        SnippetKind::Vue => {
+            out.push_str("<script setup lang=\"ts\">\n");
+            out.push_str(&format!("withDefaults(defineProps<{{ size?: number | string }}>(), {{ size: {} }});\n", size));
+            out.push_str("</script>\n\n<template>\n");
+            write_markup(&mut out, &prepared.root, 1, Syntax::Html, " :width=\"size\" :height=\"size\"");
+            out.push_str("\n</template>\n");
+        }
+        SnippetKind::Svelte => {
+            out.push_str("<script lang=\"ts\">\n");
+            out.push_str(&format!("  export let size: number | string = {};\n", size));
+            out.push_str("</script>\n\n");
+            write_markup(&mut out, &prepared.root, 0, Syntax::Svelte, " width={size} height={size} {...$$restProps}");
+            out.push('\n');
+        }
+    }
+
+    Ok(out)
+}
+
+#[derive(Default)]
+pub struct LibraryReport {
+    pub components: usize,
+    pub errors: Vec<String>,
+}
+
+// One component per SVG in `source_dir`, plus an index.ts exporting them all.
+// Names that collide get a number, e.g. Arrow and Arrow2.
+pub fn generate_library(
+    kind: SnippetKind,
+    source_dir: &Path,
+    output_dir: &Path,
+    recursive: bool,
+) -> std::io::Result<LibraryReport> {
+    let extension = kind.extension().unwrap_or("tsx");
+    fs::create_dir_all(output_dir)?;
+
+    let mut report = LibraryReport::default();
+    let mut names: Vec<String> = Vec::new();
+
+    let mut files: Vec<PathBuf> = Vec::new();
+    svg_format::collect_svgs(source_dir, recursive, &mut files)?;
+    files.sort();
+
+    for path in files {
+        let relative = path.strip_prefix(source_dir).unwrap_or(&path).display().to_string();
+        let component = match fs::read_to_string(&path).map_err(|e| e.to_string()).and_then(|s| generate(kind, &s, &path)) {
+            Ok(component) => component,
+            Err(e) => {
+                report.errors.push(format!("{}: {}", relative, e));
+                continue;
+            }
+        };
+
+        let base = component_name(&path);
+        let mut name = base.clone();
+        let mut counter = 2;
+        while names.contains(&name) {
+            name = format!("{}{}", base, counter);
+            counter += 1;
+        }
+
+        // The React component is named after the file
+        let component = if kind == SnippetKind::React && name != base {
+            component.replace(&format!("function {}(", base), &format!("function {}(", name))
+                .replace(&format!("export default {};", base), &format!("export default {};", name))
+        } else {
+            component
+        };
+
+        match fs::write(output_dir.join(format!("{}.{}", name, extension)), component) {
+            Ok(_) => {
+                report.components += 1;
+                names.push(name);
+            }
+            Err(e) => report.errors.push(format!("{}: {}", relative, e)),
+        }
+    }
+
+    names.sort();
+    let import_suffix = if kind == SnippetKind::React { String::new() } else { format!(".{}", extension) };
+    let index: String = names
+        .iter()
+        .map(|name| format!("export {{ default as {} }} from \"./{}{}\";\n", name, name, import_suffix))
+        .collect();
+    fs::write(output_dir.join("index.ts"), index)?;
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ICON: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="24" height="24"><path class="a" stroke-width="2" data-id="x" aria-hidden="true" style="fill: red; stroke-linecap: round; --tone: 1" d="M0 0"/><use xlink:href="#a"/><text font-size="4">{a}</text></svg>"##;
+
+    #[test]
+    fn names_components() {
+        assert_eq!(component_name(Path::new("arrow-left.svg")), "ArrowLeft");
+        assert_eq!(component_name(Path::new("24-clock.svg")), "Icon24Clock");
+        assert_eq!(class_name(Path::new("Arrow Left.svg")), "icon-arrow-left");
+    }
+
+    #[test]
+    fn camel_cases_jsx_attributes() {
+        let out = generate(SnippetKind::React, ICON, Path::new("add.svg")).unwrap();
+        assert!(out.contains(r#"className="a""#));
+        assert!(out.contains(r#"strokeWidth="2""#));
+        assert!(out.contains(r#"data-id="x" aria-hidden="true""#));
+        assert!(out.contains(r#"style={{ fill: "red", strokeLinecap: "round", "--tone": "1" }}"#));
+        assert!(out.contains(r##"xlinkHref="#a""##));
+        assert!(out.contains(r#"fontSize="4">{"{a}"}</text>"#));
+        assert!(out.contains("export function Add({ size = 24, ...props }"));
+    }
+
+    #[test]
+    fn escapes_braces_for_svelte() {
+        let source = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 8 8"><path data-x="{b}" d="M0 0"/><text>{a}</text></svg>"#;
+        let out = generate(SnippetKind::Svelte, source, Path::new("add.svg")).unwrap();
+        assert!(out.contains(r#"data-x="&#123;b&#125;""#));
+        assert!(out.contains("<text>&#123;a&#125;</text>"));
+        // The props the component adds itself are left alone
+        assert!(out.contains(" width={size} height={size} {...$$restProps}"));
+    }
+}
@@ -0,0 +1,229 @@
+use crate::utils::svg_document::{self, Element, Node, ParseError};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Change {
+    Added,
+    Removed,
+    Modified,
+}
+
+#[derive(Clone, Debug)]
+pub struct DiffEntry {
+    // Where in the tree, e.g. "svg > g#icon > path[2]"
+    pub path: String,
+    pub change: Change,
+    pub detail: String,
+}
+
+// Element and attribute level differences between two documents
+pub fn diff(before: &str, after: &str) -> Result<Vec<DiffEntry>, ParseError> {
+    let before = svg_document::parse(before)?;
+    let after = svg_document::parse(after)?;
+
+    let mut entries = Vec::new();
+    let path = element_label(&after.root, 1);
+    if before.root.name != after.root.name {
+        entries.push(DiffEntry {
+            path: before.root.name.clone(),
+            change: Change::Removed,
+            detail: "root element".to_string(),
+        });
+        entries.push(DiffEntry {
+            path,
+            change: Change::Added,
+            detail: "root element".to_string(),
+        });
+    } else {
+        diff_elements(&before.root, &after.root, &path, &mut entries);
+    }
+
+    Ok(entries)
+}
+
+fn diff_elements(before: &Element, after: &Element, path: &str, entries: &mut Vec<DiffEntry>) {
+    for attr in &before.attributes {
+        match after.attr(&attr.name) {
+            None => entries.push(DiffEntry {
+                path: path.to_string(),
+                change: Change::Removed,
+                detail: format!("{}=\"{}\"", attr.name, attr.value),
+            }),
+            Some(value) if value != attr.value => entries.push(DiffEntry {
+                path: path.to_string(),
+                change: Change::Modified,
+                detail: format!("{}: \"{}\" → \"{}\"", attr.name, attr.value, value),
+            }),
+            Some(_) => {}
+        }
+    }
+    for attr in &after.attributes {
+        if before.attr(&attr.name).is_none() {
+            entries.push(DiffEntry {
+                path: path.to_string(),
+                change: Change::Added,
+                detail: format!("{}=\"{}\"", attr.name, attr.value),
+            });
+        }
+    }
+
+    let (before_text, after_text) = (text_of(before), text_of(after));
+    if before_text != after_text {
+        entries.push(DiffEntry {
+            path: path.to_string(),
+            change: Change::Modified,
+            detail: format!("text: \"{}\" → \"{}\"", before_text, after_text),
+        });
+    }
+
+    let old_children: Vec<&Element> = before.child_elements().collect();
+    let new_children: Vec<&Element> = after.child_elements().collect();
+    let old_labels = sibling_labels(&old_children);
+    let new_labels = sibling_labels(&new_children);
+
+    // Pair up children that kept their name and id, in order
+    let pairs = longest_common_subsequence(&old_children, &new_children);
+    let (mut old_idx, mut new_idx) = (0, 0);
+
+    for (matched_old, matched_new) in pairs.into_iter().chain(std::iter::once((old_children.len(), new_children.len()))) {
+        while old_idx < matched_old {
+            entries.push(DiffEntry {
+                path: format!("{} > {}", path, old_labels[old_idx]),
+                change: Change::Removed,
+                detail: "element".to_string(),
+            });
+            old_idx += 1;
+        }
+        while new_idx < matched_new {
+            entries.push(DiffEntry {
+                path: format!("{} > {}", path, new_labels[new_idx]),
+                change: Change::Added,
+                detail: "element".to_string(),
+            });
+            new_idx += 1;
+        }
+        if matched_old < old_children.len() {
+            let child_path = format!("{} > {}", path, new_labels[matched_new]);
+            diff_elements(old_children[matched_old], new_children[matched_new], &child_path, entries);
+            old_idx += 1;
+            new_idx += 1;
+        }
+    }
+}
+
+fn same_element(a: &Element, b: &Element) -> bool {
+    a.name == b.name && a.attr("id") == b.attr("id")
+}
+
+// Index pairs of matching children, in document order
+fn longest_common_subsequence(old: &[&Element], new: &[&Element]) -> Vec<(usize, usize)> {
+    let mut lengths = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i][j] = if same_element(old[i], new[j]) {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut pairs = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if same_element(old[i], new[j]) {
+            pairs.push((i, j));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    pairs
+}
+
+// "g#icon" when the element has an id, otherwise "path[2]" counting same-named siblings
+fn sibling_labels(children: &[&Element]) -> Vec<String> {
+    let mut labels = Vec::with_capacity(children.len());
+    for (idx, child) in children.iter().enumerate() {
+        let position = children[..idx].iter().filter(|c| c.name == child.name).count() + 1;
+        labels.push(element_label(child, position));
+    }
+    labels
+}
+
+fn element_label(element: &Element, position: usize) -> String {
+    match element.attr("id") {
+        Some(id) => format!("{}#{}", element.name, id),
+        None if position > 1 => format!("{}[{}]", element.name, position),
+        None => element.name.clone(),
+    }
+}
+
+fn text_of(element: &Element) -> String {
+    let mut text = String::new();
+    for child in &element.children {
+        match child {
+            Node::Text(t) | Node::CData(t) => text.push_str(t),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(before: &str, after: &str) -> Vec<(String, Change, String)> {
+        diff(before, after)
+            .unwrap()
+            .into_iter()
+            .map(|entry| (entry.path, entry.change, entry.detail))
+            .collect()
+    }
+
+    #[test]
+    fn same_documents_have_no_changes() {
+        let svg = r#"<svg viewBox="0 0 24 24"><path d="M0 0"/></svg>"#;
+        assert!(summary(svg, svg).is_empty());
+    }
+
+    #[test]
+    fn reports_attribute_changes() {
+        let changes = summary(
+            r#"<svg width="24" fill="red"><path d="M0 0"/></svg>"#,
+            r#"<svg width="32" stroke="blue"><path d="M0 0"/></svg>"#,
+        );
+        assert_eq!(
+            changes,
+            [
+                ("svg".to_string(), Change::Modified, "width: \"24\" → \"32\"".to_string()),
+                ("svg".to_string(), Change::Removed, "fill=\"red\"".to_string()),
+                ("svg".to_string(), Change::Added, "stroke=\"blue\"".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn pairs_children_around_insertions() {
+        let changes = summary(
+            r#"<svg><path d="1"/><g id="icon"><path d="2"/></g></svg>"#,
+            r#"<svg><path d="1"/><circle r="1"/><g id="icon"><path d="3"/></g></svg>"#,
+        );
+        assert_eq!(
+            changes,
+            [
+                ("svg > circle".to_string(), Change::Added, "element".to_string()),
+                ("svg > g#icon > path".to_string(), Change::Modified, "d: \"2\" → \"3\"".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn labels_unnamed_siblings_by_position() {
+        let changes = summary(r#"<svg><path d="1"/><path d="2"/></svg>"#, r#"<svg><path d="1"/></svg>"#);
+        assert_eq!(changes, [("svg > path[2]".to_string(), Change::Removed, "element".to_string())]);
+    }
+}
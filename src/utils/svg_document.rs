@@ -0,0 +1,257 @@
+use xmlparser::{ElementEnd, Token, Tokenizer};
+
+// Minimal owned XML tree that keeps prefixes, raw attribute values and source
+// positions, so callers can report problems at the right line and column.
+// Comments, the declaration and the doctype are kept so it can be written back.
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Attribute {
+    // Qualified name, e.g. "xlink:href"
+    pub name: String,
+    // Raw value as written in the source, entities are not expanded
+    pub value: String,
+    // Byte offset of the attribute in the source
+    pub pos: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Element {
+    pub name: String,
+    pub attributes: Vec<Attribute>,
+    pub children: Vec<Node>,
+    // Byte offset of the opening '<' in the source
+    pub pos: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Node {
+    Element(Element),
+    Text(String),
+    Comment(String),
+    CData(String),
+    ProcessingInstruction { target: String, content: Option<String> },
+    Doctype(String),
+}
+
+#[derive(Clone, Debug)]
+pub struct Document {
+    // Raw `<?xml ... ?>` declaration, if any
+    pub declaration: Option<String>,
+    pub prolog: Vec<Node>,
+    pub root: Element,
+    pub epilog: Vec<Node>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ParseError {
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl Element {
+    pub fn local_name(&self) -> &str {
+        local_name(&self.name)
+    }
+
+    pub fn prefix(&self) -> Option<&str> {
+        self.name.split_once(':').map(|(prefix, _)| prefix)
+    }
+
+    pub fn attr(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|a| a.name == name)
+            .map(|a| a.value.as_str())
+    }
+
+    pub fn child_elements(&self) -> impl Iterator<Item = &Element> {
+        self.children.iter().filter_map(|node| match node {
+            Node::Element(e) => Some(e),
+            _ => None,
+        })
+    }
+}
+
+pub fn local_name(name: &str) -> &str {
+    name.split_once(':').map(|(_, local)| local).unwrap_or(name)
+}
+
+pub fn parse(source: &str) -> Result<Document, ParseError> {
+    let mut declaration = None;
+    let mut prolog = Vec::new();
+    let mut epilog = Vec::new();
+    let mut root: Option<Element> = None;
+
+    // Elements that have been opened but not closed yet
+    let mut stack: Vec<Element> = Vec::new();
+    let mut doctype_start: Option<usize> = None;
+
+    let error_at = |pos: usize, message: String| {
+        let (line, column) = line_col(source, pos);
+        ParseError { message, line, column }
+    };
+
+    for token in Tokenizer::from(source) {
+        let token = token.map_err(|e| {
+            let pos = e.pos();
+            ParseError {
+                message: e.to_string(),
+                line: pos.row as usize,
+                column: pos.col as usize,
+            }
+        })?;
+
+        // Nodes outside the root element go to the prolog or epilog
+        let push_node = |node: Node, stack: &mut Vec<Element>, prolog: &mut Vec<Node>, epilog: &mut Vec<Node>, has_root: bool| {
+            if let Some(parent) = stack.last_mut() {
+                parent.children.push(node);
+            } else if has_root {
+                epilog.push(node);
+            } else {
+                prolog.push(node);
+            }
+        };
+
+        match token {
+            Token::Declaration { span, .. } => {
+                declaration = Some(span.as_str().to_string());
+            }
+            Token::ProcessingInstruction { target, content, .. } => {
+                let node = Node::ProcessingInstruction {
+                    target: target.as_str().to_string(),
+                    content: content.map(|c| c.as_str().to_string()),
+                };
+                push_node(node, &mut stack, &mut prolog, &mut epilog, root.is_some());
+            }
+            Token::Comment { text, .. } => {
+                push_node(Node::Comment(text.as_str().to_string()), &mut stack, &mut prolog, &mut epilog, root.is_some());
+            }
+            Token::DtdStart { span, .. } => {
+                doctype_start = Some(span.start());
+            }
+            Token::EmptyDtd { span, .. } => {
+                prolog.push(Node::Doctype(span.as_str().to_string()));
+            }
+            Token::DtdEnd { span } => {
+                if let Some(start) = doctype_start.take() {
+                    prolog.push(Node::Doctype(source[start..span.end()].to_string()));
+                }
+            }
+            Token::EntityDeclaration { .. } => {}
+            Token::ElementStart { prefix, local, span } => {
+                if root.is_some() && stack.is_empty() {
+                    return Err(error_at(span.start(), "Only one root element is allowed".to_string()));
+                }
+
+                stack.push(Element {
+                    name: qualified_name(prefix.as_str(), local.as_str()),
+                    attributes: Vec::new(),
+                    children: Vec::new(),
+                    pos: span.start(),
+                });
+            }
+            Token::Attribute { prefix, local, value, span } => {
+                let name = qualified_name(prefix.as_str(), local.as_str());
+                if let Some(element) = stack.last_mut() {
+                    if element.attributes.iter().any(|a| a.name == name) {
+                        return Err(error_at(span.start(), format!("Duplicate attribute '{}'", name)));
+                    }
+                    element.attributes.push(Attribute {
+                        name,
+                        value: value.as_str().to_string(),
+                        pos: span.start(),
+                    });
+                }
+            }
+            Token::ElementEnd { end, span } => {
+                let finished = match end {
+                    ElementEnd::Open => None,
+                    ElementEnd::Empty => stack.pop(),
+                    ElementEnd::Close(prefix, local) => {
+                        let name = qualified_name(prefix.as_str(), local.as_str());
+                        match stack.pop() {
+                            Some(open) if open.name == name => Some(open),
+                            Some(open) => {
+                                return Err(error_at(
+                                    span.start(),
+                                    format!("Expected </{}> but found </{}>", open.name, name),
+                                ));
+                            }
+                            None => {
+                                return Err(error_at(span.start(), format!("Unexpected closing tag </{}>", name)));
+                            }
+                        }
+                    }
+                };
+
+                if let Some(element) = finished {
+                    match stack.last_mut() {
+                        Some(parent) => parent.children.push(Node::Element(element)),
+                        None => root = Some(element),
+                    }
+                }
+            }
+            Token::Text { text } => {
+                if stack.is_empty() {
+                    if !text.as_str().trim().is_empty() {
+                        return Err(error_at(text.start(), "Text is not allowed outside the root element".to_string()));
+                    }
+                } else if let Some(parent) = stack.last_mut() {
+                    parent.children.push(Node::Text(text.as_str().to_string()));
+                }
+            }
+            Token::Cdata { text, span } => {
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(Node::CData(text.as_str().to_string())),
+                    None => return Err(error_at(span.start(), "CDATA is not allowed outside the root element".to_string())),
+                }
+            }
+        }
+    }
+
+    if let Some(open) = stack.last() {
+        return Err(error_at(open.pos, format!("Element <{}> is never closed", open.name)));
+    }
+
+    match root {
+        Some(root) => Ok(Document {
+            declaration,
+            prolog,
+            root,
+            epilog,
+        }),
+        None => Err(ParseError {
+            message: "Document has no root element".to_string(),
+            line: 1,
+            column: 1,
+        }),
+    }
+}
+
+fn qualified_name(prefix: &str, local: &str) -> String {
+    if prefix.is_empty() {
+        local.to_string()
+    } else {
+        format!("{}:{}", prefix, local)
+    }
+}
+
+// 1-based line and column (in characters) of a byte offset
+pub fn line_col(source: &str, pos: usize) -> (usize, usize) {
+    let pos = pos.min(source.len());
+    let before = &source[..pos];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
+}
+
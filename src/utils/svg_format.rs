@@ -0,0 +1,541 @@
+use crate::utils::history;
+use crate::utils::svg_document::{self, Attribute, Document, Element, Node, ParseError};
+use regex::Regex;
+use std::sync::LazyLock;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Attributes whose values are numbers, lengths or number lists
+const NUMERIC_ATTRIBUTES: &[&str] = &[
+    "d", "points", "viewBox", "transform", "gradientTransform", "patternTransform",
+    "x", "y", "width", "height", "cx", "cy", "r", "rx", "ry", "fx", "fy",
+    "x1", "y1", "x2", "y2", "dx", "dy", "offset", "stroke-width", "stroke-miterlimit",
+    "stroke-dashoffset", "stroke-dasharray", "opacity", "fill-opacity", "stroke-opacity",
+    "stop-opacity", "font-size", "refX", "refY", "markerWidth", "markerHeight",
+];
+
+// Namespace prefixes used by drawing programs for their private data
+const EDITOR_PREFIXES: &[&str] = &["inkscape", "sodipodi", "sketch", "serif", "figma"];
+
+// Elements where whitespace is content and must be written back untouched
+const TEXT_ELEMENTS: &[&str] = &["text", "tspan", "textPath", "title", "desc", "style", "script"];
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum AttributeOrder {
+    // Keep the order from the source
+    Source,
+    // Namespace declarations first, then the rest by name
+    Alphabetical,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum QuoteStyle {
+    Double,
+    Single,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct FormatOptions {
+    // Spaces per level, ignored when indenting with tabs
+    pub indent: usize,
+    pub use_tabs: bool,
+    pub attribute_order: AttributeOrder,
+    // Decimal places kept when canonicalizing numbers
+    pub precision: usize,
+    pub quote_style: QuoteStyle,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            indent: 2,
+            use_tabs: false,
+            attribute_order: AttributeOrder::Source,
+            precision: 3,
+            quote_style: QuoteStyle::Double,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum FormatAction {
+    PrettyPrint,
+    Minify,
+    Canonicalize,
+}
+
+impl FormatAction {
+    pub const ALL: [FormatAction; 3] = [FormatAction::PrettyPrint, FormatAction::Minify, FormatAction::Canonicalize];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FormatAction::PrettyPrint => "Pretty-print",
+            FormatAction::Minify => "Minify",
+            FormatAction::Canonicalize => "Canonicalize",
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct FormatReport {
+    pub changed: usize,
+    pub unchanged: usize,
+    pub errors: Vec<String>,
+}
+
+impl FormatReport {
+    pub fn summary(&self) -> String {
+        let mut summary = format!("{} changed, {} unchanged", self.changed, self.unchanged);
+        if !self.errors.is_empty() {
+            summary.push_str(&format!(", {} failed", self.errors.len()));
+        }
+        summary
+    }
+}
+
+pub fn apply(action: FormatAction, source: &str, options: &FormatOptions) -> Result<String, ParseError> {
+    match action {
+        FormatAction::PrettyPrint => pretty_print(source, options),
+        FormatAction::Minify => minify(source, options),
+        FormatAction::Canonicalize => canonicalize(source, options),
+    }
+}
+
+// One element per line, indented by depth
+pub fn pretty_print(source: &str, options: &FormatOptions) -> Result<String, ParseError> {
+    let mut document = svg_document::parse(source)?;
+    order_attributes(&mut document.root, options.attribute_order);
+    Ok(write_document(&document, options))
+}
+
+// Everything on one line: no comments, no declaration, no whitespace between tags
+pub fn minify(source: &str, options: &FormatOptions) -> Result<String, ParseError> {
+    let mut document = svg_document::parse(source)?;
+    order_attributes(&mut document.root, options.attribute_order);
+    strip_for_minify(&mut document.root);
+
+    let mut out = String::new();
+    for node in &document.prolog {
+        if let Node::Doctype(_) = node {
+            write_node(&mut out, node, options.quote_style);
+        }
+    }
+    write_element(&mut out, &document.root, options.quote_style);
+
+    Ok(out)
+}
+
+// Sorted attributes and numbers rounded to the configured precision, then pretty-printed
+pub fn canonicalize(source: &str, options: &FormatOptions) -> Result<String, ParseError> {
+    let mut document = svg_document::parse(source)?;
+    order_attributes(&mut document.root, AttributeOrder::Alphabetical);
+    normalize_numbers(&mut document.root, options.precision);
+    Ok(write_document(&document, options))
+}
+
+// Drop comments, <metadata> and editor-specific elements and attributes, then pretty-print.
+// <title> and <desc> stay, they are what screen readers announce.
+pub fn strip_metadata(source: &str, options: &FormatOptions) -> Result<String, ParseError> {
+    let mut document = svg_document::parse(source)?;
+    document.prolog.retain(|node| !matches!(node, Node::Comment(_)));
+    document.epilog.retain(|node| !matches!(node, Node::Comment(_)));
+    strip_editor_data(&mut document.root);
+    order_attributes(&mut document.root, options.attribute_order);
+    Ok(write_document(&document, options))
+}
+
+// "inkscape:label" or the "xmlns:inkscape" declaration itself
+fn is_editor_name(name: &str) -> bool {
+    let prefix = match name.strip_prefix("xmlns:") {
+        Some(declared) => declared,
+        None => name.split_once(':').map(|(prefix, _)| prefix).unwrap_or_default(),
+    };
+    EDITOR_PREFIXES.contains(&prefix)
+}
+
+fn strip_editor_data(element: &mut Element) {
+    element.attributes.retain(|attr| !is_editor_name(&attr.name));
+    element.children.retain(|node| match node {
+        Node::Comment(_) => false,
+        Node::Element(child) => child.local_name() != "metadata" && !is_editor_name(&child.name),
+        _ => true,
+    });
+
+    for node in &mut element.children {
+        if let Node::Element(child) = node {
+            strip_editor_data(child);
+        }
+    }
+}
+
+// Apply an action to every SVG in a folder, skipping hidden files and folders.
+// Rewritten files get a history snapshot in the vault.
+pub fn format_folder(
+    vault: &Path,
+    root: &Path,
+    recursive: bool,
+    action: FormatAction,
+    options: &FormatOptions,
+) -> std::io::Result<FormatReport> {
+    let mut files = Vec::new();
+    collect_svgs(root, recursive, &mut files)?;
+
+    let mut report = FormatReport::default();
+    for path in files {
+        let name = path.strip_prefix(root).unwrap_or(&path).display().to_string();
+
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) => {
+                report.errors.push(format!("{}: {}", name, e));
+                continue;
+            }
+        };
+
+        match apply(action, &content, options) {
+            Ok(formatted) if formatted == content => report.unchanged += 1,
+            Ok(formatted) => match fs::write(&path, &formatted) {
+                Ok(_) => {
+                    report.changed += 1;
+                    if let Err(e) = history::record_change(vault, &path, Some(&content), &formatted, action.label()) {
+                        report.errors.push(format!("{}: {}", name, e));
+                    }
+                }
+                Err(e) => report.errors.push(format!("{}: {}", name, e)),
+            },
+            Err(e) => report.errors.push(format!("{}: {}", name, e)),
+        }
+    }
+
+    Ok(report)
+}
+
+pub(crate) fn collect_svgs(dir: &Path, recursive: bool, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+
+        if name.starts_with('.') {
+            continue;
+        }
+
+        if path.is_dir() {
+            if recursive {
+                collect_svgs(&path, recursive, files)?;
+            }
+        } else if name.to_lowercase().ends_with(".svg") {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+fn order_attributes(element: &mut Element, order: AttributeOrder) {
+    if order == AttributeOrder::Alphabetical {
+        element.attributes.sort_by(|a, b| {
+            let a_ns = a.name == "xmlns" || a.name.starts_with("xmlns:");
+            let b_ns = b.name == "xmlns" || b.name.starts_with("xmlns:");
+            b_ns.cmp(&a_ns).then_with(|| a.name.cmp(&b.name))
+        });
+    }
+
+    for node in &mut element.children {
+        if let Node::Element(child) = node {
+            order_attributes(child, order);
+        }
+    }
+}
+
+fn preserves_whitespace(element: &Element) -> bool {
+    TEXT_ELEMENTS.contains(&element.local_name())
+        || element.attr("xml:space") == Some("preserve")
+}
+
+fn strip_for_minify(element: &mut Element) {
+    let keep_whitespace = preserves_whitespace(element);
+
+    element.children.retain(|node| match node {
+        Node::Comment(_) => false,
+        Node::Text(text) => keep_whitespace || !text.trim().is_empty(),
+        _ => true,
+    });
+
+    for attr in &mut element.attributes {
+        attr.value = attr.value.split_whitespace().collect::<Vec<_>>().join(" ");
+    }
+
+    if keep_whitespace {
+        return;
+    }
+    for node in &mut element.children {
+        if let Node::Element(child) = node {
+            strip_for_minify(child);
+        }
+    }
+}
+
+// Plain and scientific decimals, e.g. "-1.5", ".5" or "1e-3"
+static NUMBER: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"-?(?:\d+\.?\d*|\.\d+)(?:[eE][-+]?\d+)?").unwrap());
+
+fn normalize_numbers(element: &mut Element, precision: usize) {
+    for attr in &mut element.attributes {
+        if attr.name == "d" {
+            attr.value = round_path(&attr.value, precision);
+        } else if NUMERIC_ATTRIBUTES.contains(&attr.name.as_str()) {
+            attr.value = round_numbers(&attr.value, precision);
+        }
+    }
+    for node in &mut element.children {
+        if let Node::Element(child) = node {
+            normalize_numbers(child, precision);
+        }
+    }
+}
+
+// Lists of numbers, e.g. points, viewBox or transform
+fn round_numbers(value: &str, precision: usize) -> String {
+    let mut out = String::new();
+    let mut last = 0;
+
+    for found in NUMBER.find_iter(value) {
+        out.push_str(&value[last..found.start()]);
+        push_number(&mut out, &round(found.as_str(), precision));
+        last = found.end();
+    }
+    out.push_str(&value[last..]);
+
+    out
+}
+
+// Path data, where arc flags are single digits that may be packed together
+// with the next number ("a1 1 0 011-1" has the flags 0 and 1, then 1 and -1)
+fn round_path(value: &str, precision: usize) -> String {
+    let mut out = String::new();
+    let mut command = ' ';
+    let mut argument = 0;
+    let mut pos = 0;
+
+    while let Some(c) = value[pos..].chars().next() {
+        if c.is_ascii_alphabetic() && c != 'e' && c != 'E' {
+            command = c;
+            argument = 0;
+            out.push(c);
+            pos += 1;
+            continue;
+        }
+
+        // rx ry rotation large-arc sweep x y
+        let is_flag = matches!(command, 'A' | 'a') && matches!(argument % 7, 3 | 4);
+        if is_flag && (c == '0' || c == '1') {
+            push_number(&mut out, &value[pos..pos + 1]);
+            argument += 1;
+            pos += 1;
+            continue;
+        }
+
+        match NUMBER.find_at(value, pos) {
+            Some(found) if found.start() == pos => {
+                push_number(&mut out, &round(found.as_str(), precision));
+                argument += 1;
+                pos = found.end();
+            }
+            _ => {
+                out.push(c);
+                pos += c.len_utf8();
+            }
+        }
+    }
+
+    out
+}
+
+fn round(number: &str, precision: usize) -> String {
+    number.parse::<f64>().map(|n| format_number(n, precision)).unwrap_or_else(|_| number.to_string())
+}
+
+// Numbers may be packed together ("1.5.5", "1-2"); keep them apart after rounding
+fn push_number(out: &mut String, number: &str) {
+    let touching = out.ends_with(|c: char| c.is_ascii_digit() || c == '.');
+    if touching && !number.starts_with('-') {
+        out.push(' ');
+    }
+    out.push_str(number);
+}
+
+// Fixed precision without trailing zeros, e.g. 1.500 -> 1.5 and -0.0001 -> 0
+pub fn format_number(value: f64, precision: usize) -> String {
+    let mut text = format!("{:.*}", precision, value);
+    if text.contains('.') {
+        text = text.trim_end_matches('0').trim_end_matches('.').to_string();
+    }
+    if text == "-0" {
+        text = "0".to_string();
+    }
+    text
+}
+
+fn write_document(document: &Document, options: &FormatOptions) -> String {
+    let mut out = String::new();
+    if let Some(declaration) = &document.declaration {
+        out.push_str(declaration);
+        out.push('\n');
+    }
+    for node in &document.prolog {
+        write_node(&mut out, node, options.quote_style);
+        out.push('\n');
+    }
+
+    write_pretty(&mut out, &document.root, 0, options);
+
+    for node in &document.epilog {
+        out.push('\n');
+        write_node(&mut out, node, options.quote_style);
+    }
+    out.push('\n');
+
+    out
+}
+
+fn indent(out: &mut String, depth: usize, options: &FormatOptions) {
+    if options.use_tabs {
+        out.push_str(&"\t".repeat(depth));
+    } else {
+        out.push_str(&" ".repeat(depth * options.indent));
+    }
+}
+
+fn write_pretty(out: &mut String, element: &Element, depth: usize, options: &FormatOptions) {
+    indent(out, depth, options);
+
+    // Mixed content and text elements are written as they are
+    let has_text = element.children.iter().any(|node| match node {
+        Node::Text(text) => !text.trim().is_empty(),
+        Node::CData(_) => true,
+        _ => false,
+    });
+    if has_text || preserves_whitespace(element) {
+        write_element(out, element, options.quote_style);
+        return;
+    }
+
+    let children: Vec<&Node> = element
+        .children
+        .iter()
+        .filter(|node| !matches!(node, Node::Text(_)))
+        .collect();
+
+    if children.is_empty() {
+        write_start_tag(out, element, true, options.quote_style);
+        return;
+    }
+
+    write_start_tag(out, element, false, options.quote_style);
+    out.push('\n');
+    for child in children {
+        match child {
+            Node::Element(child) => write_pretty(out, child, depth + 1, options),
+            other => {
+                indent(out, depth + 1, options);
+                write_node(out, other, options.quote_style);
+            }
+        }
+        out.push('\n');
+    }
+    indent(out, depth, options);
+    out.push_str("</");
+    out.push_str(&element.name);
+    out.push('>');
+}
+
+fn write_node(out: &mut String, node: &Node, quote: QuoteStyle) {
+    match node {
+        Node::Element(element) => write_element(out, element, quote),
+        Node::Text(text) => out.push_str(text),
+        Node::Comment(text) => {
+            out.push_str("<!--");
+            out.push_str(text);
+            out.push_str("-->");
+        }
+        Node::CData(text) => {
+            out.push_str("<![CDATA[");
+            out.push_str(text);
+            out.push_str("]]>");
+        }
+        Node::ProcessingInstruction { target, content } => {
+            out.push_str("<?");
+            out.push_str(target);
+            if let Some(content) = content {
+                out.push(' ');
+                out.push_str(content);
+            }
+            out.push_str("?>");
+        }
+        Node::Doctype(raw) => out.push_str(raw),
+    }
+}
+
+fn write_element(out: &mut String, element: &Element, quote: QuoteStyle) {
+    if element.children.is_empty() {
+        write_start_tag(out, element, true, quote);
+        return;
+    }
+
+    write_start_tag(out, element, false, quote);
+    for child in &element.children {
+        write_node(out, child, quote);
+    }
+    out.push_str("</");
+    out.push_str(&element.name);
+    out.push('>');
+}
+
+fn write_start_tag(out: &mut String, element: &Element, self_closing: bool, quote: QuoteStyle) {
+    out.push('<');
+    out.push_str(&element.name);
+    for attr in &element.attributes {
+        out.push(' ');
+        write_attribute(out, attr, quote);
+    }
+    out.push_str(if self_closing { "/>" } else { ">" });
+}
+
+// Values are raw source text, so only the quote character itself needs escaping
+fn write_attribute(out: &mut String, attr: &Attribute, quote: QuoteStyle) {
+    let (quote, value) = match quote {
+        QuoteStyle::Double => ('"', attr.value.replace('"', "&quot;")),
+        QuoteStyle::Single => ('\'', attr.value.replace('\'', "&apos;")),
+    };
+    out.push_str(&attr.name);
+    out.push('=');
+    out.push(quote);
+    out.push_str(&value);
+    out.push(quote);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packed_arc_flags_stay_separate() {
+        assert_eq!(round_path("M2 2a1 1 0 011-1", 3), "M2 2a1 1 0 0 1 1-1");
+        assert_eq!(round_path("a1 1 0 1 0 2 2 1 1 0 00.5.5", 3), "a1 1 0 1 0 2 2 1 1 0 0 0 0.5 0.5");
+        assert_eq!(round_path("M0 0A5 5 30 1 1 10.1234 0z", 2), "M0 0A5 5 30 1 1 10.12 0z");
+    }
+
+    #[test]
+    fn packed_numbers_stay_separate() {
+        assert_eq!(round_path("M1.5.5L-.25-1e-1", 2), "M1.5 0.5L-0.25-0.1");
+        assert_eq!(round_numbers("0 0 24.0004 24", 3), "0 0 24 24");
+    }
+
+    #[test]
+    fn canonicalize_keeps_minified_arcs() {
+        let source = r#"<svg xmlns="http://www.w3.org/2000/svg"><path d="M2 2a1 1 0 011-1"/></svg>"#;
+        let canonical = canonicalize(source, &FormatOptions::default()).unwrap();
+        assert!(canonical.contains(r#"d="M2 2a1 1 0 0 1 1-1""#), "{}", canonical);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::utils::embedded_metadata::{self, EmbeddedMetadata};
 use svgtypes::{Color, ViewBox, PathParser};
 use std::path::Path;
 use std::str::FromStr;
@@ -10,6 +11,7 @@ pub struct SvgInfo {
     pub path_count: usize,
     pub colors_used: Vec<Color>,
     pub total_path_commands: usize,
+    pub embedded: EmbeddedMetadata,
 }
 
 pub fn parse_svg_info(svg_path: &Path) -> Result<SvgInfo, Box<dyn std::error::Error>> {
@@ -60,6 +62,7 @@ pub fn parse_svg_info(svg_path: &Path) -> Result<SvgInfo, Box<dyn std::error::Er
         path_count,
         colors_used,
         total_path_commands,
+        embedded: embedded_metadata::read(&svg_content),
     })
 }
 
@@ -0,0 +1,87 @@
+use resvg::tiny_skia::{Pixmap, Transform};
+use resvg::usvg::{Options, Tree};
+
+// Keep rasterized previews within what GPUs accept as a texture
+pub const MAX_RASTER_SIZE: u32 = 4096;
+
+pub fn parse_tree(svg: &str) -> Result<Tree, String> {
+    Tree::from_str(svg, &Options::default()).map_err(|e| e.to_string())
+}
+
+// Rasterize at `scale` times the intrinsic size of the document.
+// The returned pixmap holds premultiplied RGBA.
+pub fn rasterize_tree(tree: &Tree, scale: f32) -> Result<Pixmap, String> {
+    let size = tree.size();
+
+    let largest_side = size.width().max(size.height()) * scale;
+    let scale = if largest_side > MAX_RASTER_SIZE as f32 {
+        scale * MAX_RASTER_SIZE as f32 / largest_side
+    } else {
+        scale
+    };
+
+    let width = ((size.width() * scale).ceil() as u32).max(1);
+    let height = ((size.height() * scale).ceil() as u32).max(1);
+    let mut pixmap = Pixmap::new(width, height).ok_or("Image has no size")?;
+
+    resvg::render(tree, Transform::from_scale(scale, scale), &mut pixmap.as_mut());
+
+    Ok(pixmap)
+}
+
+pub fn to_color_image(pixmap: &Pixmap) -> egui::ColorImage {
+    egui::ColorImage::from_rgba_premultiplied(
+        [pixmap.width() as usize, pixmap.height() as usize],
+        pixmap.data(),
+    )
+}
+
+// Rasterize into a square of `size` pixels, centered and scaled to fit,
+// so documents with different dimensions can be compared pixel by pixel
+pub fn rasterize_fit(tree: &Tree, size: u32) -> Result<Pixmap, String> {
+    let tree_size = tree.size();
+    let scale = size as f32 / tree_size.width().max(tree_size.height());
+    let dx = (size as f32 - tree_size.width() * scale) / 2.0;
+    let dy = (size as f32 - tree_size.height() * scale) / 2.0;
+
+    let mut pixmap = Pixmap::new(size, size).ok_or("Image has no size")?;
+    resvg::render(
+        tree,
+        Transform::from_scale(scale, scale).post_translate(dx, dy),
+        &mut pixmap.as_mut(),
+    );
+
+    Ok(pixmap)
+}
+
+// Highlight pixels that differ between two equally sized pixmaps. Unchanged
+// pixels are dimmed so the highlights stand out. Returns the image and the
+// number of changed pixels.
+pub fn difference_image(before: &Pixmap, after: &Pixmap, tolerance: u8) -> (egui::ColorImage, usize) {
+    let mut pixels = Vec::with_capacity((after.width() * after.height()) as usize);
+    let mut changed = 0;
+
+    for (a, b) in before.pixels().iter().zip(after.pixels()) {
+        let delta = [
+            a.red().abs_diff(b.red()),
+            a.green().abs_diff(b.green()),
+            a.blue().abs_diff(b.blue()),
+            a.alpha().abs_diff(b.alpha()),
+        ]
+        .into_iter()
+        .max()
+        .unwrap_or(0);
+
+        if delta > tolerance {
+            changed += 1;
+            pixels.push(egui::Color32::from_rgb(255, 0, 200));
+        } else {
+            let gray = ((b.red() as u16 + b.green() as u16 + b.blue() as u16) / 3) as u8;
+            let alpha = b.alpha() / 3;
+            pixels.push(egui::Color32::from_rgba_premultiplied(gray / 3, gray / 3, gray / 3, alpha));
+        }
+    }
+
+    let image = egui::ColorImage::new([after.width() as usize, after.height() as usize], pixels);
+    (image, changed)
+}
@@ -0,0 +1,169 @@
+use crate::utils::svg_document::{self, Element};
+use std::collections::HashSet;
+use std::str::FromStr;
+use svgtypes::{Length, Paint, PathParser, ViewBox};
+
+pub const SVG_NAMESPACE: &str = "http://www.w3.org/2000/svg";
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Clone, Debug)]
+pub struct Diagnostic {
+    pub line: usize,
+    pub column: usize,
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Ln {}, Col {}: {}", self.line, self.column, self.message)
+    }
+}
+
+// Elements defined by SVG 1.1 / SVG 2 that renderers know about
+const SVG_ELEMENTS: &[&str] = &[
+    "a", "animate", "animateMotion", "animateTransform", "circle", "clipPath", "defs", "desc",
+    "ellipse", "feBlend", "feColorMatrix", "feComponentTransfer", "feComposite",
+    "feConvolveMatrix", "feDiffuseLighting", "feDisplacementMap", "feDistantLight",
+    "feDropShadow", "feFlood", "feFuncA", "feFuncB", "feFuncG", "feFuncR", "feGaussianBlur",
+    "feImage", "feMerge", "feMergeNode", "feMorphology", "feOffset", "fePointLight",
+    "feSpecularLighting", "feSpotLight", "feTile", "feTurbulence", "filter", "font", "font-face",
+    "foreignObject", "g", "glyph", "image", "line", "linearGradient", "marker", "mask",
+    "metadata", "mpath", "path", "pattern", "polygon", "polyline", "radialGradient", "rect",
+    "script", "set", "stop", "style", "svg", "switch", "symbol", "text", "textPath", "title",
+    "tspan", "use", "view",
+];
+
+// Attributes each element can't do without
+const REQUIRED_ATTRIBUTES: &[(&str, &[&str])] = &[
+    ("path", &["d"]),
+    ("circle", &["r"]),
+    ("polygon", &["points"]),
+    ("polyline", &["points"]),
+];
+
+const LENGTH_ATTRIBUTES: &[&str] = &[
+    "x", "y", "width", "height", "cx", "cy", "r", "rx", "ry", "x1", "y1", "x2", "y2",
+    "stroke-width",
+];
+
+const PAINT_ATTRIBUTES: &[&str] = &["fill", "stroke"];
+
+pub fn has_errors(diagnostics: &[Diagnostic]) -> bool {
+    diagnostics.iter().any(|d| d.severity == Severity::Error)
+}
+
+// Check that the source is well-formed XML and a structurally sound SVG document
+pub fn validate(source: &str) -> Vec<Diagnostic> {
+    let document = match svg_document::parse(source) {
+        Ok(document) => document,
+        Err(e) => {
+            return vec![Diagnostic {
+                line: e.line,
+                column: e.column,
+                severity: Severity::Error,
+                message: e.message,
+            }];
+        }
+    };
+
+    let mut diagnostics = Vec::new();
+    let root = &document.root;
+
+    let mut report = |pos: usize, severity: Severity, message: String| {
+        let (line, column) = svg_document::line_col(source, pos);
+        diagnostics.push(Diagnostic { line, column, severity, message });
+    };
+
+    if root.local_name() != "svg" {
+        report(root.pos, Severity::Error, format!("Root element must be <svg>, found <{}>", root.name));
+    }
+
+    let default_namespace = root.attr("xmlns");
+    if root.prefix().is_none() && default_namespace != Some(SVG_NAMESPACE) {
+        report(
+            root.pos,
+            Severity::Error,
+            format!("Missing or wrong SVG namespace, expected xmlns=\"{}\"", SVG_NAMESPACE),
+        );
+    }
+
+    check_element(root, &HashSet::new(), &mut report);
+
+    diagnostics.sort_by_key(|d| (d.line, d.column));
+    diagnostics
+}
+
+fn check_element(
+    element: &Element,
+    declared_prefixes: &HashSet<String>,
+    report: &mut impl FnMut(usize, Severity, String),
+) {
+    // Namespace prefixes are scoped to the element that declares them
+    let mut scope = declared_prefixes.clone();
+    for attr in &element.attributes {
+        if let Some(prefix) = attr.name.strip_prefix("xmlns:") {
+            scope.insert(prefix.to_string());
+        }
+    }
+    scope.insert("xml".to_string());
+
+    if let Some(prefix) = element.prefix() {
+        if !scope.contains(prefix) {
+            report(element.pos, Severity::Error, format!("Undeclared namespace prefix '{}'", prefix));
+        }
+    } else if !SVG_ELEMENTS.contains(&element.name.as_str()) {
+        report(element.pos, Severity::Warning, format!("Unknown SVG element <{}>", element.name));
+    }
+
+    for (name, required) in REQUIRED_ATTRIBUTES {
+        if element.name == *name {
+            for attr in required.iter() {
+                if element.attr(attr).is_none() {
+                    report(element.pos, Severity::Warning, format!("<{}> is missing the '{}' attribute", name, attr));
+                }
+            }
+        }
+    }
+
+    for attr in &element.attributes {
+        if let Some((prefix, _)) = attr.name.split_once(':') {
+            if prefix != "xmlns" && !scope.contains(prefix) {
+                report(attr.pos, Severity::Error, format!("Undeclared namespace prefix '{}'", prefix));
+            }
+            continue;
+        }
+
+        let value = attr.value.trim();
+        let name = attr.name.as_str();
+
+        if name == "d" && element.name == "path" {
+            if let Some(e) = PathParser::from(value).find_map(|segment| segment.err()) {
+                report(attr.pos, Severity::Error, format!("Invalid path data: {}", e));
+            }
+        } else if name == "viewBox" {
+            if ViewBox::from_str(value).is_err() {
+                report(attr.pos, Severity::Error, format!("Invalid viewBox '{}'", value));
+            }
+        } else if LENGTH_ATTRIBUTES.contains(&name) {
+            if Length::from_str(value).is_err() {
+                report(attr.pos, Severity::Warning, format!("Invalid length for '{}': '{}'", name, value));
+            }
+        } else if PAINT_ATTRIBUTES.contains(&name) && Paint::from_str(value).is_err() {
+            report(attr.pos, Severity::Warning, format!("Invalid paint for '{}': '{}'", name, value));
+        }
+    }
+
+    for child in element.child_elements() {
+        // Foreign content (e.g. inside <foreignObject> or <metadata>) isn't SVG
+        if matches!(element.name.as_str(), "foreignObject" | "metadata") {
+            continue;
+        }
+        check_element(child, &scope, report);
+    }
+}
@@ -0,0 +1,259 @@
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::UNIX_EPOCH;
+use svgtypes::Color;
+
+// Name of the folder holding the generated variants, next to the sources
+pub const VARIANT_DIR: &str = "dark";
+
+// Bookkeeping file inside the variant folder, used to skip unchanged sources
+const MANIFEST_FILE: &str = ".variants.json";
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct ColorMapping {
+    pub light: String,
+    pub dark: String,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct VariantManifest {
+    mapping: Vec<ColorMapping>,
+    // Source path relative to the folder -> modification time in seconds
+    sources: BTreeMap<String, u64>,
+}
+
+#[derive(Default)]
+pub struct VariantReport {
+    pub generated: usize,
+    pub unchanged: usize,
+    pub removed: usize,
+    pub errors: Vec<String>,
+}
+
+impl VariantReport {
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "{} generated, {} unchanged, {} removed",
+            self.generated, self.unchanged, self.removed
+        );
+        if !self.errors.is_empty() {
+            summary.push_str(&format!(", {} failed", self.errors.len()));
+        }
+        summary
+    }
+}
+
+// Replace every color of the SVG that appears in the mapping table.
+// Colors are compared by value, so "#FFF", "#ffffff" and "white" all match.
+pub fn apply_mapping(content: &str, mapping: &[ColorMapping]) -> String {
+    let table: Vec<(Color, String)> = mapping
+        .iter()
+        .filter_map(|m| Color::from_str(m.light.trim()).ok().map(|c| (c, m.dark.trim().to_string())))
+        .collect();
+
+    if table.is_empty() {
+        return content.to_string();
+    }
+
+    let lookup = |value: &str| -> Option<String> {
+        let color = Color::from_str(value).ok()?;
+        let dark = table
+            .iter()
+            .find(|(light, _)| light.red == color.red && light.green == color.green && light.blue == color.blue)
+            .map(|(_, dark)| dark.clone())?;
+        // Keep the transparency of "#rrggbbaa" and "#rgba" colors
+        match Color::from_str(&dark) {
+            Ok(parsed) if color.alpha != 255 && parsed.alpha == 255 => Some(format!(
+                "#{:02x}{:02x}{:02x}{:02x}",
+                parsed.red, parsed.green, parsed.blue, color.alpha
+            )),
+            _ => Some(dark),
+        }
+    };
+
+    // Colors of paint attributes and of style declarations (inline or in <style> blocks).
+    // Values like url(#fade) and href="#id" are references, not colors, and stay as they are.
+    let paint_re = Regex::new(
+        r#"((?:fill|stroke|stop-color|color|flood-color|lighting-color)(?:\s*=\s*["']|\s*:\s*))(#(?:[0-9a-fA-F]{8}|[0-9a-fA-F]{6}|[0-9a-fA-F]{3,4})\b|[a-zA-Z]+)"#,
+    )
+    .unwrap();
+    paint_re
+        .replace_all(content, |caps: &Captures| match lookup(&caps[2]) {
+            Some(dark) => format!("{}{}", &caps[1], dark),
+            None => caps[0].to_string(),
+        })
+        .into_owned()
+}
+
+// Where the variant of the given folder lives
+pub fn variant_root(source_root: &Path) -> PathBuf {
+    source_root.join(VARIANT_DIR)
+}
+
+// A folder written by generate_variants. Vault scans skip it, so variants aren't taken for assets.
+pub fn is_variant_dir(path: &Path) -> bool {
+    path.file_name().is_some_and(|name| name == VARIANT_DIR) && path.join(MANIFEST_FILE).is_file()
+}
+
+// Write a parallel dark/ tree for every SVG below source_root.
+// Sources that didn't change since the last run (and whose mapping didn't change) are skipped.
+pub fn generate_variants(
+    source_root: &Path,
+    mapping: &[ColorMapping],
+) -> Result<VariantReport, Box<dyn std::error::Error>> {
+    let output_root = variant_root(source_root);
+    fs::create_dir_all(&output_root)?;
+
+    let manifest_path = output_root.join(MANIFEST_FILE);
+    let previous: VariantManifest = fs::read_to_string(&manifest_path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+    let mapping_changed = previous.mapping != mapping;
+
+    let mut sources = Vec::new();
+    collect_sources(source_root, source_root, &mut sources)?;
+
+    let mut report = VariantReport::default();
+    let mut manifest = VariantManifest {
+        mapping: mapping.to_vec(),
+        sources: BTreeMap::new(),
+    };
+
+    for source in sources {
+        let relative = source.strip_prefix(source_root)?.to_path_buf();
+        let key = relative.to_string_lossy().replace('\\', "/");
+        let target = output_root.join(&relative);
+        let modified = modified_secs(&source);
+
+        let up_to_date = !mapping_changed
+            && target.exists()
+            && previous.sources.get(&key) == Some(&modified);
+
+        if up_to_date {
+            report.unchanged += 1;
+            manifest.sources.insert(key, modified);
+            continue;
+        }
+
+        let result = fs::read_to_string(&source).map_err(|e| e.to_string()).and_then(|content| {
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+            }
+            fs::write(&target, apply_mapping(&content, mapping)).map_err(|e| e.to_string())
+        });
+
+        match result {
+            Ok(_) => {
+                report.generated += 1;
+                manifest.sources.insert(key, modified);
+            }
+            Err(e) => {
+                report.errors.push(format!("{}: {}", key, e));
+                // Keep the old variant around, and retry on the next run
+                if previous.sources.contains_key(&key) {
+                    manifest.sources.insert(key, 0);
+                }
+            }
+        }
+    }
+
+    // Drop variants whose source has been deleted or renamed since the last run
+    for key in previous.sources.keys() {
+        if !manifest.sources.contains_key(key) && !source_root.join(key).exists() {
+            let stale = output_root.join(key);
+            if stale.exists() && fs::remove_file(&stale).is_ok() {
+                report.removed += 1;
+            }
+        }
+    }
+
+    fs::write(&manifest_path, serde_json::to_string_pretty(&manifest)?)?;
+
+    Ok(report)
+}
+
+fn collect_sources(root: &Path, dir: &Path, sources: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+
+        if name.starts_with('.') {
+            continue;
+        }
+
+        if path.is_dir() {
+            // Never treat the generated tree as a source
+            if dir == root && name == VARIANT_DIR {
+                continue;
+            }
+            collect_sources(root, &path, sources)?;
+        } else if name.to_lowercase().ends_with(".svg") {
+            sources.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+fn modified_secs(path: &Path) -> u64 {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::file_finder::{scan_directory_recursive, FileFilter};
+
+    fn mapping(light: &str, dark: &str) -> Vec<ColorMapping> {
+        vec![ColorMapping { light: light.to_string(), dark: dark.to_string() }]
+    }
+
+    #[test]
+    fn recolors_paint_attributes_and_styles() {
+        let source = r##"<rect fill="#FFF" style="stroke: white"/><style>.a { fill: #ffffff }</style>"##;
+        assert_eq!(
+            apply_mapping(source, &mapping("#fff", "#111")),
+            r##"<rect fill="#111" style="stroke: #111"/><style>.a { fill: #111 }</style>"##
+        );
+    }
+
+    #[test]
+    fn leaves_references_alone() {
+        let source = r##"<rect fill="url(#add)"/><use href="#add"/><path clip-path="url(#add)"/>"##;
+        assert_eq!(apply_mapping(source, &mapping("#add", "#123")), source);
+    }
+
+    #[test]
+    fn keeps_alpha() {
+        let source = r##"<rect fill="#ffffff80" stroke="#fff8"/>"##;
+        assert_eq!(
+            apply_mapping(source, &mapping("#fff", "#000000")),
+            r##"<rect fill="#00000080" stroke="#00000088"/>"##
+        );
+    }
+
+    #[test]
+    fn vault_scans_skip_generated_variants() {
+        let root = std::env::temp_dir().join(format!("variants-test-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("add.svg"), r##"<svg xmlns="http://www.w3.org/2000/svg" fill="#fff"/>"##).unwrap();
+
+        generate_variants(&root, &mapping("#fff", "#111")).unwrap();
+        assert!(is_variant_dir(&variant_root(&root)));
+        let found = scan_directory_recursive(&root.to_string_lossy(), FileFilter::Svg).unwrap();
+        assert_eq!(found.len(), 1);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
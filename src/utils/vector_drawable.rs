@@ -0,0 +1,340 @@
+use crate::utils::svg_format::format_number;
+use resvg::tiny_skia::{Path, PathSegment, Transform};
+use resvg::usvg::{self, FillRule, Group, LineCap, LineJoin, Node, Paint, SpreadMethod, Tree};
+
+const ANDROID_NAMESPACE: &str = "http://schemas.android.com/apk/res/android";
+const AAPT_NAMESPACE: &str = "http://schemas.android.com/aapt";
+
+// Neither format can fade a group as a whole, shared with the PDF writer
+pub(crate) const GROUP_OPACITY: &str = "Group opacity is applied to each path, overlapping paths may look darker";
+
+pub struct VectorDrawable {
+    pub xml: String,
+    // What changed or got lost on the way, each once
+    pub issues: Vec<String>,
+}
+
+fn num(value: f32) -> String {
+    format_number(value as f64, 3)
+}
+
+fn argb(color: usvg::Color, alpha: f32) -> String {
+    let alpha = (alpha.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!("#{:02X}{:02X}{:02X}{:02X}", alpha, color.red, color.green, color.blue)
+}
+
+// Average scale of a transform, for stroke widths and radii
+fn mean_scale(ts: Transform) -> f32 {
+    (ts.sx * ts.sy - ts.kx * ts.ky).abs().sqrt()
+}
+
+fn is_uniform(ts: Transform) -> bool {
+    let (sx, sy) = ts.get_scale();
+    (sx - sy).abs() < 0.001 && (ts.sx * ts.kx + ts.ky * ts.sy).abs() < 0.001
+}
+
+fn path_data(path: &Path) -> String {
+    let mut parts = Vec::new();
+    for segment in path.segments() {
+        parts.push(match segment {
+            PathSegment::MoveTo(p) => format!("M{},{}", num(p.x), num(p.y)),
+            PathSegment::LineTo(p) => format!("L{},{}", num(p.x), num(p.y)),
+            PathSegment::QuadTo(c, p) => format!("Q{},{} {},{}", num(c.x), num(c.y), num(p.x), num(p.y)),
+            PathSegment::CubicTo(c1, c2, p) => {
+                format!("C{},{} {},{} {},{}", num(c1.x), num(c1.y), num(c2.x), num(c2.y), num(p.x), num(p.y))
+            }
+            PathSegment::Close => "Z".to_string(),
+        });
+    }
+    parts.join(" ")
+}
+
+struct Writer {
+    out: String,
+    issues: Vec<String>,
+    uses_gradients: bool,
+}
+
+impl Writer {
+    fn issue(&mut self, issue: impl Into<String>) {
+        let issue = issue.into();
+        if !self.issues.contains(&issue) {
+            self.issues.push(issue);
+        }
+    }
+
+    fn line(&mut self, depth: usize, text: &str) {
+        self.out.push_str(&"    ".repeat(depth));
+        self.out.push_str(text);
+        self.out.push('\n');
+    }
+
+    fn group(&mut self, group: &Group, depth: usize, opacity: f32) {
+        for child in group.children() {
+            match child {
+                Node::Group(child) => {
+                    if child.opacity().get() < 1.0 && child.children().len() > 1 {
+                        self.issue(GROUP_OPACITY);
+                    }
+                    let opacity = opacity * child.opacity().get();
+
+                    // Clip paths are the only reason to keep a group, transforms are baked into the paths
+                    match child.clip_path() {
+                        Some(clip) => {
+                            self.line(depth, "<group>");
+                            self.clip_path(clip, child.abs_transform(), depth + 1);
+                            self.group(child, depth + 1, opacity);
+                            self.line(depth, "</group>");
+                        }
+                        None => self.group(child, depth, opacity),
+                    }
+                }
+                Node::Path(path) => self.path(path, depth, opacity),
+                _ => {}
+            }
+        }
+    }
+
+    fn clip_path(&mut self, clip: &usvg::ClipPath, ts: Transform, depth: usize) {
+        if clip.clip_path().is_some() {
+            self.issue("Nested clip paths are reduced to the outermost one");
+        }
+        let mut data = Vec::new();
+        collect_clip_data(clip.root(), ts.pre_concat(clip.transform()), &mut data, self);
+        self.line(depth, &format!("<clip-path android:pathData=\"{}\" />", data.join(" ")));
+    }
+
+    fn path(&mut self, path: &usvg::Path, depth: usize, opacity: f32) {
+        if !path.is_visible() {
+            return;
+        }
+        let ts = path.abs_transform();
+        let Some(data) = path.data().clone().transform(ts) else {
+            return;
+        };
+
+        let mut attributes = vec![format!("android:pathData=\"{}\"", path_data(&data))];
+        let mut gradients = Vec::new();
+
+        if let Some(fill) = path.fill() {
+            match fill.paint() {
+                Paint::Color(color) => attributes.push(format!("android:fillColor=\"{}\"", argb(*color, 1.0))),
+                Paint::Pattern(_) => self.issue("Pattern fills are dropped"),
+                gradient => gradients.push(("android:fillColor", self.gradient(gradient, ts))),
+            }
+            let alpha = fill.opacity().get() * opacity;
+            if alpha < 1.0 {
+                attributes.push(format!("android:fillAlpha=\"{}\"", num(alpha)));
+            }
+            if fill.rule() == FillRule::EvenOdd {
+                attributes.push("android:fillType=\"evenOdd\"".to_string());
+            }
+        }
+
+        if let Some(stroke) = path.stroke() {
+            match stroke.paint() {
+                Paint::Color(color) => attributes.push(format!("android:strokeColor=\"{}\"", argb(*color, 1.0))),
+                Paint::Pattern(_) => self.issue("Pattern strokes are dropped"),
+                gradient => gradients.push(("android:strokeColor", self.gradient(gradient, ts))),
+            }
+            if !is_uniform(ts) {
+                self.issue("Strokes under a stretched or skewed transform get an average width");
+            }
+            attributes.push(format!("android:strokeWidth=\"{}\"", num(stroke.width().get() * mean_scale(ts))));
+            let alpha = stroke.opacity().get() * opacity;
+            if alpha < 1.0 {
+                attributes.push(format!("android:strokeAlpha=\"{}\"", num(alpha)));
+            }
+            match stroke.linecap() {
+                LineCap::Butt => {}
+                LineCap::Round => attributes.push("android:strokeLineCap=\"round\"".to_string()),
+                LineCap::Square => attributes.push("android:strokeLineCap=\"square\"".to_string()),
+            }
+            match stroke.linejoin() {
+                LineJoin::Miter | LineJoin::MiterClip => {
+                    attributes.push(format!("android:strokeMiterLimit=\"{}\"", num(stroke.miterlimit().get())))
+                }
+                LineJoin::Round => attributes.push("android:strokeLineJoin=\"round\"".to_string()),
+                LineJoin::Bevel => attributes.push("android:strokeLineJoin=\"bevel\"".to_string()),
+            }
+            if stroke.dasharray().is_some() {
+                self.issue("Dashed strokes are drawn solid");
+            }
+        }
+
+        if path.paint_order() == usvg::PaintOrder::StrokeAndFill && path.fill().is_some() && path.stroke().is_some() {
+            self.issue("paint-order is ignored, strokes are drawn above fills");
+        }
+
+        let indent = "    ".repeat(depth + 1);
+        let attributes = attributes.join(&format!("\n{}", indent));
+        if gradients.is_empty() {
+            self.line(depth, &format!("<path\n{}{} />", indent, attributes));
+            return;
+        }
+
+        self.line(depth, &format!("<path\n{}{}>", indent, attributes));
+        for (name, gradient) in gradients {
+            self.line(depth + 1, &format!("<aapt:attr name=\"{}\">", name));
+            for line in gradient {
+                self.line(depth + 2, &line);
+            }
+            self.line(depth + 1, "</aapt:attr>");
+        }
+        self.line(depth, "</path>");
+    }
+
+    // <gradient> lines in viewport coordinates, `ts` being the transform of the path
+    fn gradient(&mut self, paint: &Paint, ts: Transform) -> Vec<String> {
+        self.uses_gradients = true;
+        self.issue("Gradients need Android 7.0 (API 24) or later");
+
+        let (mut attributes, base) = match paint {
+            Paint::LinearGradient(gradient) => {
+                let ts = ts.pre_concat(gradient.transform());
+                if !is_uniform(ts) {
+                    self.issue("Stretched or skewed linear gradients are approximated");
+                }
+                let mut points = [(gradient.x1(), gradient.y1()), (gradient.x2(), gradient.y2())]
+                    .map(|(x, y)| resvg::tiny_skia::Point::from_xy(x, y));
+                ts.map_points(&mut points);
+                (
+                    vec![
+                        "android:type=\"linear\"".to_string(),
+                        format!("android:startX=\"{}\"", num(points[0].x)),
+                        format!("android:startY=\"{}\"", num(points[0].y)),
+                        format!("android:endX=\"{}\"", num(points[1].x)),
+                        format!("android:endY=\"{}\"", num(points[1].y)),
+                    ],
+                    &***gradient,
+                )
+            }
+            Paint::RadialGradient(gradient) => {
+                let ts = ts.pre_concat(gradient.transform());
+                if !is_uniform(ts) {
+                    self.issue("Elliptical radial gradients are drawn as circles");
+                }
+                if gradient.fx() != gradient.cx() || gradient.fy() != gradient.cy() {
+                    self.issue("Radial gradient focal points are ignored");
+                }
+                let mut center = resvg::tiny_skia::Point::from_xy(gradient.cx(), gradient.cy());
+                ts.map_point(&mut center);
+                (
+                    vec![
+                        "android:type=\"radial\"".to_string(),
+                        format!("android:centerX=\"{}\"", num(center.x)),
+                        format!("android:centerY=\"{}\"", num(center.y)),
+                        format!("android:gradientRadius=\"{}\"", num(gradient.r().get() * mean_scale(ts))),
+                    ],
+                    &***gradient,
+                )
+            }
+            _ => return Vec::new(),
+        };
+
+        attributes.push(match base.spread_method() {
+            SpreadMethod::Pad => "android:tileMode=\"clamp\"".to_string(),
+            SpreadMethod::Reflect => "android:tileMode=\"mirror\"".to_string(),
+            SpreadMethod::Repeat => "android:tileMode=\"repeat\"".to_string(),
+        });
+
+        let mut lines = vec!["<gradient".to_string()];
+        lines.extend(attributes.into_iter().map(|attribute| format!("    {}", attribute)));
+        if let Some(last) = lines.last_mut() {
+            last.push('>');
+        }
+        for stop in base.stops() {
+            lines.push(format!(
+                "    <item android:offset=\"{}\" android:color=\"{}\" />",
+                num(stop.offset().get()),
+                argb(stop.color(), stop.opacity().get())
+            ));
+        }
+        lines.push("</gradient>".to_string());
+        lines
+    }
+}
+
+fn collect_clip_data(group: &Group, ts: Transform, data: &mut Vec<String>, writer: &mut Writer) {
+    for child in group.children() {
+        match child {
+            Node::Path(path) => {
+                if path.fill().is_some_and(|fill| fill.rule() == FillRule::EvenOdd) {
+                    writer.issue("clip-rule=\"evenodd\" is ignored");
+                }
+                if let Some(transformed) = path.data().clone().transform(ts) {
+                    data.push(path_data(&transformed));
+                }
+            }
+            Node::Group(child) => collect_clip_data(child, ts.pre_concat(child.transform()), data, writer),
+            _ => {}
+        }
+    }
+}
+
+// Convert a parsed document. Transforms are baked into the path data, so the
+// drawable is a flat list of paths plus a group for every clip path.
+pub fn convert(tree: &Tree) -> VectorDrawable {
+    let mut writer = Writer { out: String::new(), issues: Vec::new(), uses_gradients: false };
+    writer.group(tree.root(), 1, 1.0);
+
+    let size = tree.size();
+    let mut header = format!("<vector xmlns:android=\"{}\"", ANDROID_NAMESPACE);
+    if writer.uses_gradients {
+        header.push_str(&format!("\n    xmlns:aapt=\"{}\"", AAPT_NAMESPACE));
+    }
+    header.push_str(&format!(
+        "\n    android:width=\"{}dp\"\n    android:height=\"{}dp\"\n    android:viewportWidth=\"{}\"\n    android:viewportHeight=\"{}\">\n",
+        num(size.width()),
+        num(size.height()),
+        num(size.width()),
+        num(size.height())
+    ));
+
+    VectorDrawable {
+        xml: format!("{}{}</vector>\n", header, writer.out),
+        issues: writer.issues,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::svg_render;
+
+    fn convert_source(source: &str) -> VectorDrawable {
+        convert(&svg_render::parse_tree(source).unwrap())
+    }
+
+    #[test]
+    fn bakes_transforms_into_paths() {
+        let drawable = convert_source(
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="24" height="16"><g transform="translate(10 0)"><path d="M2 2L6 2L6 6Z" fill="#ff0000" fill-opacity="0.5" stroke="#0000ff" stroke-width="2" stroke-linecap="round"/></g></svg>"##,
+        );
+        let xml = drawable.xml;
+        assert!(xml.contains("android:width=\"24dp\"\n    android:height=\"16dp\""));
+        assert!(xml.contains("android:pathData=\"M12,2 L16,2 L16,6 Z\""));
+        assert!(xml.contains("android:fillColor=\"#FFFF0000\""));
+        assert!(xml.contains("android:fillAlpha=\"0.5\""));
+        assert!(xml.contains("android:strokeColor=\"#FF0000FF\""));
+        assert!(xml.contains("android:strokeWidth=\"2\""));
+        assert!(xml.contains("android:strokeLineCap=\"round\""));
+        assert!(!xml.contains("<group>"));
+        assert!(!xml.contains(AAPT_NAMESPACE));
+        assert!(drawable.issues.is_empty());
+    }
+
+    #[test]
+    fn reports_what_gets_lost() {
+        let drawable = convert_source(
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="8" height="8"><linearGradient id="g"><stop offset="0" stop-color="#000"/><stop offset="1" stop-color="#fff"/></linearGradient><g opacity="0.5"><path d="M0 0H4V4Z" fill="url(#g)"/><path d="M4 4H8V8Z" stroke="#000" stroke-dasharray="1 1"/></g></svg>"##,
+        );
+        assert!(drawable.xml.contains(AAPT_NAMESPACE));
+        assert!(drawable.xml.contains("<aapt:attr name=\"android:fillColor\">"));
+        assert!(drawable.xml.contains("android:strokeAlpha=\"0.5\""));
+        assert_eq!(
+            drawable.issues,
+            [GROUP_OPACITY, "Gradients need Android 7.0 (API 24) or later", "Dashed strokes are drawn solid"]
+        );
+    }
+}